@@ -0,0 +1,151 @@
+//! Example plugins, built as a `cdylib` so hosts and tests can load them.
+//!
+//! `amp` multiplies its input by a gain control, `invert` flips the sign of
+//! its input, and `delay` delays its input by 64 frames and reports that as
+//! its latency.  Build with `cargo build --example amp`; the library ends up in
+//! `target/debug/examples/`.
+
+use lv2_raw::*;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+
+struct Amp {
+    gain: *const f32,
+    input: *const f32,
+    output: *mut f32,
+}
+
+impl Plugin for Amp {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:examples:amp");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Control, PortDirection::Input),
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+    ];
+
+    fn instantiate(_rate: f64, _bundle_path: &CStr, _features: Features<'_>) -> Option<Self> {
+        Some(Amp {
+            gain: ptr::null(),
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.gain = data as *const f32,
+            1 => self.input = data as *const f32,
+            2 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        let n = sample_count as usize;
+        if n == 0 {
+            return;
+        }
+        unsafe {
+            let gain = *self.gain;
+            // Input and output may be the same buffer, so go through pointers.
+            for i in 0..n {
+                *self.output.add(i) = *self.input.add(i) * gain;
+            }
+        }
+    }
+}
+
+struct Invert {
+    input: *const f32,
+    output: *mut f32,
+}
+
+impl Plugin for Invert {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:examples:invert");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+    ];
+
+    fn instantiate(_rate: f64, _bundle_path: &CStr, _features: Features<'_>) -> Option<Self> {
+        Some(Invert {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        unsafe {
+            for i in 0..sample_count as usize {
+                *self.output.add(i) = -*self.input.add(i);
+            }
+        }
+    }
+}
+
+/// The latency of `Delay`, in frames.
+const DELAY: usize = 64;
+
+struct Delay {
+    input: *const f32,
+    output: *mut f32,
+    latency: LatencyPort,
+    line: [f32; DELAY],
+    position: usize,
+}
+
+impl Plugin for Delay {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:examples:delay");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+        (PortType::Control, PortDirection::Output),
+    ];
+
+    fn instantiate(_rate: f64, _bundle_path: &CStr, _features: Features<'_>) -> Option<Self> {
+        Some(Delay {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+            latency: LatencyPort::new(),
+            line: [0.0; DELAY],
+            position: 0,
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.output = data as *mut f32,
+            2 => unsafe { self.latency.connect(data) },
+            _ => {}
+        }
+    }
+
+    fn activate(&mut self) {
+        self.line = [0.0; DELAY];
+        self.position = 0;
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        self.latency.report(DELAY as u32);
+        unsafe {
+            for i in 0..sample_count as usize {
+                let input = *self.input.add(i);
+                *self.output.add(i) = self.line[self.position];
+                self.line[self.position] = input;
+                self.position = (self.position + 1) % DELAY;
+            }
+        }
+    }
+}
+
+lv2_descriptors!(Amp, Invert, Delay);
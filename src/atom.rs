@@ -7,48 +7,44 @@
 //! between plugins and hosts.
 
 use crate::atomutils::*;
+use crate::uri::Uri;
 
-/// LV2 Atom URI as bytes (null-terminated)
-pub const LV2_ATOM_URI: &[u8] = b"http://lv2plug.in/ns/ext/atom\0";
-/// LV2 Atom URI as string
-pub const LV2_ATOM_URI_STR: &str = "http://lv2plug.in/ns/ext/atom";
+/// LV2 Atom URI
+pub const LV2_ATOM_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom");
+/// LV2 Atom prefix
+pub const LV2_ATOM_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#");
 
-/// LV2 Atom prefix as bytes (null-terminated)
-pub const LV2_ATOM_PREFIX: &[u8] = b"http://lv2plug.in/ns/ext/atom#\0";
-/// LV2 Atom prefix as string
-pub const LV2_ATOM_PREFIX_STR: &str = "http://lv2plug.in/ns/ext/atom#";
-
-pub const LV2_ATOM__ATOM: &[u8] = b"http://lv2plug.in/ns/ext/atom#Atom\0";
-pub const LV2_ATOM__ATOMPORT: &[u8] = b"http://lv2plug.in/ns/ext/atom#AtomPort\0";
-pub const LV2_ATOM__BLANK: &[u8] = b"http://lv2plug.in/ns/ext/atom#Blank\0";
-pub const LV2_ATOM__BOOL: &[u8] = b"http://lv2plug.in/ns/ext/atom#Bool\0";
-pub const LV2_ATOM__CHUNK: &[u8] = b"http://lv2plug.in/ns/ext/atom#Chunk\0";
-pub const LV2_ATOM__DOUBLE: &[u8] = b"http://lv2plug.in/ns/ext/atom#Double\0";
-pub const LV2_ATOM__EVENT: &[u8] = b"http://lv2plug.in/ns/ext/atom#Event\0";
-pub const LV2_ATOM__FLOAT: &[u8] = b"http://lv2plug.in/ns/ext/atom#Float\0";
-pub const LV2_ATOM__INT: &[u8] = b"http://lv2plug.in/ns/ext/atom#Int\0";
-pub const LV2_ATOM__LITERAL: &[u8] = b"http://lv2plug.in/ns/ext/atom#Literal\0";
-pub const LV2_ATOM__LONG: &[u8] = b"http://lv2plug.in/ns/ext/atom#Long\0";
-pub const LV2_ATOM__NUMBER: &[u8] = b"http://lv2plug.in/ns/ext/atom#Number\0";
-pub const LV2_ATOM__OBJECT: &[u8] = b"http://lv2plug.in/ns/ext/atom#Object\0";
-pub const LV2_ATOM__PATH: &[u8] = b"http://lv2plug.in/ns/ext/atom#Path\0";
-pub const LV2_ATOM__PROPERTY: &[u8] = b"http://lv2plug.in/ns/ext/atom#Property\0";
-pub const LV2_ATOM__RESOURCE: &[u8] = b"http://lv2plug.in/ns/ext/atom#Resource\0";
-pub const LV2_ATOM__SEQUENCE: &[u8] = b"http://lv2plug.in/ns/ext/atom#Sequence\0";
-pub const LV2_ATOM__SOUND: &[u8] = b"http://lv2plug.in/ns/ext/atom#Sound\0";
-pub const LV2_ATOM__STRING: &[u8] = b"http://lv2plug.in/ns/ext/atom#String\0";
-pub const LV2_ATOM__TUPLE: &[u8] = b"http://lv2plug.in/ns/ext/atom#Tuple\0";
-pub const LV2_ATOM__URI: &[u8] = b"http://lv2plug.in/ns/ext/atom#URI\0";
-pub const LV2_ATOM__URID: &[u8] = b"http://lv2plug.in/ns/ext/atom#URID\0";
-pub const LV2_ATOM__VECTOR: &[u8] = b"http://lv2plug.in/ns/ext/atom#Vector\0";
-pub const LV2_ATOM__ATOMTRANSFER: &[u8] = b"http://lv2plug.in/ns/ext/atom#atomTransfer\0";
-pub const LV2_ATOM__BEATTIME: &[u8] = b"http://lv2plug.in/ns/ext/atom#beatTime\0";
-pub const LV2_ATOM__BUFFERTYPE: &[u8] = b"http://lv2plug.in/ns/ext/atom#bufferType\0";
-pub const LV2_ATOM__CHILDTYPE: &[u8] = b"http://lv2plug.in/ns/ext/atom#childType\0";
-pub const LV2_ATOM__EVENTTRANSFER: &[u8] = b"http://lv2plug.in/ns/ext/atom#eventTransfer\0";
-pub const LV2_ATOM__FRAMETIME: &[u8] = b"http://lv2plug.in/ns/ext/atom#frameTime\0";
-pub const LV2_ATOM__SUPPORTS: &[u8] = b"http://lv2plug.in/ns/ext/atom#supports\0";
-pub const LV2_ATOM__TIMEUNIT: &[u8] = b"http://lv2plug.in/ns/ext/atom#timeUnit\0";
+pub const LV2_ATOM__ATOM: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Atom");
+pub const LV2_ATOM__ATOMPORT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#AtomPort");
+pub const LV2_ATOM__BLANK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Blank");
+pub const LV2_ATOM__BOOL: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Bool");
+pub const LV2_ATOM__CHUNK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Chunk");
+pub const LV2_ATOM__DOUBLE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Double");
+pub const LV2_ATOM__EVENT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Event");
+pub const LV2_ATOM__FLOAT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Float");
+pub const LV2_ATOM__INT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Int");
+pub const LV2_ATOM__LITERAL: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Literal");
+pub const LV2_ATOM__LONG: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Long");
+pub const LV2_ATOM__NUMBER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Number");
+pub const LV2_ATOM__OBJECT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Object");
+pub const LV2_ATOM__PATH: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Path");
+pub const LV2_ATOM__PROPERTY: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Property");
+pub const LV2_ATOM__RESOURCE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Resource");
+pub const LV2_ATOM__SEQUENCE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Sequence");
+pub const LV2_ATOM__SOUND: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Sound");
+pub const LV2_ATOM__STRING: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#String");
+pub const LV2_ATOM__TUPLE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Tuple");
+pub const LV2_ATOM__URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#URI");
+pub const LV2_ATOM__URID: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#URID");
+pub const LV2_ATOM__VECTOR: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#Vector");
+pub const LV2_ATOM__ATOMTRANSFER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#atomTransfer");
+pub const LV2_ATOM__BEATTIME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#beatTime");
+pub const LV2_ATOM__BUFFERTYPE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#bufferType");
+pub const LV2_ATOM__CHILDTYPE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#childType");
+pub const LV2_ATOM__EVENTTRANSFER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#eventTransfer");
+pub const LV2_ATOM__FRAMETIME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#frameTime");
+pub const LV2_ATOM__SUPPORTS: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#supports");
+pub const LV2_ATOM__TIMEUNIT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/atom#timeUnit");
 
 /// The header of an atom:Atom.
 #[repr(C)]
@@ -4,9 +4,8 @@
 //! Utility functions for working with LV2 Atoms.
 
 use crate::atom::*;
-use libc::{memcmp, memcpy};
-use std::mem::size_of;
-use std::os::raw::c_void;
+use core::mem::size_of;
+use core::{ptr, slice};
 
 /// Pad a size to 64 bits.
 #[inline]
@@ -42,11 +41,8 @@ pub unsafe fn lv2_atom_equals(a: *const LV2Atom, b: *const LV2Atom) -> bool {
         (a == b)
             || (((*a).type_ == (*b).type_)
                 && ((*a).size == (*b).size)
-                && (memcmp(
-                    a.add(1) as *const c_void,
-                    b.add(1) as *const c_void,
-                    (*a).size as usize,
-                ) == 0))
+                && (slice::from_raw_parts(a.add(1) as *const u8, (*a).size as usize)
+                    == slice::from_raw_parts(b.add(1) as *const u8, (*b).size as usize)))
     }
 }
 
@@ -138,15 +134,11 @@ pub unsafe fn lv2_atom_sequence_append_event(
         let total_size = size_of::<LV2AtomEvent>() as u32 + (*event).body.size;
 
         if (capacity - (*seq).atom.size) < total_size {
-            return std::ptr::null();
+            return ptr::null();
         }
 
-        let e = lv2_atom_sequence_end(&(*seq).body, (*seq).atom.size);
-        memcpy(
-            e as *mut c_void,
-            event as *const c_void,
-            total_size as usize,
-        );
+        let e = lv2_atom_sequence_end(&raw const (*seq).body, (*seq).atom.size);
+        ptr::copy_nonoverlapping(event as *const u8, e as *mut u8, total_size as usize);
 
         (*seq).atom.size += lv2_atom_pad_size(total_size);
         e
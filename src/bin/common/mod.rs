@@ -0,0 +1,124 @@
+//! Code shared by the command line tools.
+
+use lv2_raw::bundle::{Bundle, PluginDescription};
+use lv2_raw::host::{FeatureList, OptionValue, PluginIndex, UridMapper};
+use lv2_raw::*;
+use std::ffi::{CStr, CString};
+use std::path::Path;
+
+const MAX_BLOCK_LENGTH: &CStr = c"http://lv2plug.in/ns/ext/buf-size#maxBlockLength";
+const NOMINAL_BLOCK_LENGTH: &CStr = c"http://lv2plug.in/ns/ext/buf-size#nominalBlockLength";
+const BOUNDED_BLOCK_LENGTH: &CStr = c"http://lv2plug.in/ns/ext/buf-size#boundedBlockLength";
+const SAMPLE_RATE: &CStr = c"http://lv2plug.in/ns/ext/parameters#sampleRate";
+
+/// Return the description of a plugin given on the command line: a URI
+/// looked up on `LV2_PATH`, or a bundle directory, with `uri` choosing
+/// among the plugins of the bundle.
+pub fn find_plugin(plugin: &str, uri: Option<&str>) -> Result<PluginDescription, String> {
+    let path = Path::new(plugin);
+    if !path.is_dir() {
+        let index = PluginIndex::discover();
+        let plugin = index
+            .get(plugin)
+            .ok_or_else(|| format!("plugin <{plugin}> not found on LV2_PATH"))?;
+        return Ok(plugin.description.clone());
+    }
+
+    let bundle = Bundle::load(path).map_err(|e| e.to_string())?;
+    let description = match uri {
+        Some(uri) => bundle
+            .plugin(uri)
+            .ok_or_else(|| format!("no plugin <{uri}> in {}", path.display()))?,
+        None if bundle.plugins.len() == 1 => &bundle.plugins[0],
+        None => {
+            let uris: Vec<String> = bundle
+                .plugins
+                .iter()
+                .map(|p| format!("<{}>", p.uri))
+                .collect();
+            return Err(format!(
+                "{} has {} plugins, choose one with --uri: {}",
+                path.display(),
+                uris.len(),
+                uris.join(" ")
+            ));
+        }
+    };
+    Ok(description.clone())
+}
+
+/// Return the features the tools provide, and fail if the plugin requires
+/// others.
+pub fn host_features<'a>(
+    description: &PluginDescription,
+    mapper: &'a UridMapper,
+    block_size: u32,
+    sample_rate: u32,
+) -> Result<FeatureList<'a>, String> {
+    let mut features = FeatureList::new();
+    features
+        .urid_map(mapper)
+        .urid_unmap(mapper)
+        .option(
+            mapper,
+            MAX_BLOCK_LENGTH,
+            OptionValue::Int(block_size as i32),
+        )
+        .option(
+            mapper,
+            NOMINAL_BLOCK_LENGTH,
+            OptionValue::Int(block_size as i32),
+        )
+        .option(mapper, SAMPLE_RATE, OptionValue::Float(sample_rate as f32))
+        .flag(BOUNDED_BLOCK_LENGTH);
+    let harmless = [
+        LV2_CORE__HARDRTCAPABLE,
+        LV2_CORE__INPLACEBROKEN,
+        LV2_CORE__ISLIVE,
+    ];
+    for feature in &description.required_features {
+        let supported = CString::new(feature.as_str()).is_ok_and(|uri| features.contains(&uri))
+            || harmless.iter().any(|uri| *uri == feature.as_str());
+        if !supported {
+            return Err(format!(
+                "the plugin requires unsupported feature <{feature}>"
+            ));
+        }
+    }
+    Ok(features)
+}
+
+/// Parse a `SYMBOL=VALUE` control setting.
+pub fn parse_control(setting: &str) -> Result<(String, f32), String> {
+    let (symbol, number) = setting
+        .split_once('=')
+        .ok_or_else(|| format!("expected SYMBOL=VALUE, got {setting:?}"))?;
+    let number = number
+        .parse()
+        .map_err(|_| format!("invalid control value {number:?}"))?;
+    Ok((symbol.to_owned(), number))
+}
+
+/// Return the value of every control input: its default, or the value
+/// given by symbol in `settings`.
+pub fn controls(
+    description: &PluginDescription,
+    settings: &[(String, f32)],
+) -> Result<Vec<(u32, f32)>, String> {
+    let mut controls: Vec<(u32, f32)> = description
+        .ports
+        .iter()
+        .filter(|p| {
+            p.port_type == Some(PortType::Control) && p.direction == Some(PortDirection::Input)
+        })
+        .map(|p| (p.index, p.default.unwrap_or(0.0)))
+        .collect();
+    for (symbol, value) in settings {
+        let port = description
+            .port_by_symbol(symbol)
+            .filter(|p| p.port_type == Some(PortType::Control))
+            .ok_or_else(|| format!("the plugin has no control port {symbol:?}"))?;
+        controls.push((port.index, *value));
+    }
+    Ok(controls)
+}
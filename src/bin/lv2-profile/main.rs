@@ -0,0 +1,132 @@
+//! Measure how much of the real-time budget an LV2 plugin uses.
+//!
+//! Runs a plugin through many blocks of generated input and prints the
+//! mean, 99th percentile and worst `run()` times, and the times of
+//! `instantiate()`, `activate()` and `deactivate()`.  Run with `--help` for
+//! the options.
+
+#[cfg_attr(not(unix), allow(dead_code))]
+#[path = "../common/mod.rs"]
+mod common;
+#[cfg(unix)]
+mod profile;
+
+use common::parse_control;
+use lv2_raw::host::Stimulus;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: lv2-profile [OPTIONS] PLUGIN
+
+Time the run() calls of an LV2 plugin against the real-time budget.
+PLUGIN is a plugin URI, looked up on LV2_PATH, or a bundle directory.
+
+options:
+  -s, --stimulus INPUT     silence, noise or midi (default noise)
+  -c, --control SYM=VALUE  set a control input, by port symbol
+  -u, --uri URI            choose a plugin of a bundle with several
+  -b, --block-size N       frames per run() call (default 256)
+  -r, --rate HZ            sample rate (default 48000)
+  -n, --blocks N           number of run() calls (default 10000)
+  -h, --help               show this help
+";
+
+pub struct Args {
+    pub plugin: String,
+    pub stimulus: Stimulus,
+    pub controls: Vec<(String, f32)>,
+    pub uri: Option<String>,
+    pub block_size: u32,
+    pub rate: u32,
+    pub blocks: usize,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut positional = Vec::new();
+    let mut parsed = Args {
+        plugin: String::new(),
+        stimulus: Stimulus::Noise,
+        controls: Vec::new(),
+        uri: None,
+        block_size: 256,
+        rate: 48000,
+        blocks: 10000,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-u" | "--uri" => parsed.uri = Some(value()?),
+            "-c" | "--control" => parsed.controls.push(parse_control(&value()?)?),
+            "-s" | "--stimulus" => {
+                parsed.stimulus = match value()?.as_str() {
+                    "silence" => Stimulus::Silence,
+                    "noise" => Stimulus::Noise,
+                    "midi" => Stimulus::DenseMidi,
+                    other => return Err(format!("unknown stimulus {other:?}")),
+                };
+            }
+            "-b" | "--block-size" => {
+                parsed.block_size = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid block size")?;
+            }
+            "-r" | "--rate" => {
+                parsed.rate = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid sample rate")?;
+            }
+            "-n" | "--blocks" => {
+                parsed.blocks = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid number of blocks")?;
+            }
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(format!("unknown option {option}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let [plugin] =
+        <[String; 1]>::try_from(positional).map_err(|_| "expected a plugin".to_owned())?;
+    parsed.plugin = plugin;
+    Ok(Some(parsed))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("lv2-profile: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("lv2-profile: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(unix)]
+fn run(args: &Args) -> Result<(), String> {
+    profile::profile(args)
+}
+
+#[cfg(not(unix))]
+fn run(_: &Args) -> Result<(), String> {
+    Err("loading plugin libraries is only supported on Unix".to_owned())
+}
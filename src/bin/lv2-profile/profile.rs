@@ -0,0 +1,40 @@
+//! Profiling the plugin.
+
+use crate::Args;
+use crate::common::{controls, find_plugin, host_features};
+use lv2_raw::host::{Library, Profiler, UridMapper};
+use std::ffi::CString;
+
+pub fn profile(args: &Args) -> Result<(), String> {
+    let description = find_plugin(&args.plugin, args.uri.as_deref())?;
+    let ports = description
+        .port_infos()
+        .ok_or("the plugin has ports of unknown type or direction")?;
+    let controls = controls(&description, &args.controls)?;
+    let mapper = UridMapper::new();
+    let features = host_features(&description, &mapper, args.block_size, args.rate)?;
+
+    let binary = description
+        .binary
+        .as_ref()
+        .ok_or("the plugin has no binary")?;
+    let library = Library::open(binary).map_err(|e| e.to_string())?;
+    let plugin = library
+        .find(CString::new(description.uri.as_str()).map_err(|e| e.to_string())?)
+        .ok_or_else(|| format!("{} has no plugin <{}>", binary.display(), description.uri))?;
+
+    let mut profiler = Profiler::for_plugin(&plugin, features.features(), ports);
+    profiler
+        .sample_rate(f64::from(args.rate))
+        .block_size(args.block_size)
+        .blocks(args.blocks)
+        .stimulus(args.stimulus)
+        // Room for a MIDI event on every frame.
+        .atom_capacity(args.block_size.saturating_mul(24).max(8192));
+    for (index, value) in controls {
+        profiler.control(index, value);
+    }
+    let profile = profiler.run().map_err(|e| e.to_string())?;
+    println!("{profile}");
+    Ok(())
+}
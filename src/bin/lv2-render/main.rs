@@ -0,0 +1,152 @@
+//! Render audio through an LV2 plugin, offline.
+//!
+//! Reads a WAV file and/or a MIDI file, runs them through a plugin block by
+//! block, and writes the audio outputs to a WAV file.  The latency the
+//! plugin reports is compensated, so the output lines up with the input.
+//! Run with `--help` for the options.
+
+#[cfg_attr(not(unix), allow(dead_code))]
+#[path = "../common/mod.rs"]
+mod common;
+#[cfg(unix)]
+mod render;
+
+use common::parse_control;
+use lv2_raw::host::WavFormat;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: lv2-render [OPTIONS] PLUGIN OUTPUT
+
+Render audio through an LV2 plugin and write it to the WAV file OUTPUT.
+PLUGIN is a plugin URI, looked up on LV2_PATH, or a bundle directory.
+The output is shifted by the latency the plugin reports, if any.
+
+options:
+  -i, --input FILE         read audio inputs from a WAV file
+  -m, --midi FILE          send a MIDI file to the first atom input which
+                           supports MIDI, or else the first atom input
+  -c, --control SYM=VALUE  set a control input, by port symbol
+  -u, --uri URI            choose a plugin of a bundle with several
+  -b, --block-size N       frames per run() call (default 512)
+  -r, --rate HZ            sample rate without --input (default 48000)
+  -l, --length SECONDS     length of the output (default: the input, or
+                           the MIDI file plus one second)
+  -f, --format FORMAT      pcm16, pcm24, pcm32 or float (default: as the
+                           input, or float)
+  -h, --help               show this help
+";
+
+pub struct Args {
+    pub plugin: String,
+    pub output: PathBuf,
+    pub input: Option<PathBuf>,
+    pub midi: Option<PathBuf>,
+    pub controls: Vec<(String, f32)>,
+    pub uri: Option<String>,
+    pub block_size: u32,
+    pub rate: u32,
+    pub length: Option<f64>,
+    pub format: Option<WavFormat>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut positional = Vec::new();
+    let mut parsed = Args {
+        plugin: String::new(),
+        output: PathBuf::new(),
+        input: None,
+        midi: None,
+        controls: Vec::new(),
+        uri: None,
+        block_size: 512,
+        rate: 48000,
+        length: None,
+        format: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-i" | "--input" => parsed.input = Some(value()?.into()),
+            "-m" | "--midi" => parsed.midi = Some(value()?.into()),
+            "-u" | "--uri" => parsed.uri = Some(value()?),
+            "-c" | "--control" => parsed.controls.push(parse_control(&value()?)?),
+            "-b" | "--block-size" => {
+                parsed.block_size = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid block size")?;
+            }
+            "-r" | "--rate" => {
+                parsed.rate = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid sample rate")?;
+            }
+            "-l" | "--length" => {
+                let length = value()?;
+                parsed.length = Some(
+                    length
+                        .parse()
+                        .ok()
+                        .filter(|&l: &f64| l >= 0.0)
+                        .ok_or_else(|| format!("invalid length {length:?}"))?,
+                );
+            }
+            "-f" | "--format" => {
+                parsed.format = Some(match value()?.as_str() {
+                    "pcm16" => WavFormat::Pcm16,
+                    "pcm24" => WavFormat::Pcm24,
+                    "pcm32" => WavFormat::Pcm32,
+                    "float" => WavFormat::Float32,
+                    other => return Err(format!("unknown format {other:?}")),
+                });
+            }
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(format!("unknown option {option}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let [plugin, output] = <[String; 2]>::try_from(positional)
+        .map_err(|_| "expected a plugin and an output file".to_owned())?;
+    parsed.plugin = plugin;
+    parsed.output = output.into();
+    Ok(Some(parsed))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("lv2-render: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("lv2-render: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(unix)]
+fn run(args: &Args) -> Result<(), String> {
+    render::render(args)
+}
+
+#[cfg(not(unix))]
+fn run(_: &Args) -> Result<(), String> {
+    Err("loading plugin libraries is only supported on Unix".to_owned())
+}
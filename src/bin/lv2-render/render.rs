@@ -0,0 +1,188 @@
+//! Running the plugin over the input.
+
+use crate::Args;
+use crate::common::{controls, find_plugin, host_features};
+use lv2_raw::host::{
+    Instance, Library, MidiFile, PortBuffers, UridMapper, Wav, WavFormat, query_latency,
+};
+use lv2_raw::*;
+use std::ffi::CString;
+
+/// Bytes of MIDI events per block.
+const ATOM_CAPACITY: u32 = 65536;
+
+pub fn render(args: &Args) -> Result<(), String> {
+    let description = find_plugin(&args.plugin, args.uri.as_deref())?;
+    let ports = description
+        .port_infos()
+        .ok_or("the plugin has ports of unknown type or direction")?;
+    let input = args
+        .input
+        .as_ref()
+        .map(|path| Wav::open(path).map_err(|e| format!("{}: {e}", path.display())))
+        .transpose()?;
+    let midi = args
+        .midi
+        .as_ref()
+        .map(|path| MidiFile::open(path).map_err(|e| format!("{}: {e}", path.display())))
+        .transpose()?;
+    let rate = input.as_ref().map_or(args.rate, |wav| wav.sample_rate);
+
+    let frames = match (args.length, &input, &midi) {
+        (Some(length), _, _) => (length * f64::from(rate)).round() as usize,
+        (None, Some(input), _) => input.frames(),
+        (None, None, Some(midi)) => ((midi.duration() + 1.0) * f64::from(rate)).round() as usize,
+        (None, None, None) => return Err("give an --input, --midi or --length".to_owned()),
+    };
+
+    let of = |port_type: PortType, direction: PortDirection| -> Vec<u32> {
+        (0..ports.len() as u32)
+            .filter(|&i| {
+                let port = &ports[i as usize];
+                port.port_type == port_type && port.direction == direction
+            })
+            .collect()
+    };
+    let audio_inputs = of(PortType::Audio, PortDirection::Input);
+    let audio_outputs = of(PortType::Audio, PortDirection::Output);
+    if audio_outputs.is_empty() {
+        return Err("the plugin has no audio outputs".to_owned());
+    }
+    let atom_inputs = of(PortType::Atom, PortDirection::Input);
+    let midi_input = atom_inputs
+        .iter()
+        .copied()
+        .find(|&i| {
+            description.ports[i as usize]
+                .supports
+                .iter()
+                .any(|t| t == LV2_MIDI__MIDIEVENT.as_str())
+        })
+        .or(atom_inputs.first().copied());
+    if midi.is_some() && midi_input.is_none() {
+        return Err("the plugin has no atom input for MIDI".to_owned());
+    }
+
+    let controls = controls(&description, &args.controls)?;
+    let mapper = UridMapper::new();
+    let features = host_features(&description, &mapper, args.block_size, rate)?;
+
+    let binary = description
+        .binary
+        .as_ref()
+        .ok_or("the plugin has no binary")?;
+    let library = Library::open(binary).map_err(|e| e.to_string())?;
+    let plugin = library
+        .find(CString::new(description.uri.as_str()).map_err(|e| e.to_string())?)
+        .ok_or_else(|| format!("{} has no plugin <{}>", binary.display(), description.uri))?;
+    let mut instance = Instance::new(&plugin, f64::from(rate), features.features(), ports.clone())
+        .map_err(|e| e.to_string())?;
+    let mut buffers = PortBuffers::new(
+        &ports,
+        args.block_size,
+        ATOM_CAPACITY,
+        &mapper.map_feature(),
+    );
+    unsafe { buffers.connect(&mut instance) }.map_err(|e| e.to_string())?;
+    buffers.set_defaults(&description.ports);
+    for &(index, value) in &controls {
+        buffers.set_control(index, value);
+    }
+
+    let midi_urid = mapper.map(LV2_MIDI__MIDIEVENT);
+    let events = midi.map_or_else(Vec::new, |midi| midi.events);
+    let mut next_event = 0;
+    instance.activate().map_err(|e| e.to_string())?;
+    // Render as many more frames as the output is delayed, and drop as many
+    // from the start, so the output lines up with the input.
+    let latency = query_latency(&mut instance, &mut buffers)
+        .map_err(|e| e.to_string())?
+        .unwrap_or(0) as usize;
+    let total = frames + latency;
+    let mut outputs = vec![Vec::with_capacity(total); audio_outputs.len()];
+    let mut position = 0;
+    while position < total {
+        let n = (args.block_size as usize).min(total - position);
+        buffers.prepare_atoms();
+
+        for (k, &port) in audio_inputs.iter().enumerate() {
+            let samples = buffers.samples_mut(port).unwrap();
+            samples.fill(0.0);
+            // Fewer channels than inputs: repeat the last channel.
+            let channel = input
+                .as_ref()
+                .and_then(|wav| wav.channels.get(k).or(wav.channels.last()));
+            if let Some(channel) = channel {
+                let end = (position + n).min(channel.len());
+                if position < end {
+                    samples[..end - position].copy_from_slice(&channel[position..end]);
+                }
+            }
+        }
+
+        if let Some(port) = midi_input {
+            let sequence = buffers.sequence_mut(port).unwrap();
+            while let Some(event) = events.get(next_event) {
+                let frame = (event.time * f64::from(rate)).round() as usize;
+                if frame >= position + n {
+                    break;
+                }
+                let time = frame.saturating_sub(position) as i64;
+                if !unsafe {
+                    append_midi(sequence, ATOM_CAPACITY - 8, time, midi_urid, &event.data)
+                } {
+                    // Full: send the rest with the next block.
+                    break;
+                }
+                next_event += 1;
+            }
+        }
+
+        instance.run(n as u32).map_err(|e| e.to_string())?;
+        for (output, &port) in outputs.iter_mut().zip(&audio_outputs) {
+            output.extend_from_slice(&buffers.samples(port).unwrap()[..n]);
+        }
+        position += n;
+    }
+    instance.deactivate().map_err(|e| e.to_string())?;
+    for output in &mut outputs {
+        output.drain(..latency);
+    }
+
+    let format = args
+        .format
+        .or(input.as_ref().map(|wav| wav.format))
+        .unwrap_or(WavFormat::Float32);
+    let wav = Wav {
+        sample_rate: rate,
+        format,
+        channels: outputs,
+    };
+    wav.save(&args.output)
+        .map_err(|e| format!("{}: {e}", args.output.display()))
+}
+
+/// Append a MIDI event to a sequence.  Returns false if it does not fit.
+unsafe fn append_midi(
+    sequence: *mut LV2AtomSequence,
+    capacity: u32,
+    time: i64,
+    type_: LV2Urid,
+    data: &[u8],
+) -> bool {
+    // The header and the message, contiguous and aligned.
+    let header = size_of::<LV2AtomEvent>();
+    let mut storage = vec![0u64; (header + data.len()).div_ceil(8)];
+    let event = storage.as_mut_ptr() as *mut LV2AtomEvent;
+    unsafe {
+        *event = LV2AtomEvent {
+            time_in_frames: time,
+            body: LV2Atom {
+                size: data.len() as u32,
+                type_,
+            },
+        };
+        std::ptr::copy_nonoverlapping(data.as_ptr(), (event as *mut u8).add(header), data.len());
+        !lv2_atom_sequence_append_event(sequence, capacity, event).is_null()
+    }
+}
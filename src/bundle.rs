@@ -0,0 +1,667 @@
+//! Plugin data from the RDF files of a bundle.
+//!
+//! An LV2 bundle is a directory with a `manifest.ttl`, which lists the
+//! plugins in it, their binaries and (through `rdfs:seeAlso`) further data
+//! files.  [`Bundle::load`] reads all of them with the [`turtle`](crate::turtle)
+//! parser and extracts what a host needs to load and run each plugin.
+//!
+//! The other way round, [`write_bundle`] writes the files of a bundle from
+//! [`PluginDescription`]s, so they can be generated from the code of the
+//! plugins instead of being maintained by hand.
+
+use crate::atom::{LV2_ATOM__BUFFERTYPE, LV2_ATOM__SUPPORTS, LV2_ATOM_PREFIX};
+use crate::control::ControlPort;
+use crate::core::*;
+use crate::host::PortInfo;
+use crate::midi::LV2_MIDI_PREFIX;
+use crate::portprops::LV2_PORT_PROPS_PREFIX;
+use crate::turtle::{
+    DOAP__NAME, DOAP_PREFIX, Graph, Object, RDF__TYPE, RDF__VALUE, RDF_PREFIX, RDFS__LABEL,
+    RDFS__SEEALSO, RDFS_PREFIX, Term, TurtleError, Writer, XSD__DECIMAL, XSD__DOUBLE, XSD__INTEGER,
+};
+use crate::ui::{LV2_UI___UI, LV2_UI__BINARY, LV2_UI_PREFIX};
+use crate::units::{LV2_UNITS___UNIT, LV2_UNITS_PREFIX};
+use crate::uri::Uri;
+use crate::urid::LV2_URID_PREFIX;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Error returned when a bundle can not be loaded.
+#[derive(Debug)]
+pub enum BundleError {
+    /// A file could not be read.
+    Io { path: PathBuf, error: io::Error },
+    /// A file is not valid Turtle.
+    Turtle { path: PathBuf, error: TurtleError },
+    /// The data of a plugin is incomplete or inconsistent.
+    InvalidPlugin { uri: String, message: String },
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            BundleError::Turtle { path, error } => write!(f, "{}:{}", path.display(), error),
+            BundleError::InvalidPlugin { uri, message } => write!(f, "<{uri}>: {message}"),
+        }
+    }
+}
+
+impl Error for BundleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BundleError::Io { error, .. } => Some(error),
+            BundleError::Turtle { error, .. } => Some(error),
+            BundleError::InvalidPlugin { .. } => None,
+        }
+    }
+}
+
+/// The description of a port.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PortDescription {
+    /// `lv2:index`.
+    pub index: u32,
+    /// `lv2:symbol`.
+    pub symbol: String,
+    /// `lv2:name`.
+    pub name: Option<String>,
+    /// The port type, from the classes of the port.
+    pub port_type: Option<PortType>,
+    /// The direction, from the classes of the port.
+    pub direction: Option<PortDirection>,
+    /// `lv2:default`.
+    pub default: Option<f32>,
+    /// `lv2:minimum`.
+    pub minimum: Option<f32>,
+    /// `lv2:maximum`.
+    pub maximum: Option<f32>,
+    /// URIs of all `lv2:portProperty` values, including ones from other
+    /// vocabularies than lv2core.
+    pub properties: Vec<String>,
+    /// `lv2:designation`: the meaning of the port, e.g. `lv2:latency`.
+    pub designation: Option<String>,
+    /// `lv2:scalePoint`: named values of a control port.
+    pub scale_points: Vec<ScalePoint>,
+    /// `units:unit` of a control port, e.g. `units:db`.
+    pub unit: Option<String>,
+    /// `atom:bufferType` of an atom port, e.g. `atom:Sequence`.
+    pub buffer_type: Option<String>,
+    /// `atom:supports` of an atom port: the types of the atoms or events it
+    /// carries, e.g. `midi:MidiEvent`.
+    pub supports: Vec<String>,
+}
+
+/// A named value of a control port.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScalePoint {
+    /// `rdf:value`.
+    pub value: f32,
+    /// `rdfs:label`.
+    pub label: String,
+}
+
+impl PortDescription {
+    /// Return true iff the port has the port property with this URI.
+    pub fn has_property(&self, uri: impl AsRef<str>) -> bool {
+        let uri = uri.as_ref();
+        self.properties.iter().any(|p| p == uri)
+    }
+
+    /// Return what the host module needs to know about the port, or `None` if
+    /// the type or direction is unknown.
+    pub fn info(&self) -> Option<PortInfo> {
+        let mut info = PortInfo::new(self.port_type?, self.direction?);
+        for property in &self.properties {
+            if let Some(property) = PortProperty::from_uri(property) {
+                info = info.with_property(property);
+            }
+        }
+        if let Some(designation) = &self.designation {
+            info = info.with_designation(designation);
+        }
+        Some(info)
+    }
+}
+
+/// The description of a plugin.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PluginDescription {
+    /// The plugin URI.
+    pub uri: String,
+    /// `doap:name`.
+    pub name: Option<String>,
+    /// The shared library, from `lv2:binary`.
+    pub binary: Option<PathBuf>,
+    /// The data files of the plugin, from `rdfs:seeAlso`.
+    pub data_files: Vec<PathBuf>,
+    /// The ports, in index order.
+    pub ports: Vec<PortDescription>,
+    /// `lv2:requiredFeature`.
+    pub required_features: Vec<String>,
+    /// `lv2:optionalFeature`.
+    pub optional_features: Vec<String>,
+    /// `lv2:extensionData`.
+    pub extension_data: Vec<String>,
+    /// The user interfaces, from `ui:ui`.
+    pub uis: Vec<UiDescription>,
+}
+
+/// The description of a plugin UI.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UiDescription {
+    /// The UI URI.
+    pub uri: String,
+    /// The UI type, e.g. `ui:X11UI`.
+    pub class: Option<String>,
+    /// The shared library, from `lv2:binary`.
+    pub binary: Option<PathBuf>,
+}
+
+impl PluginDescription {
+    /// Extract the description of plugin `uri` from a graph.
+    pub fn from_graph(graph: &Graph, uri: &str) -> Result<Self, BundleError> {
+        let subject = Term::iri(uri);
+        let invalid = |message: String| BundleError::InvalidPlugin {
+            uri: uri.to_owned(),
+            message,
+        };
+        let iris = |predicate| -> Vec<String> {
+            graph
+                .objects(&subject, predicate)
+                .filter_map(|o| o.as_iri().map(str::to_owned))
+                .collect()
+        };
+
+        let mut ports = Vec::new();
+        for port in graph.objects(&subject, LV2_CORE___PORT) {
+            let index = graph
+                .object(port, LV2_CORE__INDEX)
+                .and_then(Term::as_i64)
+                .and_then(|i| u32::try_from(i).ok())
+                .ok_or_else(|| invalid(format!("port {port} has no valid lv2:index")))?;
+            let symbol = graph
+                .object(port, LV2_CORE__SYMBOL)
+                .and_then(Term::as_str)
+                .ok_or_else(|| invalid(format!("port {index} has no lv2:symbol")))?;
+            let classes: Vec<&str> = graph
+                .objects(port, RDF__TYPE)
+                .filter_map(Term::as_iri)
+                .collect();
+            let number = |predicate| {
+                graph
+                    .object(port, predicate)
+                    .and_then(Term::as_f64)
+                    .map(|v| v as f32)
+            };
+
+            ports.push(PortDescription {
+                index,
+                symbol: symbol.to_owned(),
+                name: graph
+                    .object(port, LV2_CORE__NAME)
+                    .and_then(Term::as_str)
+                    .map(str::to_owned),
+                port_type: classes.iter().find_map(|c| PortType::from_uri(c)),
+                direction: classes.iter().find_map(|c| PortDirection::from_uri(c)),
+                default: number(LV2_CORE__DEFAULT),
+                minimum: number(LV2_CORE__MINIMUM),
+                maximum: number(LV2_CORE__MAXIMUM),
+                properties: graph
+                    .objects(port, LV2_CORE___PORTPROPERTY)
+                    .filter_map(|o| o.as_iri().map(str::to_owned))
+                    .collect(),
+                designation: graph
+                    .object(port, LV2_CORE__DESIGNATION)
+                    .and_then(Term::as_iri)
+                    .map(str::to_owned),
+                scale_points: graph
+                    .objects(port, LV2_CORE___SCALEPOINT)
+                    .filter_map(|point| {
+                        Some(ScalePoint {
+                            value: graph.object(point, RDF__VALUE)?.as_f64()? as f32,
+                            label: graph.object(point, RDFS__LABEL)?.as_str()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                unit: graph
+                    .object(port, LV2_UNITS___UNIT)
+                    .and_then(Term::as_iri)
+                    .map(str::to_owned),
+                buffer_type: graph
+                    .object(port, LV2_ATOM__BUFFERTYPE)
+                    .and_then(Term::as_iri)
+                    .map(str::to_owned),
+                supports: graph
+                    .objects(port, LV2_ATOM__SUPPORTS)
+                    .filter_map(|o| o.as_iri().map(str::to_owned))
+                    .collect(),
+            });
+        }
+
+        ports.sort_by_key(|p| p.index);
+        for (i, port) in ports.iter().enumerate() {
+            if port.index != i as u32 {
+                return Err(invalid(format!(
+                    "port indices are not 0..{}, found {}",
+                    ports.len(),
+                    port.index
+                )));
+            }
+        }
+
+        Ok(PluginDescription {
+            uri: uri.to_owned(),
+            name: graph
+                .object(&subject, DOAP__NAME)
+                .and_then(Term::as_str)
+                .map(str::to_owned),
+            binary: graph
+                .object(&subject, LV2_CORE__BINARY)
+                .and_then(Term::as_iri)
+                .and_then(file_uri_to_path),
+            data_files: iris(RDFS__SEEALSO.as_str())
+                .iter()
+                .filter_map(|uri| file_uri_to_path(uri))
+                .collect(),
+            ports,
+            required_features: iris(LV2_CORE__REQUIREDFEATURE.as_str()),
+            optional_features: iris(LV2_CORE__OPTIONALFEATURE.as_str()),
+            extension_data: iris(LV2_CORE___EXTENSIONDATA.as_str()),
+            uis: iris(LV2_UI___UI.as_str())
+                .into_iter()
+                .map(|uri| UiDescription::from_graph(graph, uri))
+                .collect(),
+        })
+    }
+
+    /// Return the data file to write the plugin to, relative to the bundle.
+    fn data_file(&self) -> &Path {
+        self.data_files
+            .first()
+            .map_or(Path::new("plugin.ttl"), |file| in_bundle(file))
+    }
+
+    /// Return the port with the given symbol.
+    pub fn port_by_symbol(&self, symbol: &str) -> Option<&PortDescription> {
+        self.ports.iter().find(|p| p.symbol == symbol)
+    }
+
+    /// Return the index of the control output which reports the latency of
+    /// the plugin, designated `lv2:latency` or with the older
+    /// `lv2:reportsLatency` property.
+    pub fn latency_port(&self) -> Option<u32> {
+        self.ports
+            .iter()
+            .find(|p| p.info().is_some_and(|info| info.reports_latency()))
+            .map(|p| p.index)
+    }
+
+    /// Return the models of the control ports, in index order.
+    pub fn controls(&self) -> Vec<ControlPort> {
+        self.ports
+            .iter()
+            .filter_map(ControlPort::from_description)
+            .collect()
+    }
+
+    /// Return the ports as the host module needs them, or `None` if the type
+    /// or direction of any port is unknown.
+    pub fn port_infos(&self) -> Option<Vec<PortInfo>> {
+        self.ports.iter().map(PortDescription::info).collect()
+    }
+}
+
+impl UiDescription {
+    fn from_graph(graph: &Graph, uri: String) -> Self {
+        let subject = Term::iri(&uri);
+        let binary = graph
+            .object(&subject, LV2_CORE__BINARY)
+            .or_else(|| graph.object(&subject, LV2_UI__BINARY));
+        UiDescription {
+            class: graph
+                .objects(&subject, RDF__TYPE)
+                .filter_map(Term::as_iri)
+                .find(|class| class.starts_with(LV2_UI_PREFIX.as_str()))
+                .map(str::to_owned),
+            binary: binary.and_then(Term::as_iri).and_then(file_uri_to_path),
+            uri,
+        }
+    }
+}
+
+/// The plugins of a bundle.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    /// The bundle directory.
+    pub path: PathBuf,
+    /// The plugins, in the order of the manifest.
+    pub plugins: Vec<PluginDescription>,
+    graph: Graph,
+}
+
+impl Bundle {
+    /// Load the bundle in directory `path`.
+    ///
+    /// This reads `manifest.ttl`, and the `rdfs:seeAlso` files of every
+    /// plugin in it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Bundle, BundleError> {
+        let path = path.as_ref();
+        let path = std::path::absolute(path).map_err(|error| BundleError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        let mut graph = Graph::new();
+        let mut loaded = Vec::new();
+        load_file(&mut graph, &path.join("manifest.ttl"))?;
+
+        let plugin_class = Term::from(LV2_CORE__PLUGIN);
+        let uris: Vec<String> = unique(
+            graph
+                .subjects(RDF__TYPE, &plugin_class)
+                .filter_map(|s| s.as_iri().map(str::to_owned)),
+        );
+        for uri in &uris {
+            let files: Vec<PathBuf> = graph
+                .objects(&Term::iri(uri), RDFS__SEEALSO)
+                .filter_map(|o| o.as_iri().and_then(file_uri_to_path))
+                .collect();
+            for file in files {
+                if !loaded.contains(&file) {
+                    load_file(&mut graph, &file)?;
+                    loaded.push(file);
+                }
+            }
+        }
+
+        let plugins = uris
+            .iter()
+            .map(|uri| PluginDescription::from_graph(&graph, uri))
+            .collect::<Result<_, _>>()?;
+        Ok(Bundle {
+            path,
+            plugins,
+            graph,
+        })
+    }
+
+    /// Return the plugin with the given URI.
+    pub fn plugin(&self, uri: impl AsRef<str>) -> Option<&PluginDescription> {
+        let uri = uri.as_ref();
+        self.plugins.iter().find(|p| p.uri == uri)
+    }
+
+    /// Return all triples read from the bundle.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+/// Return the `manifest.ttl` for `plugins`.
+///
+/// It lists every plugin with its binary and data file, and every UI with
+/// its type and binary.  Paths are written as in [`write_bundle`].
+pub fn manifest_turtle(plugins: &[PluginDescription]) -> String {
+    let mut writer = turtle_writer();
+    for plugin in plugins {
+        writer.statement(
+            &Term::iri(&plugin.uri),
+            &properties([
+                property(RDF__TYPE, [LV2_CORE__PLUGIN.into()]),
+                property(LV2_CORE__BINARY, plugin.binary.as_deref().map(file)),
+                property(RDFS__SEEALSO, [file(plugin.data_file())]),
+            ]),
+        );
+        for ui in &plugin.uis {
+            writer.statement(
+                &Term::iri(&ui.uri),
+                &properties([
+                    property(RDF__TYPE, iris(&ui.class)),
+                    property(LV2_CORE__BINARY, ui.binary.as_deref().map(file)),
+                ]),
+            );
+        }
+    }
+    writer.finish()
+}
+
+/// Return a data file describing `plugins`: their names, features,
+/// extension data, UIs and ports.
+pub fn data_turtle<'a>(plugins: impl IntoIterator<Item = &'a PluginDescription>) -> String {
+    let mut writer = turtle_writer();
+    for plugin in plugins {
+        writer.statement(
+            &Term::iri(&plugin.uri),
+            &properties([
+                property(DOAP__NAME, plugin.name.as_deref().map(string)),
+                property(LV2_CORE__REQUIREDFEATURE, iris(&plugin.required_features)),
+                property(LV2_CORE__OPTIONALFEATURE, iris(&plugin.optional_features)),
+                property(LV2_CORE___EXTENSIONDATA, iris(&plugin.extension_data)),
+                property(LV2_UI___UI, iris(plugin.uis.iter().map(|ui| &ui.uri))),
+                property(LV2_CORE___PORT, plugin.ports.iter().map(port)),
+            ]),
+        );
+    }
+    writer.finish()
+}
+
+/// Write the files of a bundle with `plugins` into directory `path`, which
+/// is created if needed: the [`manifest_turtle`], and a [`data_turtle`] for
+/// every data file.
+///
+/// Each plugin is described in the first of its `data_files`, or in
+/// `plugin.ttl` if it has none; plugins with the same data file share it.
+/// Paths are written relative to the bundle: relative paths as they are,
+/// and absolute paths by their file name, which assumes the file is in the
+/// bundle directory.
+pub fn write_bundle(
+    path: impl AsRef<Path>,
+    plugins: &[PluginDescription],
+) -> Result<(), BundleError> {
+    let path = path.as_ref();
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |error| BundleError::Io { path, error }
+    };
+
+    let mut data_files: Vec<(&Path, Vec<&PluginDescription>)> = Vec::new();
+    for plugin in plugins {
+        let data_file = plugin.data_file();
+        match data_files.iter_mut().find(|(file, _)| *file == data_file) {
+            Some((_, plugins)) => plugins.push(plugin),
+            None => data_files.push((data_file, vec![plugin])),
+        }
+    }
+
+    std::fs::create_dir_all(path).map_err(io_error(path))?;
+    let manifest = path.join("manifest.ttl");
+    std::fs::write(&manifest, manifest_turtle(plugins)).map_err(io_error(&manifest))?;
+    for (data_file, plugins) in data_files {
+        let file = path.join(data_file);
+        std::fs::write(&file, data_turtle(plugins)).map_err(io_error(&file))?;
+    }
+    Ok(())
+}
+
+fn turtle_writer() -> Writer {
+    let mut writer = Writer::new();
+    writer
+        .prefix("atom", LV2_ATOM_PREFIX)
+        .prefix("doap", DOAP_PREFIX)
+        .prefix("lv2", LV2_CORE_PREFIX)
+        .prefix("midi", LV2_MIDI_PREFIX)
+        .prefix("pprops", LV2_PORT_PROPS_PREFIX)
+        .prefix("rdf", RDF_PREFIX)
+        .prefix("rdfs", RDFS_PREFIX)
+        .prefix("ui", LV2_UI_PREFIX)
+        .prefix("units", LV2_UNITS_PREFIX)
+        .prefix("urid", LV2_URID_PREFIX);
+    writer
+}
+
+fn port(port: &PortDescription) -> Object {
+    let classes = port.direction.map(PortDirection::uri);
+    let classes = classes.into_iter().chain(port.port_type.map(PortType::uri));
+    Object::Node(properties([
+        property(RDF__TYPE, classes.map(Object::from)),
+        property(
+            LV2_CORE__INDEX,
+            [Term::typed(port.index.to_string(), XSD__INTEGER).into()],
+        ),
+        property(LV2_CORE__SYMBOL, [string(&port.symbol)]),
+        property(LV2_CORE__NAME, port.name.as_deref().map(string)),
+        property(LV2_CORE__DEFAULT, port.default.map(number)),
+        property(LV2_CORE__MINIMUM, port.minimum.map(number)),
+        property(LV2_CORE__MAXIMUM, port.maximum.map(number)),
+        property(LV2_CORE___PORTPROPERTY, iris(&port.properties)),
+        property(LV2_CORE__DESIGNATION, iris(&port.designation)),
+        property(
+            LV2_CORE___SCALEPOINT,
+            port.scale_points.iter().map(|point| {
+                Object::Node(properties([
+                    property(RDFS__LABEL, [string(&point.label)]),
+                    property(RDF__VALUE, [number(point.value)]),
+                ]))
+            }),
+        ),
+        property(LV2_UNITS___UNIT, iris(&port.unit)),
+        property(LV2_ATOM__BUFFERTYPE, iris(&port.buffer_type)),
+        property(LV2_ATOM__SUPPORTS, iris(&port.supports)),
+    ]))
+}
+
+fn property(predicate: Uri, objects: impl IntoIterator<Item = Object>) -> (String, Vec<Object>) {
+    (predicate.as_str().to_owned(), objects.into_iter().collect())
+}
+
+/// Drop the properties without objects.
+fn properties<const N: usize>(
+    properties: [(String, Vec<Object>); N],
+) -> Vec<(String, Vec<Object>)> {
+    properties
+        .into_iter()
+        .filter(|(_, objects)| !objects.is_empty())
+        .collect()
+}
+
+fn iris<'a>(uris: impl IntoIterator<Item = &'a String>) -> impl Iterator<Item = Object> {
+    uris.into_iter().map(|uri| Term::iri(uri).into())
+}
+
+fn string(value: &str) -> Object {
+    Term::string(value).into()
+}
+
+fn file(path: &Path) -> Object {
+    Term::iri(percent_encode(&in_bundle(path).to_string_lossy())).into()
+}
+
+fn number(value: f32) -> Object {
+    let term = if value.is_nan() {
+        Term::typed("NaN", XSD__DOUBLE)
+    } else if value.is_infinite() {
+        Term::typed(if value > 0.0 { "INF" } else { "-INF" }, XSD__DOUBLE)
+    } else {
+        let mut text = value.to_string();
+        if !text.contains('.') {
+            text.push_str(".0");
+        }
+        Term::typed(text, XSD__DECIMAL)
+    };
+    term.into()
+}
+
+/// Return `path` relative to the bundle directory.
+fn in_bundle(path: &Path) -> &Path {
+    match path.file_name() {
+        Some(name) if path.is_absolute() => Path::new(name),
+        _ => path,
+    }
+}
+
+fn load_file(graph: &mut Graph, path: &Path) -> Result<(), BundleError> {
+    let text = std::fs::read_to_string(path).map_err(|error| BundleError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    graph
+        .load(&text, &path_to_file_uri(path))
+        .map_err(|error| BundleError::Turtle {
+            path: path.to_owned(),
+            error,
+        })
+}
+
+fn unique(items: impl Iterator<Item = String>) -> Vec<String> {
+    let mut unique = Vec::new();
+    for item in items {
+        if !unique.contains(&item) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
+/// Return the `file:` URI of an absolute path.
+///
+/// Windows paths like `C:\x` become `file:///C:/x`.
+pub fn path_to_file_uri(path: &Path) -> String {
+    let mut path = path.to_string_lossy().into_owned();
+    if std::path::MAIN_SEPARATOR != '/' {
+        path = path.replace(std::path::MAIN_SEPARATOR, "/");
+    }
+    if !path.starts_with('/') {
+        path.insert(0, '/');
+    }
+    format!("file://{}", percent_encode(&path))
+}
+
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::new();
+    for &byte in path.as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~:".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Return the path of a `file:` URI, or `None` if it is not one or names
+/// a host other than `localhost`.
+pub fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let (host, path) = rest.split_at(rest.find('/')?);
+    if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+        return None;
+    }
+    let path = &path[..path.find(['?', '#']).unwrap_or(path.len())];
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut iter = path.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    if std::path::MAIN_SEPARATOR == '/' {
+        return Some(PathBuf::from(path));
+    }
+    // `/C:/x` is the path `C:\x`.
+    let drive = path.as_bytes().get(1..3);
+    let path = match drive {
+        Some([letter, b':']) if letter.is_ascii_alphabetic() => &path[1..],
+        _ => &path[..],
+    };
+    Some(PathBuf::from(
+        path.replace('/', std::path::MAIN_SEPARATOR_STR),
+    ))
+}
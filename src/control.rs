@@ -0,0 +1,225 @@
+//! A typed model of control ports, for automation and generic UIs.
+//!
+//! [`ControlPort`] gathers what the data of a plugin says about a control
+//! port — its range, whether it takes integers, on/off values or a fixed
+//! set of named values, whether it is best shown on a logarithmic scale,
+//! and its unit — and maps values the way a host should:
+//!
+//! ```
+//! use lv2_raw::bundle::PortDescription;
+//! use lv2_raw::control::ControlPort;
+//! use lv2_raw::*;
+//!
+//! let cutoff = ControlPort::from_description(&PortDescription {
+//!     symbol: "cutoff".to_owned(),
+//!     port_type: Some(PortType::Control),
+//!     direction: Some(PortDirection::Input),
+//!     default: Some(1000.0),
+//!     minimum: Some(20.0),
+//!     maximum: Some(20000.0),
+//!     properties: vec![LV2_PORT_PROPS__LOGARITHMIC.as_str().to_owned()],
+//!     unit: Some(LV2_UNITS__HZ.as_str().to_owned()),
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//! assert_eq!(cutoff.clamp(50000.0), 20000.0);
+//! assert!((cutoff.normalize(632.456) - 0.5).abs() < 1e-4);
+//! assert_eq!(cutoff.unit, Some(Unit::Hz));
+//! ```
+
+use crate::bundle::{PortDescription, ScalePoint};
+use crate::core::{
+    LV2_CORE__ENABLED, LV2_CORE__ENUMERATION, LV2_CORE__INTEGER, LV2_CORE__SAMPLERATE,
+    LV2_CORE__TOGGLED, PortDirection, PortType,
+};
+use crate::portprops::LV2_PORT_PROPS__LOGARITHMIC;
+use crate::units::Unit;
+
+/// A control port, with everything needed to show and edit its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlPort {
+    /// `lv2:index`.
+    pub index: u32,
+    /// `lv2:symbol`.
+    pub symbol: String,
+    /// `lv2:name`, or the symbol if the port has no name.
+    pub name: String,
+    /// Whether the plugin reads or writes the port.
+    pub direction: PortDirection,
+    /// `lv2:default`, or the minimum if the port has none.
+    pub default: f32,
+    /// `lv2:minimum`, 0 if the port has none.
+    pub minimum: f32,
+    /// `lv2:maximum`, 1 if the port has none.
+    pub maximum: f32,
+    /// `lv2:integer`: the port only takes integer values.
+    pub integer: bool,
+    /// `lv2:toggled`: the port is off at the minimum and on at the maximum.
+    pub toggled: bool,
+    /// `lv2:enumeration`: the port only takes the values of its scale
+    /// points.
+    pub enumeration: bool,
+    /// `pprops:logarithmic`: the port is best shown on a logarithmic scale.
+    pub logarithmic: bool,
+    /// `lv2:sampleRate`: the bounds and default are fractions of the sample
+    /// rate; see [`at_sample_rate`](Self::at_sample_rate).
+    pub sample_rate: bool,
+    /// The named values, sorted by value.
+    pub scale_points: Vec<ScalePoint>,
+    /// `units:unit`, if it is a unit of the units extension.
+    pub unit: Option<Unit>,
+    /// The URI of the `lv2:designation` of the port, if it has one.  Ports
+    /// the host drives itself, like `lv2:enabled`, are usually not shown.
+    pub designation: Option<String>,
+}
+
+impl ControlPort {
+    /// Return the model of a port, or `None` if it is not a control port.
+    ///
+    /// Missing bounds default to 0 and 1, and a missing or out-of-range
+    /// default to the nearest bound.  Bounds given the wrong way round are
+    /// swapped.
+    pub fn from_description(port: &PortDescription) -> Option<Self> {
+        if port.port_type != Some(PortType::Control) {
+            return None;
+        }
+        let minimum = port.minimum.unwrap_or(0.0);
+        let maximum = port.maximum.unwrap_or(1.0);
+        let (minimum, maximum) = if minimum <= maximum {
+            (minimum, maximum)
+        } else {
+            (maximum, minimum)
+        };
+        let mut scale_points = port.scale_points.clone();
+        scale_points.sort_by(|a, b| a.value.total_cmp(&b.value));
+
+        Some(ControlPort {
+            index: port.index,
+            symbol: port.symbol.clone(),
+            name: port.name.clone().unwrap_or_else(|| port.symbol.clone()),
+            direction: port.direction?,
+            default: port.default.unwrap_or(minimum).clamp(minimum, maximum),
+            minimum,
+            maximum,
+            integer: port.has_property(LV2_CORE__INTEGER),
+            toggled: port.has_property(LV2_CORE__TOGGLED),
+            enumeration: port.has_property(LV2_CORE__ENUMERATION),
+            logarithmic: port.has_property(LV2_PORT_PROPS__LOGARITHMIC),
+            sample_rate: port.has_property(LV2_CORE__SAMPLERATE),
+            scale_points,
+            unit: port.unit.as_deref().and_then(Unit::from_uri),
+            designation: port.designation.clone(),
+        })
+    }
+
+    /// Return the port with bounds and default in absolute units for
+    /// `sample_rate`, if they are fractions of it.
+    pub fn at_sample_rate(&self, sample_rate: f32) -> Self {
+        let mut port = self.clone();
+        if port.sample_rate {
+            port.minimum *= sample_rate;
+            port.maximum *= sample_rate;
+            port.default *= sample_rate;
+            if port.minimum > port.maximum {
+                (port.minimum, port.maximum) = (port.maximum, port.minimum);
+            }
+            port.sample_rate = false;
+        }
+        port
+    }
+
+    /// Return true iff this is an input port.
+    pub fn is_input(&self) -> bool {
+        self.direction == PortDirection::Input
+    }
+
+    /// Return true iff the port is the `lv2:enabled` input through which
+    /// the host bypasses the plugin.
+    pub fn is_enabled_port(&self) -> bool {
+        self.is_input() && self.designation.as_deref() == Some(LV2_CORE__ENABLED.as_str())
+    }
+
+    /// Return `value` limited to the range of the port.  NaN is replaced
+    /// by the default.
+    ///
+    /// This does not panic if the bounds were edited the wrong way round;
+    /// the maximum wins.
+    pub fn clamp(&self, value: f32) -> f32 {
+        if value.is_nan() {
+            self.default
+        } else {
+            value.max(self.minimum).min(self.maximum)
+        }
+    }
+
+    /// Return the valid value closest to `value`: within the range, and the
+    /// nearest scale point of an enumeration, the nearest integer of an
+    /// integer port, or the nearest bound of a toggle.
+    pub fn quantize(&self, value: f32) -> f32 {
+        let value = self.clamp(value);
+        if self.toggled {
+            let middle = self.minimum + (self.maximum - self.minimum) / 2.0;
+            if value > middle {
+                self.maximum
+            } else {
+                self.minimum
+            }
+        } else if self.enumeration && !self.scale_points.is_empty() {
+            self.scale_points
+                .iter()
+                .map(|point| point.value)
+                .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+                .unwrap_or(value)
+        } else if self.integer {
+            self.clamp(value.round())
+        } else {
+            value
+        }
+    }
+
+    /// Map `value` to `[0, 1]`, as for the position of a slider.
+    ///
+    /// Logarithmic ports are mapped logarithmically if both bounds are
+    /// positive, and linearly otherwise.
+    pub fn normalize(&self, value: f32) -> f32 {
+        let value = self.clamp(value);
+        if self.maximum <= self.minimum {
+            return 0.0;
+        }
+        let position = if self.is_log_scale() {
+            (value / self.minimum).ln() / (self.maximum / self.minimum).ln()
+        } else {
+            (value - self.minimum) / (self.maximum - self.minimum)
+        };
+        position.clamp(0.0, 1.0)
+    }
+
+    /// Map a position in `[0, 1]` back to a valid value: the inverse of
+    /// [`normalize`](Self::normalize), followed by
+    /// [`quantize`](Self::quantize).
+    pub fn denormalize(&self, position: f32) -> f32 {
+        let position = if position.is_nan() {
+            0.0
+        } else {
+            position.clamp(0.0, 1.0)
+        };
+        let value = if self.is_log_scale() {
+            self.minimum * (self.maximum / self.minimum).powf(position)
+        } else {
+            self.minimum + position * (self.maximum - self.minimum)
+        };
+        self.quantize(value)
+    }
+
+    /// Return the label of the scale point at `value`, if there is one.
+    pub fn label(&self, value: f32) -> Option<&str> {
+        self.scale_points
+            .iter()
+            .find(|point| point.value == value)
+            .map(|point| point.label.as_str())
+    }
+
+    fn is_log_scale(&self) -> bool {
+        self.logarithmic && self.minimum > 0.0
+    }
+}
@@ -25,7 +25,115 @@
 
 //! Documentation of the corresponding C header files: <http://lv2plug.in/ns/lv2core/>.
 
-use std::os::raw::*;
+use core::ffi::*;
+
+use crate::atom::LV2_ATOM__ATOMPORT;
+use crate::uri::Uri;
+
+pub const LV2_CORE_URI: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core");
+pub const LV2_CORE_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#");
+
+pub const LV2_CORE__ALLPASSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#AllpassPlugin");
+pub const LV2_CORE__AMPLIFIERPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#AmplifierPlugin");
+pub const LV2_CORE__ANALYSERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#AnalyserPlugin");
+pub const LV2_CORE__AUDIOPORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#AudioPort");
+pub const LV2_CORE__BANDPASSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#BandpassPlugin");
+pub const LV2_CORE__CVPORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#CVPort");
+pub const LV2_CORE__CHORUSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ChorusPlugin");
+pub const LV2_CORE__COMBPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#CombPlugin");
+pub const LV2_CORE__COMPRESSORPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#CompressorPlugin");
+pub const LV2_CORE__CONSTANTPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ConstantPlugin");
+pub const LV2_CORE__CONTROLPORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ControlPort");
+pub const LV2_CORE__CONVERTERPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#ConverterPlugin");
+pub const LV2_CORE__DELAYPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#DelayPlugin");
+pub const LV2_CORE__DISTORTIONPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#DistortionPlugin");
+pub const LV2_CORE__DYNAMICSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#DynamicsPlugin");
+pub const LV2_CORE__EQPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#EQPlugin");
+pub const LV2_CORE__ENVELOPEPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#EnvelopePlugin");
+pub const LV2_CORE__EXPANDERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ExpanderPlugin");
+pub const LV2_CORE__EXTENSIONDATA: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ExtensionData");
+pub const LV2_CORE__FEATURE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Feature");
+pub const LV2_CORE__FILTERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#FilterPlugin");
+pub const LV2_CORE__FLANGERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#FlangerPlugin");
+pub const LV2_CORE__FUNCTIONPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#FunctionPlugin");
+pub const LV2_CORE__GATEPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#GatePlugin");
+pub const LV2_CORE__GENERATORPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#GeneratorPlugin");
+pub const LV2_CORE__HIGHPASSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#HighpassPlugin");
+pub const LV2_CORE__INPUTPORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#InputPort");
+pub const LV2_CORE__INSTRUMENTPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#InstrumentPlugin");
+pub const LV2_CORE__LIMITERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#LimiterPlugin");
+pub const LV2_CORE__LOWPASSPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#LowpassPlugin");
+pub const LV2_CORE__MIDIPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#MIDIPlugin");
+pub const LV2_CORE__MIXERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#MixerPlugin");
+pub const LV2_CORE__MODULATORPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#ModulatorPlugin");
+pub const LV2_CORE__MULTIEQPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#MultiEQPlugin");
+pub const LV2_CORE__OSCILLATORPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#OscillatorPlugin");
+pub const LV2_CORE__OUTPUTPORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#OutputPort");
+pub const LV2_CORE__PARAEQPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ParaEQPlugin");
+pub const LV2_CORE__PHASERPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#PhaserPlugin");
+pub const LV2_CORE__PITCHPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#PitchPlugin");
+pub const LV2_CORE__PLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Plugin");
+pub const LV2_CORE__PLUGINBASE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#PluginBase");
+pub const LV2_CORE__POINT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Point");
+pub const LV2_CORE__PORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Port");
+pub const LV2_CORE__PORTPROPERTY: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#PortProperty");
+pub const LV2_CORE__RESOURCE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Resource");
+pub const LV2_CORE__REVERBPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ReverbPlugin");
+pub const LV2_CORE__SCALEPOINT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#ScalePoint");
+pub const LV2_CORE__SIMULATORPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#SimulatorPlugin");
+pub const LV2_CORE__SPATIALPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#SpatialPlugin");
+pub const LV2_CORE__SPECIFICATION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#Specification");
+pub const LV2_CORE__SPECTRALPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#SpectralPlugin");
+pub const LV2_CORE__UTILITYPLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#UtilityPlugin");
+pub const LV2_CORE__WAVESHAPERPLUGIN: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#WaveshaperPlugin");
+pub const LV2_CORE__APPLIESTO: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#appliesTo");
+pub const LV2_CORE__BINARY: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#binary");
+pub const LV2_CORE__CONNECTIONOPTIONAL: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#connectionOptional");
+pub const LV2_CORE__CONTROL: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#control");
+pub const LV2_CORE__DEFAULT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#default");
+pub const LV2_CORE__DESIGNATION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#designation");
+pub const LV2_CORE__DOCUMENTATION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#documentation");
+pub const LV2_CORE__ENABLED: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#enabled");
+pub const LV2_CORE__ENUMERATION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#enumeration");
+pub const LV2_CORE___EXTENSIONDATA: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#extensionData");
+pub const LV2_CORE__FREEWHEELING: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#freeWheeling");
+pub const LV2_CORE__HARDRTCAPABLE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#hardRTCapable");
+pub const LV2_CORE__INPLACEBROKEN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#inPlaceBroken");
+pub const LV2_CORE__INDEX: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#index");
+pub const LV2_CORE__INTEGER: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#integer");
+pub const LV2_CORE__ISLIVE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#isLive");
+pub const LV2_CORE__ISSIDECHAIN: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#isSideChain");
+pub const LV2_CORE__LATENCY: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#latency");
+pub const LV2_CORE__MAXIMUM: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#maximum");
+pub const LV2_CORE__MICROVERSION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#microVersion");
+pub const LV2_CORE__MINIMUM: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#minimum");
+pub const LV2_CORE__MINORVERSION: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#minorVersion");
+pub const LV2_CORE__NAME: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#name");
+pub const LV2_CORE__OPTIONALFEATURE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#optionalFeature");
+pub const LV2_CORE___PORT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#port");
+pub const LV2_CORE___PORTPROPERTY: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#portProperty");
+pub const LV2_CORE__PROJECT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#project");
+pub const LV2_CORE__PROTOTYPE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#prototype");
+pub const LV2_CORE__REPORTSLATENCY: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#reportsLatency");
+pub const LV2_CORE__REQUIREDFEATURE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/lv2core#requiredFeature");
+pub const LV2_CORE__SAMPLERATE: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#sampleRate");
+pub const LV2_CORE___SCALEPOINT: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#scalePoint");
+pub const LV2_CORE__SHORTNAME: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#shortName");
+pub const LV2_CORE__SYMBOL: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#symbol");
+pub const LV2_CORE__TOGGLED: Uri = Uri::new(c"http://lv2plug.in/ns/lv2core#toggled");
 
 /// Plugin Instance Handle.
 ///
@@ -62,6 +170,18 @@ pub struct LV2Feature {
     pub data: *mut c_void,
 }
 
+impl LV2Feature {
+    /// Return true iff this feature's URI is equal to `uri`.
+    ///
+    /// # Safety
+    ///
+    /// The `uri` field must be NULL or point to a valid NUL-terminated string.
+    #[inline]
+    pub unsafe fn has_uri(&self, uri: impl AsRef<CStr>) -> bool {
+        unsafe { !self.uri.is_null() && CStr::from_ptr(self.uri) == uri.as_ref() }
+    }
+}
+
 /// Plugin Descriptor.
 ///
 /// This structure provides the core functions necessary to instantiate and use
@@ -240,3 +360,208 @@ pub struct LV2Descriptor {
     /// The host is never responsible for freeing the returned value.
     pub extension_data: Option<extern "C" fn(uri: *const c_char) -> *const c_void>,
 }
+
+// A descriptor is immutable: a static URI and function pointers.  This allows
+// plugins to keep their descriptors in statics.
+unsafe impl Sync for LV2Descriptor {}
+
+/**
+   Type of the lv2_descriptor() function in a library (old discovery API).
+*/
+pub type LV2DescriptorFunction = extern "C" fn(index: u32) -> *const LV2Descriptor;
+
+/**
+   Handle for a library descriptor.
+*/
+pub type LV2LibHandle = *mut c_void;
+
+/**
+   Descriptor for a plugin library.
+
+   To access a plugin library, the host creates an LV2_Lib_Descriptor via the
+   lv2_lib_descriptor() function in the shared object.
+*/
+#[repr(C)]
+#[derive(Debug)]
+pub struct LV2LibDescriptor {
+    /**
+       Opaque library data which must be passed as the first parameter to all
+       the methods of this struct.
+    */
+    pub handle: LV2LibHandle,
+
+    /**
+       The total size of this struct.  This allows for this struct to be
+       expanded in the future if necessary.  This MUST be set by the library to
+       sizeof(LV2_Lib_Descriptor).  The host MUST NOT access any fields of this
+       struct beyond get_plugin() unless this field indicates they are present.
+    */
+    pub size: u32,
+
+    /**
+       Destroy this library descriptor and free all related resources.
+    */
+    pub cleanup: extern "C" fn(handle: LV2LibHandle),
+
+    /**
+       Plugin accessor.
+
+       Plugins are accessed by index using values from 0 upwards.  Out of range
+       indices MUST result in this function returning NULL, so the host can
+       enumerate plugins by increasing `index` until NULL is returned.
+    */
+    pub get_plugin: extern "C" fn(handle: LV2LibHandle, index: u32) -> *const LV2Descriptor,
+}
+
+/**
+   Type of the lv2_lib_descriptor() function in an LV2 library.
+
+   This is the entry point for a plugin library.  Hosts load this symbol from
+   the library and call this function to obtain a library descriptor which can
+   be used to access all the contained plugins.  The returned object must not
+   be destroyed (using LV2_Lib_Descriptor::cleanup()) until all plugins loaded
+   from that library have been destroyed.
+*/
+pub type LV2LibDescriptorFunction = extern "C" fn(
+    bundle_path: *const c_char,
+    features: *const *const LV2Feature,
+) -> *const LV2LibDescriptor;
+
+/// The type of a port, i.e. what its buffer contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortType {
+    /// `lv2:AudioPort`: a block of `f32` audio samples.
+    Audio,
+    /// `lv2:ControlPort`: a single `f32` value.
+    Control,
+    /// `lv2:CVPort`: a block of `f32` control values at audio rate.
+    Cv,
+    /// `atom:AtomPort`: an atom, usually an `atom:Sequence`.
+    Atom,
+}
+
+impl PortType {
+    /// All port types.
+    pub const ALL: [PortType; 4] = [
+        PortType::Audio,
+        PortType::Control,
+        PortType::Cv,
+        PortType::Atom,
+    ];
+
+    /// Return the URI of the RDF class for this port type.
+    #[must_use]
+    pub const fn uri(self) -> Uri {
+        match self {
+            PortType::Audio => LV2_CORE__AUDIOPORT,
+            PortType::Control => LV2_CORE__CONTROLPORT,
+            PortType::Cv => LV2_CORE__CVPORT,
+            PortType::Atom => LV2_ATOM__ATOMPORT,
+        }
+    }
+
+    /// Return the port type for a class URI, if it is one.
+    #[must_use]
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.uri() == uri)
+    }
+}
+
+/// The direction of a port, from the plugin's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortDirection {
+    /// `lv2:InputPort`: read by the plugin.
+    Input,
+    /// `lv2:OutputPort`: written by the plugin.
+    Output,
+}
+
+impl PortDirection {
+    /// Both port directions.
+    pub const ALL: [PortDirection; 2] = [PortDirection::Input, PortDirection::Output];
+
+    /// Return the URI of the RDF class for this direction.
+    #[must_use]
+    pub const fn uri(self) -> Uri {
+        match self {
+            PortDirection::Input => LV2_CORE__INPUTPORT,
+            PortDirection::Output => LV2_CORE__OUTPUTPORT,
+        }
+    }
+
+    /// Return the direction for a class URI, if it is one.
+    #[must_use]
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.uri() == uri)
+    }
+}
+
+/// A port property defined by lv2core (the object of `lv2:portProperty`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortProperty {
+    /// `lv2:connectionOptional`: the port may be left unconnected (NULL).
+    ConnectionOptional,
+    /// `lv2:enumeration`: the port only takes the values of its scale points.
+    Enumeration,
+    /// `lv2:integer`: the port only takes integer values.
+    Integer,
+    /// `lv2:isSideChain`: the port is a side-chain, not a main signal.
+    IsSideChain,
+    /// `lv2:reportsLatency`: the port reports the plugin's latency in frames.
+    ReportsLatency,
+    /// `lv2:sampleRate`: bounds and default are multiples of the sample rate.
+    SampleRate,
+    /// `lv2:toggled`: the port is a boolean, 0 is off and anything else is on.
+    Toggled,
+}
+
+impl PortProperty {
+    /// All lv2core port properties.
+    pub const ALL: [PortProperty; 7] = [
+        PortProperty::ConnectionOptional,
+        PortProperty::Enumeration,
+        PortProperty::Integer,
+        PortProperty::IsSideChain,
+        PortProperty::ReportsLatency,
+        PortProperty::SampleRate,
+        PortProperty::Toggled,
+    ];
+
+    /// Return the URI of this property.
+    #[must_use]
+    pub const fn uri(self) -> Uri {
+        match self {
+            PortProperty::ConnectionOptional => LV2_CORE__CONNECTIONOPTIONAL,
+            PortProperty::Enumeration => LV2_CORE__ENUMERATION,
+            PortProperty::Integer => LV2_CORE__INTEGER,
+            PortProperty::IsSideChain => LV2_CORE__ISSIDECHAIN,
+            PortProperty::ReportsLatency => LV2_CORE__REPORTSLATENCY,
+            PortProperty::SampleRate => LV2_CORE__SAMPLERATE,
+            PortProperty::Toggled => LV2_CORE__TOGGLED,
+        }
+    }
+
+    /// Return the property for a URI, if it is an lv2core port property.
+    #[must_use]
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.uri() == uri)
+    }
+}
+
+impl From<PortType> for Uri {
+    fn from(t: PortType) -> Uri {
+        t.uri()
+    }
+}
+
+impl From<PortDirection> for Uri {
+    fn from(d: PortDirection) -> Uri {
+        d.uri()
+    }
+}
+
+impl From<PortProperty> for Uri {
+    fn from(p: PortProperty) -> Uri {
+        p.uri()
+    }
+}
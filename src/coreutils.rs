@@ -4,9 +4,14 @@
 //! Utility functions for LV2 core features.
 
 use crate::core::LV2Feature;
-use std::ffi::CStr;
-use std::os::raw::{c_char, c_void};
-use std::ptr::NonNull;
+use crate::uri::Uri;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::ffi::{CStr, c_char, c_void};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
 
 /// Return the data for a feature in a features array.
 ///
@@ -24,7 +29,7 @@ pub unsafe fn lv2_features_data(
             return None;
         }
 
-        let uri = CStr::from_ptr(curi).to_string_lossy();
+        let uri = CStr::from_ptr(curi);
 
         let mut i = 0;
         loop {
@@ -33,8 +38,7 @@ pub unsafe fn lv2_features_data(
                 break;
             }
 
-            let feature_uri = CStr::from_ptr((*feature).uri).to_string_lossy();
-            if feature_uri == uri {
+            if (*feature).has_uri(uri) {
                 return NonNull::new((*feature).data);
             }
 
@@ -72,13 +76,247 @@ pub unsafe fn lv2_features_query(
     unsafe {
         for it in query {
             let data_ptr = lv2_features_data(features, it.urid);
-            *it.data = data_ptr.map_or(std::ptr::null_mut(), |p| p.as_ptr());
+            *it.data = data_ptr.map_or(core::ptr::null_mut(), |p| p.as_ptr());
 
             if it.required && (*it.data).is_null() {
                 return it.urid;
             }
         }
 
-        std::ptr::null()
+        core::ptr::null()
     }
 }
+
+/// A borrowed, NULL terminated features array.
+///
+/// This is a safe view of the `features` parameter of `instantiate()`.
+#[derive(Clone, Copy)]
+pub struct Features<'a> {
+    features: *const *const LV2Feature,
+    _marker: PhantomData<&'a LV2Feature>,
+}
+
+impl<'a> Features<'a> {
+    /// Wrap a raw features array.
+    ///
+    /// A NULL `features` is treated like an empty array, even though hosts
+    /// must not pass one.
+    ///
+    /// # Safety
+    ///
+    /// `features` must be NULL or a valid NULL terminated array of valid
+    /// features, which stays alive and unchanged for `'a`.
+    pub unsafe fn from_raw(features: *const *const LV2Feature) -> Self {
+        Features {
+            features,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the raw array.
+    pub fn as_ptr(&self) -> *const *const LV2Feature {
+        self.features
+    }
+
+    /// Iterate over the features in the array.
+    pub fn iter(&self) -> FeaturesIter<'a> {
+        FeaturesIter {
+            next: self.features,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the data of the feature with the given URI.
+    ///
+    /// Returns `None` if the feature is not present or its data is NULL.
+    pub fn data(&self, uri: impl AsRef<CStr>) -> Option<NonNull<c_void>> {
+        let uri = uri.as_ref();
+        self.iter()
+            .find(|f| unsafe { f.has_uri(uri) })
+            .and_then(|f| NonNull::new(f.data))
+    }
+
+    /// Return true iff a feature with the given URI is present.
+    pub fn contains(&self, uri: impl AsRef<CStr>) -> bool {
+        let uri = uri.as_ref();
+        self.iter().any(|f| unsafe { f.has_uri(uri) })
+    }
+
+    /// Return the data of feature `T`, if present.
+    pub fn get<T: FeatureData>(&self) -> Option<&'a T> {
+        // The data pointer comes from an array that is valid for 'a.
+        self.data(T::URI)
+            .map(|data| unsafe { &*(data.as_ptr() as *const T) })
+    }
+
+    /// Return the data of feature `T`, or an error if it is missing.
+    pub fn require<T: FeatureData>(&self) -> Result<&'a T, FeatureError> {
+        self.get().ok_or_else(|| FeatureError {
+            missing: vec![T::URI],
+        })
+    }
+
+    /// Resolve several features at once.
+    ///
+    /// `Q` is a feature type, `Option` of a feature type for optional
+    /// features, or a tuple of those.  On failure, the error lists every
+    /// missing required feature, not just the first one.
+    ///
+    /// ```
+    /// use lv2_raw::*;
+    /// # fn instantiate(features: Features<'_>) -> Result<(), FeatureError> {
+    /// let (map, unmap, log) =
+    ///     features.resolve::<(LV2UridMap, LV2UridUnmap, Option<LV2LogLog>)>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve<Q: FeatureQuery<'a>>(&self) -> Result<Q::Output, FeatureError> {
+        let mut missing = Vec::new();
+        match Q::query(self, &mut missing) {
+            Some(output) if missing.is_empty() => Ok(output),
+            _ => Err(FeatureError { missing }),
+        }
+    }
+}
+
+impl<'a> IntoIterator for Features<'a> {
+    type Item = &'a LV2Feature;
+    type IntoIter = FeaturesIter<'a>;
+
+    fn into_iter(self) -> FeaturesIter<'a> {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for Features<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.iter()
+                    .map(|feature| unsafe { CStr::from_ptr(feature.uri) }),
+            )
+            .finish()
+    }
+}
+
+/// Iterator over a features array, see [`Features::iter`].
+pub struct FeaturesIter<'a> {
+    next: *const *const LV2Feature,
+    _marker: PhantomData<&'a LV2Feature>,
+}
+
+impl<'a> Iterator for FeaturesIter<'a> {
+    type Item = &'a LV2Feature;
+
+    fn next(&mut self) -> Option<&'a LV2Feature> {
+        if self.next.is_null() {
+            return None;
+        }
+        unsafe {
+            let feature = *self.next;
+            if feature.is_null() {
+                self.next = core::ptr::null();
+                None
+            } else {
+                self.next = self.next.add(1);
+                Some(&*feature)
+            }
+        }
+    }
+}
+
+/// A struct which is passed as the data of a feature.
+///
+/// This pairs a feature URI with the type its data points to, which lets
+/// [`Features`] look up features by type.
+///
+/// # Safety
+///
+/// The specification of the feature `URI` must require its data to point to
+/// a valid `Self`.
+pub unsafe trait FeatureData {
+    /// URI of the feature.
+    const URI: Uri;
+}
+
+/// A set of features to look up with [`Features::resolve`].
+///
+/// This is implemented for every [`FeatureData`] type (a required feature),
+/// for `Option` of them (an optional feature), and for tuples of up to eight
+/// queries.
+pub trait FeatureQuery<'a>: Sized {
+    /// What a successful query returns.
+    type Output;
+
+    /// Look up the features, adding every missing required one to `missing`.
+    ///
+    /// Returns `None` if any required feature is missing.
+    fn query(features: &Features<'a>, missing: &mut Vec<Uri>) -> Option<Self::Output>;
+}
+
+impl<'a, T: FeatureData + 'a> FeatureQuery<'a> for T {
+    type Output = &'a T;
+
+    fn query(features: &Features<'a>, missing: &mut Vec<Uri>) -> Option<&'a T> {
+        let data = features.get::<T>();
+        if data.is_none() {
+            missing.push(T::URI);
+        }
+        data
+    }
+}
+
+impl<'a, T: FeatureData + 'a> FeatureQuery<'a> for Option<T> {
+    type Output = Option<&'a T>;
+
+    fn query(features: &Features<'a>, _missing: &mut Vec<Uri>) -> Option<Option<&'a T>> {
+        Some(features.get::<T>())
+    }
+}
+
+macro_rules! tuple_feature_query {
+    ($($name:ident),+) => {
+        impl<'a, $($name: FeatureQuery<'a>),+> FeatureQuery<'a> for ($($name,)+) {
+            type Output = ($($name::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn query(features: &Features<'a>, missing: &mut Vec<Uri>) -> Option<Self::Output> {
+                // Query everything before bailing out, to collect all missing features.
+                $(let $name = $name::query(features, missing);)+
+                Some(($($name?,)+))
+            }
+        }
+    };
+}
+
+tuple_feature_query!(A);
+tuple_feature_query!(A, B);
+tuple_feature_query!(A, B, C);
+tuple_feature_query!(A, B, C, D);
+tuple_feature_query!(A, B, C, D, E);
+tuple_feature_query!(A, B, C, D, E, F);
+tuple_feature_query!(A, B, C, D, E, F, G);
+tuple_feature_query!(A, B, C, D, E, F, G, H);
+
+/// Error returned when required features are missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureError {
+    /// URIs of all missing required features.
+    pub missing: Vec<Uri>,
+}
+
+impl fmt::Display for FeatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing required feature")?;
+        if self.missing.len() != 1 {
+            f.write_str("s")?;
+        }
+        for (i, uri) in self.missing.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{sep}<{uri}>")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FeatureError {}
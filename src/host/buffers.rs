@@ -0,0 +1,447 @@
+//! Buffers for the ports of a plugin instance.
+
+use super::instance::{Instance, InstanceError};
+use super::port::PortInfo;
+use crate::atom::{
+    LV2_ATOM__CHUNK, LV2_ATOM__SEQUENCE, LV2Atom, LV2AtomEvent, LV2AtomSequence,
+    LV2AtomSequenceBody,
+};
+use crate::atomutils::{lv2_atom_sequence_append_event, lv2_atom_sequence_clear};
+use crate::bundle::PortDescription;
+use crate::core::PortType;
+use crate::urid::{LV2Urid, LV2UridMap};
+use std::error::Error;
+use std::fmt;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Error returned when port buffers can not be set up as requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferError {
+    /// The port index is out of range.
+    NoSuchPort(u32),
+    /// Only an audio or CV input can share its buffer with an output of the
+    /// same type.
+    CanNotShare { input: u32, output: u32 },
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferError::NoSuchPort(index) => write!(f, "no port {index}"),
+            BufferError::CanNotShare { input, output } => {
+                write!(f, "port {output} can not share the buffer of port {input}")
+            }
+        }
+    }
+}
+
+impl Error for BufferError {}
+
+enum Buffer {
+    /// Audio or CV samples, one block long.
+    Samples(Vec<f32>),
+    /// A single control value.  Boxed so it never moves.
+    Control(Box<f32>),
+    /// An atom, as 64-bit words to keep it aligned.
+    Atom(Vec<u64>),
+    /// The buffer of another port, for in-place processing.
+    Shared(u32),
+}
+
+/// Buffers for every port of a plugin, of the right type and size.
+///
+/// Audio and CV ports get one block of `f32` samples, control ports a single
+/// `f32`, and atom ports an `atom:Sequence` with a fixed capacity in bytes.
+/// Control and CV buffers start out at the default of their port, 0 unless
+/// set with [`set_default`](PortBuffers::set_default).
+/// An output port may share the buffer of an input port of the same type,
+/// to have the plugin process in place.
+///
+/// The buffers must be connected to the instance with
+/// [`connect`](PortBuffers::connect), and again whenever they move, which
+/// [`set_block_size`](PortBuffers::set_block_size) and
+/// [`share`](PortBuffers::share) take care of.
+pub struct PortBuffers {
+    ports: Vec<PortInfo>,
+    buffers: Vec<Buffer>,
+    defaults: Vec<f32>,
+    block_size: u32,
+    atom_capacity: u32,
+    sequence_urid: LV2Urid,
+    chunk_urid: LV2Urid,
+}
+
+impl PortBuffers {
+    /// Allocate buffers for `ports`.
+    ///
+    /// `atom_capacity` is the size in bytes of each atom buffer, including
+    /// the atom header.  `map` is used to map the atom types.
+    pub fn new(ports: &[PortInfo], block_size: u32, atom_capacity: u32, map: &LV2UridMap) -> Self {
+        let atom_capacity = atom_capacity.max(size_of::<LV2AtomSequence>() as u32);
+        let mut buffers = PortBuffers {
+            ports: ports.to_vec(),
+            buffers: Vec::with_capacity(ports.len()),
+            defaults: vec![0.0; ports.len()],
+            block_size,
+            atom_capacity,
+            sequence_urid: map.map(LV2_ATOM__SEQUENCE),
+            chunk_urid: map.map(LV2_ATOM__CHUNK),
+        };
+        for index in 0..ports.len() {
+            let buffer = buffers.allocate(index);
+            buffers.buffers.push(buffer);
+        }
+        buffers.prepare_atoms();
+        buffers
+    }
+
+    fn allocate(&self, index: usize) -> Buffer {
+        let default = self.defaults[index];
+        match self.ports[index].port_type {
+            PortType::Audio | PortType::Cv => {
+                Buffer::Samples(vec![default; self.block_size as usize])
+            }
+            PortType::Control => Buffer::Control(Box::new(default)),
+            PortType::Atom => Buffer::Atom(vec![0; self.atom_capacity.div_ceil(8) as usize]),
+        }
+    }
+
+    /// Return the ports the buffers are for.
+    pub fn ports(&self) -> &[PortInfo] {
+        &self.ports
+    }
+
+    /// Return the number of frames in audio and CV buffers.
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    /// Return the capacity of atom buffers in bytes.
+    pub fn atom_capacity(&self) -> u32 {
+        self.atom_capacity
+    }
+
+    /// Return the pointer to connect port `index` to.
+    pub fn data(&mut self, index: u32) -> Option<*mut c_void> {
+        Some(match self.resolve_mut(index)? {
+            Buffer::Samples(samples) => samples.as_mut_ptr() as *mut c_void,
+            Buffer::Control(value) => &mut **value as *mut f32 as *mut c_void,
+            Buffer::Atom(words) => words.as_mut_ptr() as *mut c_void,
+            Buffer::Shared(_) => unreachable!("buffers are only shared with owned buffers"),
+        })
+    }
+
+    /// Connect every port of `instance` to its buffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffers must outlive the connections, i.e. `self` must not be
+    /// dropped before the instance, unless the ports are connected elsewhere
+    /// before the next `run()`.
+    pub unsafe fn connect(&mut self, instance: &mut Instance<'_>) -> Result<(), InstanceError> {
+        for index in 0..self.buffers.len() as u32 {
+            let data = self.data(index).unwrap_or(ptr::null_mut());
+            unsafe { instance.connect_port(index, data)? };
+        }
+        Ok(())
+    }
+
+    /// Change the block size, and reconnect the instance if the audio and CV
+    /// buffers had to be reallocated.
+    ///
+    /// # Safety
+    ///
+    /// See [`connect`](PortBuffers::connect).
+    pub unsafe fn set_block_size(
+        &mut self,
+        instance: &mut Instance<'_>,
+        block_size: u32,
+    ) -> Result<(), InstanceError> {
+        if block_size == self.block_size {
+            return Ok(());
+        }
+        self.block_size = block_size;
+        for index in 0..self.buffers.len() {
+            if let Buffer::Samples(_) = self.buffers[index] {
+                self.buffers[index] = self.allocate(index);
+            }
+        }
+        unsafe { self.connect(instance) }
+    }
+
+    /// Make output port `output` use the buffer of input port `input`, and
+    /// reconnect it.
+    ///
+    /// Both ports must be audio ports, or both CV ports.  Plugins with the
+    /// `lv2:inPlaceBroken` feature do not support this.
+    ///
+    /// # Safety
+    ///
+    /// See [`connect`](PortBuffers::connect).
+    pub unsafe fn share(
+        &mut self,
+        instance: &mut Instance<'_>,
+        input: u32,
+        output: u32,
+    ) -> Result<(), BufferError> {
+        let Some(i) = self.ports.get(input as usize) else {
+            return Err(BufferError::NoSuchPort(input));
+        };
+        let Some(o) = self.ports.get(output as usize) else {
+            return Err(BufferError::NoSuchPort(output));
+        };
+        let signal = matches!(i.port_type, PortType::Audio | PortType::Cv);
+        if !signal || i.port_type != o.port_type || !i.is_input() || !o.is_output() {
+            return Err(BufferError::CanNotShare { input, output });
+        }
+        if let Buffer::Shared(_) = self.buffers[input as usize] {
+            return Err(BufferError::CanNotShare { input, output });
+        }
+
+        self.buffers[output as usize] = Buffer::Shared(input);
+        let data = self.data(output).unwrap();
+        unsafe { instance.connect_port(output, data) }.map_err(|_| BufferError::NoSuchPort(output))
+    }
+
+    /// Give output port `output` its own buffer again, and reconnect it.
+    ///
+    /// # Safety
+    ///
+    /// See [`connect`](PortBuffers::connect).
+    pub unsafe fn unshare(
+        &mut self,
+        instance: &mut Instance<'_>,
+        output: u32,
+    ) -> Result<(), BufferError> {
+        match self.buffers.get(output as usize) {
+            Some(Buffer::Shared(_)) => {}
+            Some(_) => return Ok(()),
+            None => return Err(BufferError::NoSuchPort(output)),
+        }
+        self.buffers[output as usize] = self.allocate(output as usize);
+        let data = self.data(output).unwrap();
+        unsafe { instance.connect_port(output, data) }.map_err(|_| BufferError::NoSuchPort(output))
+    }
+
+    /// Return true iff port `index` uses the buffer of another port.
+    pub fn is_shared(&self, index: u32) -> bool {
+        matches!(self.buffers.get(index as usize), Some(Buffer::Shared(_)))
+    }
+
+    fn resolve(&self, index: u32) -> Option<&Buffer> {
+        match self.buffers.get(index as usize)? {
+            Buffer::Shared(source) => self.buffers.get(*source as usize),
+            buffer => Some(buffer),
+        }
+    }
+
+    fn resolve_mut(&mut self, index: u32) -> Option<&mut Buffer> {
+        let index = match self.buffers.get(index as usize)? {
+            Buffer::Shared(source) => *source,
+            _ => index,
+        };
+        self.buffers.get_mut(index as usize)
+    }
+
+    /// Return the samples of an audio or CV port.
+    pub fn samples(&self, index: u32) -> Option<&[f32]> {
+        match self.resolve(index)? {
+            Buffer::Samples(samples) => Some(samples),
+            _ => None,
+        }
+    }
+
+    /// Return the samples of an audio or CV port for writing.
+    pub fn samples_mut(&mut self, index: u32) -> Option<&mut [f32]> {
+        match self.resolve_mut(index)? {
+            Buffer::Samples(samples) => Some(samples),
+            _ => None,
+        }
+    }
+
+    /// Return the value of a control port.
+    pub fn control(&self, index: u32) -> Option<f32> {
+        match self.resolve(index)? {
+            Buffer::Control(value) => Some(**value),
+            _ => None,
+        }
+    }
+
+    /// Set the value of a control port.  Returns false if it is not one.
+    pub fn set_control(&mut self, index: u32, value: f32) -> bool {
+        match self.resolve_mut(index) {
+            Some(Buffer::Control(cell)) => {
+                **cell = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Fill the buffer of a CV port with `value`, as a control value held
+    /// for the whole block.  Returns false if it is not a CV port.
+    pub fn set_cv(&mut self, index: u32, value: f32) -> bool {
+        if self.ports.get(index as usize).map(|p| p.port_type) != Some(PortType::Cv) {
+            return false;
+        }
+        match self.resolve_mut(index) {
+            Some(Buffer::Samples(samples)) => {
+                samples.fill(value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set the default of a control or CV port, and reset its buffer to it.
+    /// Returns false if it is not one.
+    ///
+    /// The buffer of a CV port is filled with its default again whenever it
+    /// is reallocated, so a CV input the host does not drive keeps it.
+    pub fn set_default(&mut self, index: u32, value: f32) -> bool {
+        let reset = match self.ports.get(index as usize).map(|p| p.port_type) {
+            Some(PortType::Control) => self.set_control(index, value),
+            Some(PortType::Cv) => self.set_cv(index, value),
+            _ => false,
+        };
+        if reset {
+            self.defaults[index as usize] = value;
+        }
+        reset
+    }
+
+    /// Set the defaults of all control and CV ports from the plugin data,
+    /// for the ports which have an `lv2:default`.
+    pub fn set_defaults(&mut self, ports: &[PortDescription]) {
+        for port in ports {
+            if let Some(default) = port.default {
+                self.set_default(port.index, default);
+            }
+        }
+    }
+
+    /// Return a pointer to the sequence of an atom port.
+    ///
+    /// The pointer covers the whole buffer of
+    /// [`atom_capacity`](PortBuffers::atom_capacity) bytes, so the events
+    /// after the header may be read through it.
+    pub fn sequence(&self, index: u32) -> Option<*const LV2AtomSequence> {
+        match self.resolve(index)? {
+            Buffer::Atom(words) => Some(words.as_ptr() as *const LV2AtomSequence),
+            _ => None,
+        }
+    }
+
+    /// Return a pointer to the sequence of an atom port for writing.
+    ///
+    /// The buffer holds [`atom_capacity`](PortBuffers::atom_capacity) bytes,
+    /// including the atom header, so the capacity to pass to
+    /// [`lv2_atom_sequence_append_event`] is 8 bytes less.
+    pub fn sequence_mut(&mut self, index: u32) -> Option<*mut LV2AtomSequence> {
+        match self.resolve_mut(index)? {
+            Buffer::Atom(words) => Some(words.as_mut_ptr() as *mut LV2AtomSequence),
+            _ => None,
+        }
+    }
+
+    /// Return the [`atom_capacity`](PortBuffers::atom_capacity) bytes of
+    /// the buffer of an atom port.
+    pub fn atom_bytes(&self, index: u32) -> Option<&[u8]> {
+        let capacity = self.atom_capacity as usize;
+        match self.resolve(index)? {
+            Buffer::Atom(words) => {
+                Some(unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, capacity) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Reset the atom buffers for the next `run()`.
+    ///
+    /// Input sequences are emptied, and output buffers are marked as an
+    /// `atom:Chunk` spanning the whole capacity, which tells the plugin how
+    /// much space it may write.  Call this before writing the input events
+    /// of each block.
+    pub fn prepare_atoms(&mut self) {
+        let (sequence_urid, chunk_urid) = (self.sequence_urid, self.chunk_urid);
+        let body_capacity = self.atom_capacity - size_of::<LV2Atom>() as u32;
+        for (port, buffer) in self.ports.iter().zip(&mut self.buffers) {
+            let Buffer::Atom(words) = buffer else {
+                continue;
+            };
+            let sequence = words.as_mut_ptr() as *mut LV2AtomSequence;
+            unsafe {
+                if port.is_input() {
+                    (*sequence).atom.type_ = sequence_urid;
+                    (*sequence).body.unit = 0;
+                    (*sequence).body.pad = 0;
+                    lv2_atom_sequence_clear(sequence);
+                } else {
+                    (*sequence).atom.type_ = chunk_urid;
+                    (*sequence).atom.size = body_capacity;
+                }
+            }
+        }
+    }
+
+    /// Return how many 3 byte MIDI events fit in an atom input.
+    pub(crate) fn midi_capacity(&self) -> u32 {
+        let stride = size_of::<MidiEvent>().next_multiple_of(8) as u32;
+        let body_capacity = self.atom_capacity - size_of::<LV2Atom>() as u32;
+        (body_capacity - size_of::<LV2AtomSequenceBody>() as u32) / stride
+    }
+
+    /// Append `(frame, message)` MIDI events to the sequence of an atom
+    /// input, after [`prepare_atoms`](Self::prepare_atoms), until it is full.
+    pub(crate) fn append_midi(
+        &mut self,
+        index: u32,
+        midi_urid: LV2Urid,
+        events: impl IntoIterator<Item = (u32, [u8; 3])>,
+    ) {
+        let capacity = self.atom_capacity - size_of::<LV2Atom>() as u32;
+        let Some(sequence) = self.sequence_mut(index) else {
+            return;
+        };
+        for (frame, data) in events {
+            let event = MidiEvent {
+                event: LV2AtomEvent {
+                    time_in_frames: i64::from(frame),
+                    body: LV2Atom {
+                        size: 3,
+                        type_: midi_urid,
+                    },
+                },
+                data,
+            };
+            let appended = unsafe {
+                lv2_atom_sequence_append_event(
+                    sequence,
+                    capacity,
+                    &event as *const MidiEvent as *const LV2AtomEvent,
+                )
+            };
+            if appended.is_null() {
+                break;
+            }
+        }
+    }
+}
+
+/// A MIDI event as it is stored in a sequence.
+#[repr(C)]
+struct MidiEvent {
+    event: LV2AtomEvent,
+    data: [u8; 3],
+}
+
+impl fmt::Debug for PortBuffers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortBuffers")
+            .field("ports", &self.ports.len())
+            .field("block_size", &self.block_size)
+            .field("atom_capacity", &self.atom_capacity)
+            .finish()
+    }
+}
@@ -0,0 +1,137 @@
+//! Bypassing a plugin without clicks.
+//!
+//! A plugin with a control input designated `lv2:enabled` bypasses itself
+//! when the host sets it to 0, and is expected to fade to its dry signal.
+//! For any other plugin the host has to do the fade itself, by mixing what
+//! the audio inputs held before `run()` into the audio outputs.  [`Bypass`]
+//! does whichever of the two the plugin needs.
+
+use super::buffers::PortBuffers;
+use super::instance::{Instance, InstanceError};
+use super::port::PortInfo;
+use crate::core::{LV2_CORE__ENABLED, PortType};
+use crate::ports::Fade;
+
+/// Return the index of the control input designated `lv2:enabled`, if any.
+pub fn enabled_port(ports: &[PortInfo]) -> Option<u32> {
+    ports
+        .iter()
+        .position(|port| {
+            port.port_type == PortType::Control
+                && port.is_input()
+                && port.is_designated(LV2_CORE__ENABLED)
+        })
+        .map(|index| index as u32)
+}
+
+/// Runs an instance which can be bypassed.
+///
+/// If the plugin has an `lv2:enabled` port, it is set before every run and
+/// the plugin fades by itself.  Otherwise the n-th audio output is faded to
+/// the n-th audio input, or to silence if there are more outputs than
+/// inputs.  The plugin keeps running while it is bypassed, so it is in a
+/// sensible state when it is enabled again; its latency is not compensated
+/// in the dry signal.
+#[derive(Debug)]
+pub struct Bypass {
+    enabled_port: Option<u32>,
+    pairs: Vec<(Option<u32>, u32)>,
+    dry: Vec<Vec<f32>>,
+    fade: Fade,
+}
+
+impl Bypass {
+    /// Create an enabled bypass for an instance with `ports`, which fades
+    /// over `fade_frames` frames.  The dry signal is kept in buffers of
+    /// `block_size` frames, which grow if a larger block is run.
+    pub fn new(ports: &[PortInfo], block_size: u32, fade_frames: u32) -> Self {
+        let audio = |input: bool| {
+            ports
+                .iter()
+                .enumerate()
+                .filter(move |(_, port)| {
+                    port.port_type == PortType::Audio && port.is_input() == input
+                })
+                .map(|(index, _)| index as u32)
+        };
+        let enabled_port = enabled_port(ports);
+        let mut inputs = audio(true);
+        let pairs: Vec<_> = audio(false).map(|output| (inputs.next(), output)).collect();
+        let dry = if enabled_port.is_some() {
+            Vec::new()
+        } else {
+            vec![vec![0.0; block_size as usize]; pairs.len()]
+        };
+        Bypass {
+            enabled_port,
+            pairs,
+            dry,
+            fade: Fade::new(fade_frames, true),
+        }
+    }
+
+    /// Return the index of the `lv2:enabled` port which is driven, or
+    /// `None` if the host fades.
+    pub fn enabled_port(&self) -> Option<u32> {
+        self.enabled_port
+    }
+
+    /// Enable or bypass the plugin from the next run on.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.fade.set(enabled);
+    }
+
+    /// Return true iff the plugin is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.fade.is_on()
+    }
+
+    /// Skip the fade in progress, e.g. after `activate()`.
+    pub fn settle(&mut self) {
+        self.fade.settle();
+    }
+
+    /// Run `instance` for `sample_count` frames with `buffers`, which must
+    /// be connected to it.
+    pub fn run(
+        &mut self,
+        instance: &mut Instance<'_>,
+        buffers: &mut PortBuffers,
+        sample_count: u32,
+    ) -> Result<(), InstanceError> {
+        if let Some(port) = self.enabled_port {
+            let value = if self.fade.is_on() { 1.0 } else { 0.0 };
+            buffers.set_control(port, value);
+            return instance.run(sample_count);
+        }
+        if self.fade.is_on() && self.fade.is_settled() {
+            return instance.run(sample_count);
+        }
+
+        let frames = sample_count as usize;
+        for (dry, &(input, _)) in self.dry.iter_mut().zip(&self.pairs) {
+            if dry.len() < frames {
+                dry.resize(frames, 0.0);
+            }
+            match input.and_then(|input| buffers.samples(input)) {
+                Some(samples) => {
+                    for (dry, sample) in dry[..frames].iter_mut().zip(samples) {
+                        *dry = *sample;
+                    }
+                }
+                None => dry[..frames].fill(0.0),
+            }
+        }
+        instance.run(sample_count)?;
+
+        let ramp = self.fade.block(sample_count);
+        for (dry, &(_, output)) in self.dry.iter().zip(&self.pairs) {
+            if let Some(wet) = buffers.samples_mut(output) {
+                for ((wet, dry), gain) in wet.iter_mut().zip(dry).zip(ramp.clone()) {
+                    *wet = *wet * gain + dry * (1.0 - gain);
+                }
+            }
+        }
+        Ok(())
+    }
+}
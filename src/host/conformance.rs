@@ -0,0 +1,627 @@
+//! Checking that a plugin handles the edge cases the LV2 specification
+//! requires of it.
+
+use super::buffers::PortBuffers;
+use super::instance::{Instance, InstanceError};
+#[cfg(unix)]
+use super::library::LibraryPlugin;
+use super::port::PortInfo;
+use super::rtcheck::is_installed;
+use super::urid::UridMapper;
+use crate::atom::{LV2_ATOM__SEQUENCE, LV2Atom, LV2AtomEvent, LV2AtomSequence};
+use crate::core::{LV2Descriptor, PortType};
+use crate::coreutils::Features;
+use crate::midi::LV2_MIDI__MIDIEVENT;
+use crate::urid::{LV2Urid, LV2UridMap};
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ptr;
+
+/// Written to output buffers before each `run()`, to find frames the plugin
+/// did not write, or wrote past the end of the block.
+const SENTINEL: f32 = 1.234_567_9e30;
+
+/// An edge case exercised by [`Conformance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// `instantiate()` must succeed with the given features.
+    Instantiate,
+    /// `run(0)` must be harmless.
+    RunZero,
+    /// The number of frames may change on every `run()`, and buffers may be
+    /// reallocated and reconnected between calls.
+    BlockSizes,
+    /// An instance may be activated and deactivated several times.
+    ActivationCycles,
+    /// `lv2:connectionOptional` ports may be left unconnected.
+    UnconnectedOptional,
+    /// Audio and CV outputs may share the buffer of an input, unless the
+    /// plugin requires `lv2:inPlaceBroken`.
+    InPlace,
+    /// `extension_data()` must return NULL for unknown URIs.
+    ExtensionData,
+    /// Atom inputs may hold empty sequences.
+    EmptyAtoms,
+    /// Atom inputs may be filled to capacity.
+    LargeAtoms,
+    /// `run()` must not allocate, free or block, if the plugin declares
+    /// `lv2:hardRTCapable`.  Only run with
+    /// [`hard_rt_capable`](Conformance::hard_rt_capable).
+    RealTime,
+}
+
+impl Check {
+    /// All checks, in the order they are run.
+    pub const ALL: [Check; 10] = [
+        Check::Instantiate,
+        Check::RunZero,
+        Check::BlockSizes,
+        Check::ActivationCycles,
+        Check::UnconnectedOptional,
+        Check::InPlace,
+        Check::ExtensionData,
+        Check::EmptyAtoms,
+        Check::LargeAtoms,
+        Check::RealTime,
+    ];
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Check::Instantiate => "instantiate",
+            Check::RunZero => "run(0)",
+            Check::BlockSizes => "changing block sizes",
+            Check::ActivationCycles => "activation cycles",
+            Check::UnconnectedOptional => "unconnected optional ports",
+            Check::InPlace => "in-place buffers",
+            Check::ExtensionData => "unknown extension data",
+            Check::EmptyAtoms => "empty atom input",
+            Check::LargeAtoms => "large atom input",
+            Check::RealTime => "real-time safety",
+        })
+    }
+}
+
+/// A problem found by a check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The check which found the problem.
+    pub check: Check,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.check, self.message)
+    }
+}
+
+/// The result of [`Conformance::run`].
+#[derive(Debug, Clone)]
+pub struct ConformanceReport {
+    /// The plugin URI.
+    pub uri: CString,
+    /// The checks which were run.
+    pub checks: Vec<Check>,
+    /// The problems found.
+    pub violations: Vec<Violation>,
+}
+
+impl ConformanceReport {
+    /// Return true iff no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Return the problems found by `check`.
+    pub fn violations_of(&self, check: Check) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().filter(move |v| v.check == check)
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uri = self.uri.to_string_lossy();
+        if self.is_ok() {
+            return write!(f, "<{uri}>: passed {} checks", self.checks.len());
+        }
+        write!(f, "<{uri}>: {} violations", self.violations.len())?;
+        for violation in &self.violations {
+            write!(f, "\n  {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A test harness which runs a plugin through the edge cases of the LV2
+/// specification (see [`Check`]), and reports what it gets wrong.
+///
+/// After every `run()`, the outputs are checked:
+///
+/// - audio, CV and control outputs must be finite and not denormal, and
+///   audio and CV outputs must be written for every frame of the block, and
+///   no further;
+/// - atom outputs must hold a well-formed `atom:Sequence` within the buffer,
+///   with events in order and inside the block.
+///
+/// Every check uses a fresh instance.  Inputs are a deterministic signal on
+/// audio and CV ports, and the values set with
+/// [`control`](Conformance::control) on control ports.
+///
+/// The harness runs the plugin in-process, so a plugin which crashes takes
+/// the harness down with it.  Run each plugin in its own process, e.g. its
+/// own test, to tell crashes apart.
+///
+/// ```no_run
+/// use lv2_raw::host::{Conformance, FeatureList, Library, UridMapper};
+/// use lv2_raw::bundle::Bundle;
+///
+/// let bundle = Bundle::load("amp.lv2").unwrap();
+/// let description = &bundle.plugins[0];
+/// let library = Library::open(description.binary.as_ref().unwrap()).unwrap();
+/// let plugin = library.find(c"urn:example:amp").unwrap();
+///
+/// let mapper = UridMapper::new();
+/// let mut features = FeatureList::new();
+/// features.urid_map(&mapper);
+/// let ports = description.port_infos().unwrap();
+/// let report = Conformance::for_plugin(&plugin, features.features(), ports).run();
+/// assert!(report.is_ok(), "{report}");
+/// ```
+pub struct Conformance<'a> {
+    descriptor: &'a LV2Descriptor,
+    bundle_path: CString,
+    features: Features<'a>,
+    ports: Vec<PortInfo>,
+    sample_rate: f64,
+    block_size: u32,
+    atom_capacity: u32,
+    controls: Vec<(u32, f32)>,
+    in_place: bool,
+    hard_rt: bool,
+    mapper: UridMapper,
+}
+
+impl<'a> Conformance<'a> {
+    /// Create a harness for the plugin described by `descriptor`.
+    ///
+    /// `ports` must describe the ports of the plugin, in index order.  If
+    /// `features` has no `urid:map`, atom types are mapped by the harness.
+    pub fn new(
+        descriptor: &'a LV2Descriptor,
+        bundle_path: &CStr,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Self {
+        Conformance {
+            descriptor,
+            bundle_path: bundle_path.to_owned(),
+            features,
+            ports,
+            sample_rate: 48000.0,
+            block_size: 256,
+            atom_capacity: 8192,
+            controls: Vec::new(),
+            in_place: true,
+            hard_rt: false,
+            mapper: UridMapper::new(),
+        }
+    }
+
+    /// Create a harness for a plugin from a loaded library.
+    #[cfg(unix)]
+    pub fn for_plugin(
+        plugin: &'a LibraryPlugin,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Self {
+        Self::new(
+            plugin.descriptor(),
+            plugin.library().bundle_path(),
+            features,
+            ports,
+        )
+    }
+
+    /// Set the sample rate, 48 kHz by default.
+    pub fn sample_rate(&mut self, sample_rate: f64) -> &mut Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the largest block size, 256 frames by default.  Smaller blocks
+    /// are run as well, and twice this size once.
+    pub fn block_size(&mut self, block_size: u32) -> &mut Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Set the size of atom buffers in bytes, 8192 by default.
+    pub fn atom_capacity(&mut self, atom_capacity: u32) -> &mut Self {
+        self.atom_capacity = atom_capacity;
+        self
+    }
+
+    /// Set the value of control input `index`, 0 by default.
+    pub fn control(&mut self, index: u32, value: f32) -> &mut Self {
+        self.controls.retain(|(i, _)| *i != index);
+        self.controls.push((index, value));
+        self
+    }
+
+    /// Skip [`Check::InPlace`], for plugins which require
+    /// `lv2:inPlaceBroken`.
+    pub fn in_place_broken(&mut self) -> &mut Self {
+        self.in_place = false;
+        self
+    }
+
+    /// Run [`Check::RealTime`], for plugins which declare
+    /// `lv2:hardRTCapable`.
+    ///
+    /// This needs [`RtCheckAllocator`](super::RtCheckAllocator) as the
+    /// global allocator of the program; without it, the check reports only
+    /// that.
+    pub fn hard_rt_capable(&mut self) -> &mut Self {
+        self.hard_rt = true;
+        self
+    }
+
+    /// Run all checks.
+    pub fn run(&self) -> ConformanceReport {
+        let mut report = ConformanceReport {
+            uri: unsafe { CStr::from_ptr(self.descriptor.uri) }.to_owned(),
+            checks: Vec::new(),
+            violations: Vec::new(),
+        };
+        for check in Check::ALL {
+            if check == Check::InPlace && !self.in_place
+                || check == Check::RealTime && !self.hard_rt
+            {
+                continue;
+            }
+            let mut problems = Problems::default();
+            if let Err(error) = self.check(check, &mut problems) {
+                problems.push(error.to_string());
+            }
+            report.checks.push(check);
+            report.violations.extend(
+                problems
+                    .0
+                    .into_iter()
+                    .map(|message| Violation { check, message }),
+            );
+            if check == Check::Instantiate && !report.is_ok() {
+                break;
+            }
+        }
+        report
+    }
+
+    fn check(&self, check: Check, problems: &mut Problems) -> Result<(), InstanceError> {
+        let block = self.block_size;
+        let mut session = self.session(block)?;
+        match check {
+            Check::Instantiate => {}
+            Check::RunZero => {
+                session.instance.activate()?;
+                session.run(0, problems)?;
+                session.run(block, problems)?;
+                session.run(0, problems)?;
+            }
+            Check::BlockSizes => {
+                session.instance.activate()?;
+                for n in [block, 1, block / 2 + 1, block.min(7), block, block - 1] {
+                    session.run(n, problems)?;
+                }
+                for n in [block * 2, (block / 4).max(1), block] {
+                    unsafe { session.buffers.set_block_size(&mut session.instance, n)? };
+                    session.run(n, problems)?;
+                }
+            }
+            Check::ActivationCycles => {
+                for _ in 0..3 {
+                    session.instance.activate()?;
+                    session.run(block, problems)?;
+                    session.run(block, problems)?;
+                    session.instance.deactivate()?;
+                }
+            }
+            Check::UnconnectedOptional => {
+                for (index, port) in self.ports.iter().enumerate() {
+                    if port.is_optional() {
+                        unsafe {
+                            session
+                                .instance
+                                .connect_port(index as u32, ptr::null_mut())?
+                        };
+                    }
+                }
+                session.instance.activate()?;
+                session.run(block, problems)?;
+                session.run(block, problems)?;
+            }
+            Check::InPlace => {
+                let pairs = self.in_place_pairs();
+                let mut shared = self.session(block)?;
+                for &(input, output) in &pairs {
+                    unsafe { shared.buffers.share(&mut shared.instance, input, output) }.map_err(
+                        |_| InstanceError::NoSuchPort {
+                            index: output,
+                            count: self.ports.len() as u32,
+                        },
+                    )?;
+                }
+                session.instance.activate()?;
+                shared.instance.activate()?;
+                for n in [block, block, block / 2 + 1] {
+                    session.run(n, problems)?;
+                    shared.run(n, problems)?;
+                    for &(input, output) in &pairs {
+                        let expected = &session.buffers.samples(output).unwrap()[..n as usize];
+                        let actual = &shared.buffers.samples(output).unwrap()[..n as usize];
+                        if let Some(frame) = (0..expected.len())
+                            .find(|&i| expected[i].to_bits() != actual[i].to_bits())
+                        {
+                            problems.push(format!(
+                                "port {output} sharing the buffer of port {input} differs at \
+                                 frame {frame}: {}, expected {}",
+                                actual[frame], expected[frame]
+                            ));
+                        }
+                    }
+                }
+            }
+            Check::ExtensionData => {
+                let data = session
+                    .instance
+                    .extension_data(c"urn:lv2_raw:conformance#unknownExtension");
+                if !data.is_null() {
+                    problems.push("extension_data() returned non-NULL for an unknown URI".into());
+                }
+            }
+            Check::EmptyAtoms => {
+                session.instance.activate()?;
+                session.run(block, problems)?;
+                session.run(0, problems)?;
+                session.run(block, problems)?;
+            }
+            Check::LargeAtoms => {
+                session.fill_atoms = true;
+                session.instance.activate()?;
+                session.run(block, problems)?;
+                session.run(1, problems)?;
+                session.run(block, problems)?;
+            }
+            Check::RealTime => {
+                if !is_installed() {
+                    problems.push(
+                        "RtCheckAllocator is not the global allocator, so allocations can not \
+                         be seen"
+                            .into(),
+                    );
+                }
+                session.instance.activate()?;
+                session.real_time = true;
+                for n in [block, 1, 0, block, block] {
+                    session.fill_atoms = n == 1;
+                    session.run(n, problems)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pair audio and CV inputs with the outputs of the same type, in order.
+    fn in_place_pairs(&self) -> Vec<(u32, u32)> {
+        let mut pairs = Vec::new();
+        for port_type in [PortType::Audio, PortType::Cv] {
+            let of = |input: bool| {
+                self.ports
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, p)| p.port_type == port_type && p.is_input() == input)
+                    .map(|(i, _)| i as u32)
+            };
+            pairs.extend(of(true).zip(of(false)));
+        }
+        pairs
+    }
+
+    fn session(&self, block_size: u32) -> Result<Session<'a>, InstanceError> {
+        let owned_map = self.mapper.map_feature();
+        let map = self.features.get::<LV2UridMap>().unwrap_or(&owned_map);
+        let mut buffers = PortBuffers::new(&self.ports, block_size, self.atom_capacity, map);
+        let mut instance = Instance::from_descriptor(
+            self.descriptor,
+            self.sample_rate,
+            &self.bundle_path,
+            self.features,
+            self.ports.clone(),
+        )?;
+        unsafe { buffers.connect(&mut instance)? };
+        for &(index, value) in &self.controls {
+            buffers.set_control(index, value);
+        }
+        Ok(Session {
+            sequence_urid: map.map(LV2_ATOM__SEQUENCE),
+            midi_urid: map.map(LV2_MIDI__MIDIEVENT),
+            instance,
+            buffers,
+            fill_atoms: false,
+            real_time: false,
+            frame: 0,
+        })
+    }
+}
+
+impl fmt::Debug for Conformance<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conformance")
+            .field("uri", &unsafe { CStr::from_ptr(self.descriptor.uri) })
+            .field("block_size", &self.block_size)
+            .field("atom_capacity", &self.atom_capacity)
+            .finish()
+    }
+}
+
+/// Problem messages, without repetitions.
+#[derive(Default)]
+struct Problems(Vec<String>);
+
+impl Problems {
+    fn push(&mut self, message: String) {
+        if !self.0.contains(&message) {
+            self.0.push(message);
+        }
+    }
+}
+
+struct Session<'a> {
+    // Dropped before the buffers it is connected to.
+    instance: Instance<'a>,
+    buffers: PortBuffers,
+    sequence_urid: LV2Urid,
+    midi_urid: LV2Urid,
+    fill_atoms: bool,
+    /// Whether to catch what `run()` does that is not real-time safe.
+    real_time: bool,
+    /// Frames run so far, which drives the input signal.
+    frame: u64,
+}
+
+impl Session<'_> {
+    fn run(&mut self, n: u32, problems: &mut Problems) -> Result<(), InstanceError> {
+        self.prepare(n);
+        if self.real_time {
+            let report = self.instance.run_checked(n)?;
+            for violation in &report.violations {
+                // Repeated violations have the same backtrace.
+                problems.push(format!("run() {violation}"));
+            }
+        } else {
+            self.instance.run(n)?;
+        }
+        self.frame += u64::from(n);
+        for index in 0..self.buffers.ports().len() as u32 {
+            let port = &self.buffers.ports()[index as usize];
+            if port.is_input() || !self.instance.is_connected(index) {
+                continue;
+            }
+            if let Err(message) = self.check_output(index, n) {
+                problems.push(format!("port {index}: {message}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn prepare(&mut self, n: u32) {
+        self.buffers.prepare_atoms();
+        for index in 0..self.buffers.ports().len() as u32 {
+            let port = self.buffers.ports()[index as usize].clone();
+            match port.port_type {
+                PortType::Audio | PortType::Cv if port.is_input() => {
+                    let frame = self.frame;
+                    let samples = self.buffers.samples_mut(index).unwrap();
+                    for (i, sample) in samples.iter_mut().enumerate() {
+                        *sample = signal(frame + i as u64);
+                    }
+                }
+                PortType::Audio | PortType::Cv if !self.buffers.is_shared(index) => {
+                    self.buffers.samples_mut(index).unwrap().fill(SENTINEL);
+                }
+                PortType::Atom if port.is_input() && self.fill_atoms && n > 0 => {
+                    // As many events as fit, spread evenly over the block.
+                    let count = self.buffers.midi_capacity();
+                    let events = (0..count).map(|i| {
+                        let frame = u64::from(i) * u64::from(n) / u64::from(count);
+                        (frame as u32, [0x90, (i % 128) as u8, 100])
+                    });
+                    self.buffers.append_midi(index, self.midi_urid, events);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_output(&self, index: u32, n: u32) -> Result<(), String> {
+        let n = n as usize;
+        match self.buffers.ports()[index as usize].port_type {
+            PortType::Audio | PortType::Cv => {
+                let samples = self.buffers.samples(index).unwrap();
+                let shared = self.buffers.is_shared(index);
+                for (frame, &value) in samples.iter().enumerate() {
+                    if frame >= n {
+                        if !shared && value.to_bits() != SENTINEL.to_bits() {
+                            return Err(format!("wrote frame {frame} of a {n} frame block"));
+                        }
+                    } else if !shared && value.to_bits() == SENTINEL.to_bits() {
+                        return Err(format!("did not write frame {frame}"));
+                    } else {
+                        check_value(value).map_err(|e| format!("{e} at frame {frame}"))?;
+                    }
+                }
+                Ok(())
+            }
+            PortType::Control => check_value(self.buffers.control(index).unwrap()),
+            PortType::Atom => self.check_sequence(index, n as u32),
+        }
+    }
+
+    fn check_sequence(&self, index: u32, n: u32) -> Result<(), String> {
+        let bytes = self.buffers.atom_bytes(index).unwrap();
+        let capacity = self.buffers.atom_capacity() as usize;
+        let sequence = unsafe { &*(bytes.as_ptr() as *const LV2AtomSequence) };
+        if sequence.atom.type_ != self.sequence_urid {
+            return Err("atom output is not an atom:Sequence".into());
+        }
+        let end = size_of::<LV2Atom>() + sequence.atom.size as usize;
+        if end > capacity {
+            return Err(format!(
+                "atom output of {end} bytes overflows its {capacity} byte buffer"
+            ));
+        }
+
+        let header = size_of::<LV2AtomEvent>();
+        let mut offset = size_of::<LV2AtomSequence>();
+        let mut last = 0;
+        while offset < end {
+            if offset + header > end {
+                return Err(format!("truncated event header at byte {offset}"));
+            }
+            let event = unsafe { &*(bytes.as_ptr().add(offset) as *const LV2AtomEvent) };
+            let body = event.body.size as usize;
+            if offset + header + body > end {
+                return Err(format!(
+                    "event of {body} bytes at byte {offset} overruns the sequence"
+                ));
+            }
+            let time = event.time_in_frames;
+            if time < last || time >= i64::from(n).max(1) {
+                return Err(format!(
+                    "event at frame {time} is out of order or outside the {n} frame block"
+                ));
+            }
+            last = time;
+            offset += (header + body).next_multiple_of(8);
+        }
+        Ok(())
+    }
+}
+
+/// A sine-like test signal in [-0.5, 0.5].
+fn signal(frame: u64) -> f32 {
+    ((frame % 1000) as f32 * 0.0503).sin() * 0.5
+}
+
+fn check_value(value: f32) -> Result<(), String> {
+    if !value.is_finite() {
+        Err(format!("{value} output"))
+    } else if value.is_subnormal() {
+        Err(format!("denormal output {value:e}"))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Enumerating the plugins in a library.
+
+use crate::core::{
+    LV2Descriptor, LV2DescriptorFunction, LV2Feature, LV2LibDescriptor, LV2LibDescriptorFunction,
+};
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr::NonNull;
+
+enum Source {
+    Lib(NonNull<LV2LibDescriptor>),
+    Plain(LV2DescriptorFunction),
+}
+
+/// The plugin descriptors of one library.
+///
+/// A library may export `lv2_lib_descriptor()`, `lv2_descriptor()`, or both.
+/// The library descriptor is preferred: it lets the library set up state
+/// shared by all of its plugins, and refuse to load if the host lacks a
+/// feature it needs.  `lv2_descriptor()` is only used if the library has no
+/// `lv2_lib_descriptor()`; if that returns NULL, the library fails to load.
+///
+/// When a library descriptor is used, it is cleaned up when this is dropped,
+/// so it must outlive every instance created from its descriptors.
+pub struct PluginDescriptors {
+    source: Source,
+}
+
+impl PluginDescriptors {
+    /// Get the descriptors of a library from its entry points.
+    ///
+    /// Returns `None` if there is no entry point, or if
+    /// `lv2_lib_descriptor()` returned NULL.
+    ///
+    /// # Safety
+    ///
+    /// The entry points must be valid, and must stay valid (i.e. the library
+    /// must stay loaded) until this is dropped.  `features` must be a valid,
+    /// NULL terminated features array, which outlives this.
+    pub unsafe fn new(
+        lib_descriptor: Option<LV2LibDescriptorFunction>,
+        descriptor: Option<LV2DescriptorFunction>,
+        bundle_path: &CStr,
+        features: *const *const LV2Feature,
+    ) -> Option<Self> {
+        if let Some(f) = lib_descriptor {
+            let lib = NonNull::new(f(bundle_path.as_ptr(), features) as *mut _)?;
+            return Some(PluginDescriptors {
+                source: Source::Lib(lib),
+            });
+        }
+
+        descriptor.map(|f| PluginDescriptors {
+            source: Source::Plain(f),
+        })
+    }
+
+    /// Return true iff the descriptors come from `lv2_lib_descriptor()`.
+    pub fn is_lib_descriptor(&self) -> bool {
+        matches!(self.source, Source::Lib(_))
+    }
+
+    /// Return the descriptor at `index`, or `None` past the last plugin.
+    pub fn get(&self, index: u32) -> Option<&LV2Descriptor> {
+        let descriptor = match self.source {
+            Source::Lib(lib) => unsafe {
+                let lib = lib.as_ref();
+                (lib.get_plugin)(lib.handle, index)
+            },
+            Source::Plain(f) => f(index),
+        };
+        unsafe { descriptor.as_ref() }
+    }
+
+    /// Iterate over all descriptors, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = &LV2Descriptor> {
+        (0..).map_while(move |i| self.get(i))
+    }
+
+    /// Return the descriptor of the plugin with the given URI.
+    pub fn find(&self, uri: impl AsRef<CStr>) -> Option<&LV2Descriptor> {
+        let uri = uri.as_ref();
+        self.iter()
+            .find(|d| !d.uri.is_null() && unsafe { CStr::from_ptr(d.uri) } == uri)
+    }
+}
+
+impl Drop for PluginDescriptors {
+    fn drop(&mut self) {
+        if let Source::Lib(lib) = self.source {
+            unsafe {
+                let lib = lib.as_ref();
+                (lib.cleanup)(lib.handle);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PluginDescriptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Skip descriptors without a URI, as `find()` does.
+        f.debug_list()
+            .entries(
+                self.iter()
+                    .filter(|d| !d.uri.is_null())
+                    .map(|d| unsafe { CStr::from_ptr(d.uri) }),
+            )
+            .finish()
+    }
+}
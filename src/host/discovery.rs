@@ -0,0 +1,188 @@
+//! Finding installed plugins.
+
+use crate::bundle::{Bundle, BundleError, PluginDescription};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Return the default LV2 search path for this platform.
+///
+/// This is `~/Library/Audio/Plug-Ins/LV2` and `/Library/Audio/Plug-Ins/LV2`
+/// on macOS, `%APPDATA%\LV2` and `%COMMONPROGRAMFILES%\LV2` on Windows, and
+/// `~/.lv2`, `/usr/local/lib/lv2` and `/usr/lib/lv2` elsewhere.
+pub fn default_lv2_path() -> Vec<PathBuf> {
+    let mut path = Vec::new();
+    if cfg!(target_os = "macos") {
+        if let Some(home) = std::env::var_os("HOME") {
+            path.push(Path::new(&home).join("Library/Audio/Plug-Ins/LV2"));
+        }
+        path.push(PathBuf::from("/Library/Audio/Plug-Ins/LV2"));
+    } else if cfg!(windows) {
+        for var in ["APPDATA", "COMMONPROGRAMFILES"] {
+            if let Some(dir) = std::env::var_os(var) {
+                path.push(Path::new(&dir).join("LV2"));
+            }
+        }
+    } else {
+        if let Some(home) = std::env::var_os("HOME") {
+            path.push(Path::new(&home).join(".lv2"));
+        }
+        path.push(PathBuf::from("/usr/local/lib/lv2"));
+        path.push(PathBuf::from("/usr/lib/lv2"));
+    }
+    path
+}
+
+/// Return the directories to search for bundles: the entries of `LV2_PATH`
+/// if it is set, [`default_lv2_path`] otherwise.
+pub fn lv2_path() -> Vec<PathBuf> {
+    lv2_path_from(std::env::var_os("LV2_PATH"))
+}
+
+fn lv2_path_from(value: Option<OsString>) -> Vec<PathBuf> {
+    match value {
+        Some(value) if !value.is_empty() => std::env::split_paths(&value)
+            .filter(|p| !p.as_os_str().is_empty())
+            .collect(),
+        _ => default_lv2_path(),
+    }
+}
+
+/// A plugin found by discovery.
+#[derive(Debug, Clone)]
+pub struct DiscoveredPlugin {
+    /// The bundle directory.
+    pub bundle_path: PathBuf,
+    /// The plugin data.
+    pub description: PluginDescription,
+}
+
+impl DiscoveredPlugin {
+    /// Return the plugin URI.
+    pub fn uri(&self) -> &str {
+        &self.description.uri
+    }
+
+    /// Return the path of the shared library, if the plugin has one.
+    pub fn binary(&self) -> Option<&Path> {
+        self.description.binary.as_deref()
+    }
+}
+
+/// An index of the plugins in a set of directories, by URI.
+///
+/// Bundles which fail to load do not stop discovery; they are recorded in
+/// [`errors`](PluginIndex::errors) instead.  If several bundles contain a
+/// plugin with the same URI, the first one found wins, so earlier entries of
+/// the search path take precedence.
+#[derive(Default)]
+pub struct PluginIndex {
+    plugins: BTreeMap<String, DiscoveredPlugin>,
+    errors: Vec<(PathBuf, BundleError)>,
+}
+
+impl PluginIndex {
+    /// Index the plugins on the [`lv2_path`].
+    pub fn discover() -> Self {
+        Self::scan(lv2_path())
+    }
+
+    /// Index the plugins in the bundles (`*.lv2` directories with a
+    /// `manifest.ttl`) directly inside `dirs`.  Directories which do not
+    /// exist are skipped.
+    pub fn scan<P: AsRef<Path>>(dirs: impl IntoIterator<Item = P>) -> Self {
+        let mut index = PluginIndex::default();
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(dir.as_ref()) else {
+                continue;
+            };
+            let mut bundles: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.extension().is_some_and(|e| e == "lv2")
+                        && path.join("manifest.ttl").is_file()
+                })
+                .collect();
+            bundles.sort();
+            for bundle in bundles {
+                index.add_bundle(&bundle);
+            }
+        }
+        index
+    }
+
+    /// Load one bundle into the index.
+    ///
+    /// Returns false if it could not be loaded, in which case the error is
+    /// recorded.
+    pub fn add_bundle(&mut self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        match Bundle::load(path) {
+            Ok(bundle) => {
+                for description in bundle.plugins {
+                    self.plugins
+                        .entry(description.uri.clone())
+                        .or_insert_with(|| DiscoveredPlugin {
+                            bundle_path: bundle.path.clone(),
+                            description,
+                        });
+                }
+                true
+            }
+            Err(error) => {
+                self.errors.push((path.to_owned(), error));
+                false
+            }
+        }
+    }
+
+    /// Return the plugin with the given URI.
+    pub fn get(&self, uri: impl AsRef<str>) -> Option<&DiscoveredPlugin> {
+        self.plugins.get(uri.as_ref())
+    }
+
+    /// Iterate over all plugins, ordered by URI.
+    pub fn plugins(&self) -> impl Iterator<Item = &DiscoveredPlugin> {
+        self.plugins.values()
+    }
+
+    /// Return the bundles which failed to load, with their errors.
+    pub fn errors(&self) -> &[(PathBuf, BundleError)] {
+        &self.errors
+    }
+
+    /// Return the number of plugins.
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Return true iff no plugins were found.
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+}
+
+impl fmt::Debug for PluginIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginIndex")
+            .field("plugins", &self.plugins.keys())
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lv2_path_from_value() {
+        let a = std::env::temp_dir().join("a.lv2");
+        let b = std::env::temp_dir().join("b.lv2");
+        let value = std::env::join_paths([&a, Path::new(""), &b]).unwrap();
+        assert_eq!(lv2_path_from(Some(value)), [a, b]);
+        assert_eq!(lv2_path_from(Some(OsString::new())), default_lv2_path());
+        assert_eq!(lv2_path_from(None), default_lv2_path());
+    }
+}
@@ -0,0 +1,326 @@
+//! Building the features array passed to `instantiate()`.
+
+use crate::atom::{
+    LV2_ATOM__BOOL, LV2_ATOM__DOUBLE, LV2_ATOM__FLOAT, LV2_ATOM__INT, LV2_ATOM__LONG,
+    LV2_ATOM__URID,
+};
+use crate::core::LV2Feature;
+use crate::coreutils::Features;
+use crate::host::urid::UridMapper;
+use crate::log::{LV2_LOG__LOG, LV2LogLog};
+use crate::options::{LV2_OPTIONS__OPTIONS, LV2_OPTIONS_INSTANCE, LV2OptionsOption};
+use crate::state::{
+    LV2_STATE__FREEPATH, LV2_STATE__MAKEPATH, LV2_STATE__MAPPATH, LV2StateFreePath,
+    LV2StateMakePath, LV2StateMapPath,
+};
+use crate::urid::{LV2_URID__MAP, LV2_URID__UNMAP, LV2Urid};
+use crate::worker::{LV2_WORKER__SCHEDULE, LV2WorkerSchedule};
+use std::any::Any;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// The value of an option passed through the options feature.
+///
+/// Each variant is stored with the matching atom type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionValue {
+    /// An `atom:Int`.
+    Int(i32),
+    /// An `atom:Long`.
+    Long(i64),
+    /// An `atom:Float`.
+    Float(f32),
+    /// An `atom:Double`.
+    Double(f64),
+    /// An `atom:Bool`.
+    Bool(bool),
+    /// An `atom:URID`.
+    Urid(LV2Urid),
+}
+
+impl OptionValue {
+    fn to_storage(self, mapper: &UridMapper) -> (LV2Urid, u32, Box<u64>) {
+        // Every value fits in, and is aligned for, a u64.
+        let (type_, size, bits): (_, usize, u64) = match self {
+            OptionValue::Int(v) => (LV2_ATOM__INT, size_of::<i32>(), v as u32 as u64),
+            OptionValue::Long(v) => (LV2_ATOM__LONG, size_of::<i64>(), v as u64),
+            OptionValue::Float(v) => (LV2_ATOM__FLOAT, size_of::<f32>(), v.to_bits() as u64),
+            OptionValue::Double(v) => (LV2_ATOM__DOUBLE, size_of::<f64>(), v.to_bits()),
+            OptionValue::Bool(v) => (LV2_ATOM__BOOL, size_of::<i32>(), v as u64),
+            OptionValue::Urid(v) => (LV2_ATOM__URID, size_of::<u32>(), v as u64),
+        };
+        let storage = if size == size_of::<u64>() {
+            Box::new(bits)
+        } else {
+            // Put the 32-bit value in the first four bytes, whatever the
+            // endianness.
+            let mut storage = Box::new(0u64);
+            unsafe { ptr::write(&mut *storage as *mut u64 as *mut u32, bits as u32) };
+            storage
+        };
+        (mapper.map(type_), size as u32, storage)
+    }
+}
+
+struct Entry {
+    uri: CString,
+    // Boxed so the pointer in the features array stays valid.
+    feature: Box<LV2Feature>,
+    // Keeps the feature data alive.
+    _payload: Option<Box<dyn Any>>,
+}
+
+/// An owned, NULL terminated features array.
+///
+/// The list owns the feature URIs, the feature data and the pointer array,
+/// so [`as_ptr`](FeatureList::as_ptr) can be passed straight to
+/// `instantiate()`.  The pointer is valid until the list is modified or
+/// dropped.  An empty list is a single NULL element, as required by
+/// `LV2Descriptor::instantiate`.
+///
+/// Features backed by borrowed host objects, like the URID map, borrow them
+/// for `'a`.  Plugins may keep feature pointers for the lifetime of an
+/// instance, so the list must outlive every instance it was passed to.
+///
+/// ```
+/// use lv2_raw::host::{FeatureList, OptionValue, UridMapper};
+/// use lv2_raw::*;
+///
+/// let mapper = UridMapper::new();
+/// let mut features = FeatureList::new();
+/// features
+///     .urid_map(&mapper)
+///     .urid_unmap(&mapper)
+///     .option(&mapper, c"urn:example:blockLength", OptionValue::Int(512))
+///     .flag(LV2_CORE__ISLIVE);
+/// assert_eq!(features.len(), 4);
+/// ```
+pub struct FeatureList<'a> {
+    entries: Vec<Entry>,
+    options: Vec<LV2OptionsOption>,
+    // Boxed so the values don't move when more options are added.
+    #[allow(clippy::vec_box)]
+    option_values: Vec<Box<u64>>,
+    pointers: Vec<*const LV2Feature>,
+    _marker: PhantomData<&'a UridMapper>,
+}
+
+impl<'a> FeatureList<'a> {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        FeatureList {
+            entries: Vec::new(),
+            options: Vec::new(),
+            option_values: Vec::new(),
+            pointers: vec![ptr::null()],
+            _marker: PhantomData,
+        }
+    }
+
+    /// Add the `urid#map` feature, backed by `mapper`.
+    pub fn urid_map(&mut self, mapper: &'a UridMapper) -> &mut Self {
+        self.boxed(LV2_URID__MAP, mapper.map_feature())
+    }
+
+    /// Add the `urid#unmap` feature, backed by `mapper`.
+    pub fn urid_unmap(&mut self, mapper: &'a UridMapper) -> &mut Self {
+        self.boxed(LV2_URID__UNMAP, mapper.unmap_feature())
+    }
+
+    /// Add an instance option to the `options#options` feature.
+    ///
+    /// The key and the value type are mapped with `mapper`.  All options
+    /// share a single feature, which is added along with the first one.
+    pub fn option(
+        &mut self,
+        mapper: &UridMapper,
+        key: impl AsRef<CStr>,
+        value: OptionValue,
+    ) -> &mut Self {
+        let (type_, size, storage) = value.to_storage(mapper);
+        let option = LV2OptionsOption {
+            context: LV2_OPTIONS_INSTANCE,
+            subject: 0,
+            key: mapper.map(key),
+            size,
+            type_,
+            value: &*storage as *const u64 as *const c_void,
+        };
+        self.option_values.push(storage);
+        unsafe { self.raw_option(option) }
+    }
+
+    /// Add an option with caller-managed value to the `options#options`
+    /// feature.
+    ///
+    /// # Safety
+    ///
+    /// `option.value` must point to `option.size` bytes of the given type,
+    /// which must stay valid for as long as the features are used.
+    pub unsafe fn raw_option(&mut self, option: LV2OptionsOption) -> &mut Self {
+        match self.options.last_mut() {
+            Some(end) => {
+                *end = option;
+                self.options.push(LV2OptionsOption::END);
+            }
+            None => self.options = vec![option, LV2OptionsOption::END],
+        }
+        // The array may have moved, so (re)point the feature at it.
+        let data = self.options.as_mut_ptr() as *mut c_void;
+        match self.find(LV2_OPTIONS__OPTIONS) {
+            Some(i) => self.entries[i].feature.data = data,
+            None => self.push(LV2_OPTIONS__OPTIONS, data, None),
+        }
+        self
+    }
+
+    /// Add the `log#log` feature.
+    pub fn log(&mut self, log: LV2LogLog) -> &mut Self {
+        self.boxed(LV2_LOG__LOG, log)
+    }
+
+    /// Add the `worker#schedule` feature.
+    pub fn worker_schedule(&mut self, schedule: LV2WorkerSchedule) -> &mut Self {
+        self.boxed(LV2_WORKER__SCHEDULE, schedule)
+    }
+
+    /// Add the `state#makePath` feature.
+    pub fn state_make_path(&mut self, make_path: LV2StateMakePath) -> &mut Self {
+        self.boxed(LV2_STATE__MAKEPATH, make_path)
+    }
+
+    /// Add the `state#mapPath` feature.
+    pub fn state_map_path(&mut self, map_path: LV2StateMapPath) -> &mut Self {
+        self.boxed(LV2_STATE__MAPPATH, map_path)
+    }
+
+    /// Add the `state#freePath` feature.
+    pub fn state_free_path(&mut self, free_path: LV2StateFreePath) -> &mut Self {
+        self.boxed(LV2_STATE__FREEPATH, free_path)
+    }
+
+    /// Add a feature without data, such as `lv2:isLive`.
+    pub fn flag(&mut self, uri: impl AsRef<CStr>) -> &mut Self {
+        self.push(uri.as_ref(), ptr::null_mut(), None);
+        self
+    }
+
+    /// Add a feature with owned data of any type.
+    ///
+    /// The data is boxed, and the feature points to the box.
+    pub fn data<T: 'static>(&mut self, uri: impl AsRef<CStr>, data: T) -> &mut Self {
+        self.boxed(uri.as_ref(), data)
+    }
+
+    /// Add a feature with caller-managed data.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be NULL or point to the data the feature's specification
+    /// requires, which must stay valid for as long as the features are used.
+    pub unsafe fn raw(&mut self, uri: impl AsRef<CStr>, data: *mut c_void) -> &mut Self {
+        self.push(uri.as_ref(), data, None);
+        self
+    }
+
+    /// Return the NULL terminated features array.
+    ///
+    /// The array is valid until the list is modified or dropped.
+    pub fn as_ptr(&self) -> *const *const LV2Feature {
+        self.pointers.as_ptr()
+    }
+
+    /// Return a view of the features array.
+    pub fn features(&self) -> Features<'_> {
+        unsafe { Features::from_raw(self.as_ptr()) }
+    }
+
+    /// Return the number of features (not counting the terminating NULL).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true iff the list has no features.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return true iff a feature with the given URI is in the list.
+    pub fn contains(&self, uri: impl AsRef<CStr>) -> bool {
+        self.find(uri.as_ref()).is_some()
+    }
+
+    /// Remove the feature with the given URI, returning true if it was present.
+    pub fn remove(&mut self, uri: impl AsRef<CStr>) -> bool {
+        let uri = uri.as_ref();
+        let Some(i) = self.find(uri) else {
+            return false;
+        };
+        self.entries.remove(i);
+        if uri == LV2_OPTIONS__OPTIONS.as_cstr() {
+            self.options.clear();
+            self.option_values.clear();
+        }
+        self.update_pointers();
+        true
+    }
+
+    fn boxed<T: 'static>(&mut self, uri: impl AsRef<CStr>, data: T) -> &mut Self {
+        let mut payload = Box::new(data);
+        let data = &mut *payload as *mut T as *mut c_void;
+        self.push(uri.as_ref(), data, Some(payload));
+        self
+    }
+
+    fn find(&self, uri: impl AsRef<CStr>) -> Option<usize> {
+        let uri = uri.as_ref();
+        self.entries.iter().position(|e| e.uri.as_c_str() == uri)
+    }
+
+    /// Add a feature, replacing any previous one with the same URI.
+    fn push(&mut self, uri: impl AsRef<CStr>, data: *mut c_void, payload: Option<Box<dyn Any>>) {
+        let uri = CString::from(uri.as_ref());
+        let feature = Box::new(LV2Feature {
+            uri: uri.as_ptr(),
+            data,
+        });
+        let entry = Entry {
+            uri,
+            feature,
+            _payload: payload,
+        };
+        match self.find(&entry.uri) {
+            Some(i) => self.entries[i] = entry,
+            None => self.entries.push(entry),
+        }
+        self.update_pointers();
+    }
+
+    fn update_pointers(&mut self) {
+        self.pointers.clear();
+        self.pointers.extend(
+            self.entries
+                .iter()
+                .map(|e| &*e.feature as *const LV2Feature),
+        );
+        self.pointers.push(ptr::null());
+    }
+}
+
+impl Default for FeatureList<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for FeatureList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|e| &e.uri))
+            .finish()
+    }
+}
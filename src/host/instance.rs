@@ -0,0 +1,286 @@
+//! Running a plugin instance, following the rules of the LV2 lifecycle.
+
+#[cfg(unix)]
+use super::library::{Library, LibraryPlugin};
+use super::port::PortInfo;
+use super::rtcheck::{RtReport, rt_check};
+use crate::core::{LV2Descriptor, LV2Handle};
+use crate::coreutils::Features;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Error returned when an instance function is called out of order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstanceError {
+    /// `instantiate()` returned NULL for the plugin with this URI.
+    InstantiationFailed(CString),
+    /// The port index is out of range.
+    NoSuchPort { index: u32, count: u32 },
+    /// `activate()` was called on an active instance.
+    AlreadyActive,
+    /// `run()` or `deactivate()` was called on an inactive instance.
+    NotActive,
+    /// `run()` was called while these required ports are unconnected.
+    UnconnectedPorts(Vec<u32>),
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceError::InstantiationFailed(uri) => {
+                write!(f, "failed to instantiate <{}>", uri.to_string_lossy())
+            }
+            InstanceError::NoSuchPort { index, count } => {
+                write!(f, "port {index} out of range, the plugin has {count} ports")
+            }
+            InstanceError::AlreadyActive => f.write_str("instance is already active"),
+            InstanceError::NotActive => f.write_str("instance is not active"),
+            InstanceError::UnconnectedPorts(ports) => {
+                f.write_str("required ports are not connected:")?;
+                for port in ports {
+                    write!(f, " {port}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for InstanceError {}
+
+/// Where an instance is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceState {
+    /// Instantiated, or deactivated.  Only ports may be connected.
+    Inactive,
+    /// Activated, so `run()` may be called.
+    Active,
+}
+
+/// A plugin instance which enforces the order of calls documented on
+/// [`LV2Descriptor`].
+///
+/// - `activate()` may not be called twice without `deactivate()` in
+///   between, and `deactivate()` only on an active instance.
+/// - `run()` needs an active instance, unless the plugin has no
+///   `activate()`, and every port that is not `lv2:connectionOptional` must
+///   be connected.
+/// - When dropped, the instance is deactivated if needed, then cleaned up.
+///
+/// Violations are reported as [`InstanceError`]s, and the plugin is not
+/// called.  The features passed to the instance are borrowed for `'a`,
+/// since the plugin may use them until it is cleaned up.
+pub struct Instance<'a> {
+    descriptor: &'a LV2Descriptor,
+    handle: LV2Handle,
+    ports: Vec<PortInfo>,
+    connected: Vec<bool>,
+    state: InstanceState,
+    _features: Features<'a>,
+    #[cfg(unix)]
+    library: Option<Library>,
+}
+
+impl<'a> Instance<'a> {
+    /// Instantiate the plugin described by `descriptor`.
+    ///
+    /// `ports` must describe the ports of the plugin, in index order.
+    pub fn from_descriptor(
+        descriptor: &'a LV2Descriptor,
+        sample_rate: f64,
+        bundle_path: &CStr,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Result<Self, InstanceError> {
+        let handle = (descriptor.instantiate)(
+            descriptor,
+            sample_rate,
+            bundle_path.as_ptr(),
+            features.as_ptr(),
+        );
+        if handle.is_null() {
+            let uri = unsafe { CStr::from_ptr(descriptor.uri) };
+            return Err(InstanceError::InstantiationFailed(uri.to_owned()));
+        }
+
+        Ok(Instance {
+            descriptor,
+            handle,
+            connected: vec![false; ports.len()],
+            ports,
+            state: InstanceState::Inactive,
+            _features: features,
+            #[cfg(unix)]
+            library: None,
+        })
+    }
+
+    /// Instantiate a plugin from a loaded library, which stays loaded until
+    /// the instance is dropped.
+    #[cfg(unix)]
+    pub fn new(
+        plugin: &LibraryPlugin,
+        sample_rate: f64,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Result<Self, InstanceError> {
+        let library = plugin.library().clone();
+        // The descriptor lives as long as the library, which the instance owns.
+        let descriptor = unsafe { &*(plugin.descriptor() as *const LV2Descriptor) };
+        let mut instance = Self::from_descriptor(
+            descriptor,
+            sample_rate,
+            library.bundle_path(),
+            features,
+            ports,
+        )?;
+        instance.library = Some(library);
+        Ok(instance)
+    }
+
+    /// Return the plugin descriptor.
+    pub fn descriptor(&self) -> &LV2Descriptor {
+        self.descriptor
+    }
+
+    /// Return the URI of the plugin.
+    pub fn uri(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.descriptor.uri) }
+    }
+
+    /// Return the raw instance handle.
+    pub fn handle(&self) -> LV2Handle {
+        self.handle
+    }
+
+    /// Return the ports of the plugin.
+    pub fn ports(&self) -> &[PortInfo] {
+        &self.ports
+    }
+
+    /// Return the current lifecycle state.
+    pub fn state(&self) -> InstanceState {
+        self.state
+    }
+
+    /// Return true iff the instance is active.
+    pub fn is_active(&self) -> bool {
+        self.state == InstanceState::Active
+    }
+
+    /// Return true iff port `index` is connected to a buffer.
+    pub fn is_connected(&self, index: u32) -> bool {
+        self.connected.get(index as usize).copied().unwrap_or(false)
+    }
+
+    /// Connect port `index` to `data`, or disconnect it if `data` is NULL.
+    ///
+    /// # Safety
+    ///
+    /// `data` must point to a buffer of the right type and size for the port,
+    /// which stays valid until the port is connected elsewhere or the
+    /// instance is dropped.
+    pub unsafe fn connect_port(
+        &mut self,
+        index: u32,
+        data: *mut c_void,
+    ) -> Result<(), InstanceError> {
+        let connected =
+            self.connected
+                .get_mut(index as usize)
+                .ok_or(InstanceError::NoSuchPort {
+                    index,
+                    count: self.ports.len() as u32,
+                })?;
+        *connected = !data.is_null();
+        (self.descriptor.connect_port)(self.handle, index, data);
+        Ok(())
+    }
+
+    /// Activate the instance.
+    pub fn activate(&mut self) -> Result<(), InstanceError> {
+        if self.is_active() {
+            return Err(InstanceError::AlreadyActive);
+        }
+        if let Some(activate) = self.descriptor.activate {
+            activate(self.handle);
+        }
+        self.state = InstanceState::Active;
+        Ok(())
+    }
+
+    /// Deactivate the instance.
+    pub fn deactivate(&mut self) -> Result<(), InstanceError> {
+        if !self.is_active() {
+            return Err(InstanceError::NotActive);
+        }
+        if let Some(deactivate) = self.descriptor.deactivate {
+            deactivate(self.handle);
+        }
+        self.state = InstanceState::Inactive;
+        Ok(())
+    }
+
+    /// Run the instance for `sample_count` frames.
+    pub fn run(&mut self, sample_count: u32) -> Result<(), InstanceError> {
+        self.check_runnable()?;
+        (self.descriptor.run)(self.handle, sample_count);
+        Ok(())
+    }
+
+    /// Run the instance for `sample_count` frames, and report what `run()`
+    /// did that is not real-time safe.  See [`rt_check`] for what can be
+    /// caught.
+    pub fn run_checked(&mut self, sample_count: u32) -> Result<RtReport, InstanceError> {
+        self.check_runnable()?;
+        let ((), report) = rt_check(|| (self.descriptor.run)(self.handle, sample_count));
+        Ok(report)
+    }
+
+    fn check_runnable(&self) -> Result<(), InstanceError> {
+        if !self.is_active() && self.descriptor.activate.is_some() {
+            return Err(InstanceError::NotActive);
+        }
+        let unconnected: Vec<u32> = self
+            .ports
+            .iter()
+            .zip(&self.connected)
+            .enumerate()
+            .filter(|(_, (port, connected))| !**connected && !port.is_optional())
+            .map(|(index, _)| index as u32)
+            .collect();
+        if !unconnected.is_empty() {
+            return Err(InstanceError::UnconnectedPorts(unconnected));
+        }
+        Ok(())
+    }
+
+    /// Return extension data of the plugin, or NULL if it has none for `uri`.
+    pub fn extension_data(&self, uri: impl AsRef<CStr>) -> *const c_void {
+        match self.descriptor.extension_data {
+            Some(extension_data) => extension_data(uri.as_ref().as_ptr()),
+            None => ptr::null(),
+        }
+    }
+}
+
+impl Drop for Instance<'_> {
+    fn drop(&mut self) {
+        if self.is_active() {
+            let _ = self.deactivate();
+        }
+        (self.descriptor.cleanup)(self.handle);
+    }
+}
+
+impl fmt::Debug for Instance<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instance")
+            .field("uri", &self.uri())
+            .field("state", &self.state)
+            .finish()
+    }
+}
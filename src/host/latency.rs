@@ -0,0 +1,40 @@
+//! Reading the latency a plugin reports.
+//!
+//! A plugin which delays its output reports by how many frames on a control
+//! output designated `lv2:latency` (older plugins use the
+//! `lv2:reportsLatency` port property instead).  The port is updated on
+//! every `run()`, and a host which needs the latency before processing any
+//! audio, e.g. to compensate it, calls `run(0)`: a block without frames in
+//! which the plugin only updates its control outputs.
+
+use super::buffers::PortBuffers;
+use super::instance::{Instance, InstanceError};
+use super::port::PortInfo;
+
+/// Return the index of the port which reports the latency, if any.
+pub fn latency_port(ports: &[PortInfo]) -> Option<u32> {
+    ports
+        .iter()
+        .position(PortInfo::reports_latency)
+        .map(|index| index as u32)
+}
+
+/// Return the latency of `instance` in frames, or `None` if the plugin does
+/// not report any.
+///
+/// The latency port is found in the ports of the instance, and read after
+/// a `run(0)` with `buffers`, which must be connected to `instance`.  Atom
+/// inputs are reset to empty sequences first.  Like any `run()`, this needs
+/// an active instance.  Negative and NaN values are read as 0.
+pub fn query_latency(
+    instance: &mut Instance<'_>,
+    buffers: &mut PortBuffers,
+) -> Result<Option<u32>, InstanceError> {
+    let Some(port) = latency_port(instance.ports()) else {
+        return Ok(None);
+    };
+    buffers.prepare_atoms();
+    instance.run(0)?;
+    let latency = buffers.control(port).unwrap_or(0.0);
+    Ok(Some(latency.round().max(0.0) as u32))
+}
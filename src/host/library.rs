@@ -0,0 +1,305 @@
+//! Loading plugin libraries at run time.
+
+use super::descriptors::PluginDescriptors;
+use crate::core::{LV2Descriptor, LV2DescriptorFunction, LV2Feature, LV2LibDescriptorFunction};
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_void;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// Error returned when a plugin library can not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibraryError {
+    /// The path contains a NUL byte.
+    InvalidPath(PathBuf),
+    /// `dlopen()` failed, with the message from `dlerror()`.
+    Open { path: PathBuf, message: String },
+    /// The library exports neither `lv2_lib_descriptor()` nor
+    /// `lv2_descriptor()`, or its library descriptor refused to load.
+    NoDescriptors(PathBuf),
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryError::InvalidPath(path) => write!(f, "invalid path {}", path.display()),
+            LibraryError::Open { path, message } => {
+                write!(f, "failed to open {}: {}", path.display(), message)
+            }
+            LibraryError::NoDescriptors(path) => {
+                write!(f, "{} has no LV2 descriptors", path.display())
+            }
+        }
+    }
+}
+
+impl Error for LibraryError {}
+
+struct Handle(NonNull<c_void>);
+
+impl Handle {
+    unsafe fn symbol(&self, name: &CStr) -> *mut c_void {
+        unsafe { libc::dlsym(self.0.as_ptr(), name.as_ptr()) }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe {
+            libc::dlclose(self.0.as_ptr());
+        }
+    }
+}
+
+struct Inner {
+    // Fields are dropped in order: the descriptors (and with them the library
+    // descriptor's cleanup()) must go before the library is closed.
+    descriptors: PluginDescriptors,
+    bundle_path: CString,
+    path: PathBuf,
+    _handle: Handle,
+}
+
+// Descriptors are immutable, and `dlclose()` may be called from any thread.
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+/// A loaded plugin library.
+///
+/// This is a cheap, reference counted handle: the library stays loaded as
+/// long as any clone of it, or any [`LibraryPlugin`] from it, is alive.
+#[derive(Clone)]
+pub struct Library {
+    inner: Arc<Inner>,
+}
+
+// An empty features array; raw pointers can not be put in a static.
+static NO_FEATURES: [usize; 1] = [0];
+
+impl Library {
+    /// Load the library at `path`, which lives in the bundle directory that
+    /// contains it.
+    ///
+    /// No features are passed to `lv2_lib_descriptor()`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Library, LibraryError> {
+        let path = path.as_ref();
+        unsafe {
+            Self::open_with_features(
+                path,
+                bundle_dir(path),
+                NO_FEATURES.as_ptr() as *const *const LV2Feature,
+            )
+        }
+    }
+
+    /// Load the library at `path`, which lives in the bundle `bundle_path`.
+    ///
+    /// # Safety
+    ///
+    /// `features` must be a valid, NULL terminated features array, which
+    /// outlives every clone of the returned library.
+    pub unsafe fn open_with_features(
+        path: impl AsRef<Path>,
+        bundle_path: impl AsRef<Path>,
+        features: *const *const LV2Feature,
+    ) -> Result<Library, LibraryError> {
+        let path = path.as_ref();
+        let c_path = dlopen_path(path).ok_or_else(|| LibraryError::InvalidPath(path.to_owned()))?;
+        let bundle_path = bundle_path.as_ref();
+        let bundle_path = c_bundle_path(bundle_path)
+            .ok_or_else(|| LibraryError::InvalidPath(bundle_path.to_owned()))?;
+
+        let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        let handle = match NonNull::new(handle) {
+            Some(handle) => Handle(handle),
+            None => {
+                return Err(LibraryError::Open {
+                    path: path.to_owned(),
+                    message: dlerror(),
+                });
+            }
+        };
+
+        let descriptors = unsafe {
+            let lib = handle.symbol(c"lv2_lib_descriptor");
+            let plain = handle.symbol(c"lv2_descriptor");
+            let lib = (!lib.is_null())
+                .then(|| std::mem::transmute::<*mut c_void, LV2LibDescriptorFunction>(lib));
+            let plain = (!plain.is_null())
+                .then(|| std::mem::transmute::<*mut c_void, LV2DescriptorFunction>(plain));
+            PluginDescriptors::new(lib, plain, &bundle_path, features)
+        }
+        .ok_or_else(|| LibraryError::NoDescriptors(path.to_owned()))?;
+
+        Ok(Library {
+            inner: Arc::new(Inner {
+                descriptors,
+                bundle_path,
+                path: path.to_owned(),
+                _handle: handle,
+            }),
+        })
+    }
+
+    /// Return the path the library was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Return the bundle path, including the trailing separator.
+    pub fn bundle_path(&self) -> &CStr {
+        &self.inner.bundle_path
+    }
+
+    /// Return the descriptors of the library.
+    pub fn descriptors(&self) -> &PluginDescriptors {
+        &self.inner.descriptors
+    }
+
+    /// Return the plugin at `index`, or `None` past the last plugin.
+    pub fn plugin(&self, index: u32) -> Option<LibraryPlugin> {
+        self.descriptors()
+            .get(index)
+            .map(|descriptor| self.make_plugin(descriptor))
+    }
+
+    /// Iterate over all plugins, in index order.
+    pub fn plugins(&self) -> impl Iterator<Item = LibraryPlugin> + '_ {
+        self.descriptors().iter().map(|d| self.make_plugin(d))
+    }
+
+    /// Return the plugin with the given URI.
+    pub fn find(&self, uri: impl AsRef<CStr>) -> Option<LibraryPlugin> {
+        self.descriptors()
+            .find(uri)
+            .map(|descriptor| self.make_plugin(descriptor))
+    }
+
+    fn make_plugin(&self, descriptor: &LV2Descriptor) -> LibraryPlugin {
+        LibraryPlugin {
+            library: self.clone(),
+            descriptor: NonNull::from(descriptor),
+        }
+    }
+}
+
+impl fmt::Debug for Library {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Library")
+            .field("path", &self.inner.path)
+            .field("plugins", &self.inner.descriptors)
+            .finish()
+    }
+}
+
+/// A plugin descriptor which keeps its library loaded.
+#[derive(Clone)]
+pub struct LibraryPlugin {
+    library: Library,
+    descriptor: NonNull<LV2Descriptor>,
+}
+
+// The descriptor is immutable and owned by the library, which is shared.
+unsafe impl Send for LibraryPlugin {}
+unsafe impl Sync for LibraryPlugin {}
+
+impl LibraryPlugin {
+    /// Return the library this plugin comes from.
+    pub fn library(&self) -> &Library {
+        &self.library
+    }
+
+    /// Return the plugin descriptor.
+    pub fn descriptor(&self) -> &LV2Descriptor {
+        // Descriptors stay valid while the library is loaded.
+        unsafe { self.descriptor.as_ref() }
+    }
+
+    /// Return the URI of the plugin.
+    pub fn uri(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.descriptor().uri) }
+    }
+}
+
+impl fmt::Debug for LibraryPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LibraryPlugin").field(&self.uri()).finish()
+    }
+}
+
+/// Return the directory of the library at `path`.
+fn bundle_dir(path: &Path) -> &Path {
+    // The parent of a bare file name is empty, not `None`.
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Return `path` as `dlopen()` should get it, or `None` if it contains NUL.
+fn dlopen_path(path: &Path) -> Option<CString> {
+    // `dlopen()` searches the library path for a name without a slash.
+    let mut bytes = path.as_os_str().as_bytes().to_vec();
+    if !bytes.contains(&b'/') {
+        bytes.splice(0..0, *b"./");
+    }
+    CString::new(bytes).ok()
+}
+
+/// Return the bundle path to pass to plugins, or `None` if it contains NUL.
+fn c_bundle_path(bundle_path: &Path) -> Option<CString> {
+    // The bundle path passed to plugins must end with a separator.
+    let mut bytes = bundle_path.as_os_str().as_bytes().to_vec();
+    if bytes.last() != Some(&b'/') {
+        bytes.push(b'/');
+    }
+    CString::new(bytes).ok()
+}
+
+fn dlerror() -> String {
+    let message = unsafe { libc::dlerror() };
+    if message.is_null() {
+        String::from("unknown error")
+    } else {
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_file_name() {
+        let path = Path::new("lib.so");
+        assert_eq!(bundle_dir(path), Path::new("."));
+        assert_eq!(dlopen_path(path).unwrap().as_c_str(), c"./lib.so");
+        assert_eq!(c_bundle_path(bundle_dir(path)).unwrap().as_c_str(), c"./");
+    }
+
+    #[test]
+    fn paths_with_directories() {
+        let path = Path::new("/b/amp.lv2/amp.so");
+        assert_eq!(bundle_dir(path), Path::new("/b/amp.lv2"));
+        assert_eq!(dlopen_path(path).unwrap().as_c_str(), c"/b/amp.lv2/amp.so");
+        assert_eq!(
+            dlopen_path(Path::new("sub/lib.so")).unwrap().as_c_str(),
+            c"sub/lib.so"
+        );
+        assert_eq!(
+            c_bundle_path(Path::new("/b/amp.lv2/")).unwrap().as_c_str(),
+            c"/b/amp.lv2/"
+        );
+        assert_eq!(
+            c_bundle_path(Path::new("/b/amp.lv2")).unwrap().as_c_str(),
+            c"/b/amp.lv2/"
+        );
+        assert!(dlopen_path(Path::new("a\0b")).is_none());
+    }
+}
@@ -0,0 +1,39 @@
+//! Host-side helpers.
+//!
+//! Everything in this module is meant for programs that load and run LV2
+//! plugins (hosts, test harnesses, offline renderers), not for the plugins
+//! themselves.
+
+pub mod buffers;
+pub mod bypass;
+pub mod conformance;
+pub mod descriptors;
+pub mod discovery;
+pub mod features;
+pub mod instance;
+pub mod latency;
+#[cfg(unix)]
+pub mod library;
+pub mod port;
+pub mod profile;
+pub mod rtcheck;
+pub mod smf;
+pub mod urid;
+pub mod wav;
+
+pub use self::buffers::*;
+pub use self::bypass::*;
+pub use self::conformance::*;
+pub use self::descriptors::*;
+pub use self::discovery::*;
+pub use self::features::*;
+pub use self::instance::*;
+pub use self::latency::*;
+#[cfg(unix)]
+pub use self::library::*;
+pub use self::port::*;
+pub use self::profile::*;
+pub use self::rtcheck::*;
+pub use self::smf::*;
+pub use self::urid::*;
+pub use self::wav::*;
@@ -0,0 +1,80 @@
+//! What a host needs to know about the ports of a plugin.
+
+use crate::core::{LV2_CORE__LATENCY, PortDirection, PortProperty, PortType};
+
+/// The description of one port of a plugin.
+///
+/// Hosts normally get this from the plugin's RDF data; the index of the port
+/// is its position in the slice of ports passed around by this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortInfo {
+    /// What the port buffer contains.
+    pub port_type: PortType,
+    /// Whether the plugin reads or writes the port.
+    pub direction: PortDirection,
+    /// The lv2core port properties of the port.
+    pub properties: Vec<PortProperty>,
+    /// The URI of the `lv2:designation` of the port, if it has one.
+    pub designation: Option<String>,
+}
+
+impl PortInfo {
+    /// Create a port without any properties.
+    pub fn new(port_type: PortType, direction: PortDirection) -> Self {
+        PortInfo {
+            port_type,
+            direction,
+            properties: Vec::new(),
+            designation: None,
+        }
+    }
+
+    /// Add a port property.
+    pub fn with_property(mut self, property: PortProperty) -> Self {
+        if !self.has_property(property) {
+            self.properties.push(property);
+        }
+        self
+    }
+
+    /// Set the designation.
+    pub fn with_designation(mut self, designation: impl AsRef<str>) -> Self {
+        self.designation = Some(designation.as_ref().to_owned());
+        self
+    }
+
+    /// Return true iff the port has the given property.
+    pub fn has_property(&self, property: PortProperty) -> bool {
+        self.properties.contains(&property)
+    }
+
+    /// Return true iff the port may be left unconnected.
+    pub fn is_optional(&self) -> bool {
+        self.has_property(PortProperty::ConnectionOptional)
+    }
+
+    /// Return true iff the port has the designation with this URI.
+    pub fn is_designated(&self, designation: impl AsRef<str>) -> bool {
+        self.designation.as_deref() == Some(designation.as_ref())
+    }
+
+    /// Return true iff the port is a control output which reports the
+    /// latency of the plugin, designated `lv2:latency` or with the older
+    /// `lv2:reportsLatency` property.
+    pub fn reports_latency(&self) -> bool {
+        self.port_type == PortType::Control
+            && self.is_output()
+            && (self.is_designated(LV2_CORE__LATENCY)
+                || self.has_property(PortProperty::ReportsLatency))
+    }
+
+    /// Return true iff this is an input port.
+    pub fn is_input(&self) -> bool {
+        self.direction == PortDirection::Input
+    }
+
+    /// Return true iff this is an output port.
+    pub fn is_output(&self) -> bool {
+        self.direction == PortDirection::Output
+    }
+}
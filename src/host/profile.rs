@@ -0,0 +1,344 @@
+//! Measuring how much of the real-time budget a plugin uses.
+
+use super::buffers::PortBuffers;
+use super::instance::{Instance, InstanceError};
+#[cfg(unix)]
+use super::library::LibraryPlugin;
+use super::port::PortInfo;
+use super::urid::UridMapper;
+use crate::core::{LV2Descriptor, PortType};
+use crate::coreutils::Features;
+use crate::midi::LV2_MIDI__MIDIEVENT;
+use crate::urid::{LV2Urid, LV2UridMap};
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The input a [`Profiler`] generates for every block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stimulus {
+    /// Zeros on audio and CV inputs, and empty atom sequences.
+    Silence,
+    /// White noise on audio and CV inputs, and empty atom sequences.
+    Noise,
+    /// White noise on audio and CV inputs, and a MIDI note on or off on
+    /// every frame of atom inputs, as far as they fit.
+    DenseMidi,
+}
+
+impl fmt::Display for Stimulus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stimulus::Silence => "silence",
+            Stimulus::Noise => "noise",
+            Stimulus::DenseMidi => "dense MIDI",
+        })
+    }
+}
+
+/// The timings measured by [`Profiler::run`].
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The plugin URI.
+    pub uri: CString,
+    /// The sample rate the plugin ran at.
+    pub sample_rate: f64,
+    /// Frames per `run()` call.
+    pub block_size: u32,
+    /// The input of every block.
+    pub stimulus: Stimulus,
+    /// Time spent in `instantiate()`.
+    pub instantiate: Duration,
+    /// Time spent in `activate()`.
+    pub activate: Duration,
+    /// Time spent in `deactivate()`.
+    pub deactivate: Duration,
+    /// Time spent in each `run()` call, in order.
+    pub runs: Vec<Duration>,
+}
+
+impl Profile {
+    /// Return the real-time budget of one block: the time it takes to play.
+    pub fn budget(&self) -> Duration {
+        Duration::from_secs_f64(f64::from(self.block_size) / self.sample_rate)
+    }
+
+    /// Return `time` as a share of the budget of one block, where 1.0 is
+    /// the whole budget.
+    pub fn load(&self, time: Duration) -> f64 {
+        time.as_secs_f64() / self.budget().as_secs_f64()
+    }
+
+    /// Return the mean time of a `run()` call.
+    pub fn mean(&self) -> Duration {
+        let total: Duration = self.runs.iter().sum();
+        total / self.runs.len().max(1) as u32
+    }
+
+    /// Return the time within which the fraction `p` of the `run()` calls
+    /// finished, e.g. 0.99 for the 99th percentile.
+    pub fn percentile(&self, p: f64) -> Duration {
+        let mut runs = self.runs.clone();
+        runs.sort_unstable();
+        let rank = (p.clamp(0.0, 1.0) * runs.len() as f64).ceil() as usize;
+        runs.get(rank.saturating_sub(1))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Return the time of the slowest `run()` call.
+    pub fn worst(&self) -> Duration {
+        self.runs.iter().max().copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "<{}>: {} blocks of {} frames at {} Hz, {}",
+            self.uri.to_string_lossy(),
+            self.runs.len(),
+            self.block_size,
+            self.sample_rate,
+            self.stimulus
+        )?;
+        writeln!(f, "  budget       {:>10.3?}", self.budget())?;
+        for (name, time) in [
+            ("run() mean", self.mean()),
+            ("run() p99", self.percentile(0.99)),
+            ("run() worst", self.worst()),
+        ] {
+            writeln!(
+                f,
+                "  {name:<12} {time:>10.3?} {:>8.2}%",
+                self.load(time) * 100.0
+            )?;
+        }
+        writeln!(f, "  instantiate  {:>10.3?}", self.instantiate)?;
+        writeln!(f, "  activate     {:>10.3?}", self.activate)?;
+        write!(f, "  deactivate   {:>10.3?}", self.deactivate)
+    }
+}
+
+/// Runs a plugin through many blocks of generated input, and times every
+/// call.
+///
+/// Inputs are generated between `run()` calls, so only the plugin is timed.
+/// Times are wall-clock times of the calling thread, which the system may
+/// preempt: profile on an idle machine, and with enough blocks that the
+/// percentiles mean something.
+///
+/// ```no_run
+/// use lv2_raw::host::{FeatureList, Library, PortInfo, Profiler, Stimulus};
+/// use lv2_raw::{PortDirection, PortType};
+///
+/// let library = Library::open("amp.lv2/amp.so").unwrap();
+/// let plugin = library.find(c"urn:example:amp").unwrap();
+/// let features = FeatureList::new();
+/// let ports = vec![
+///     PortInfo::new(PortType::Control, PortDirection::Input),
+///     PortInfo::new(PortType::Audio, PortDirection::Input),
+///     PortInfo::new(PortType::Audio, PortDirection::Output),
+/// ];
+/// let mut profiler = Profiler::for_plugin(&plugin, features.features(), ports);
+/// profiler.block_size(64).blocks(10_000).stimulus(Stimulus::Noise);
+/// println!("{}", profiler.run().unwrap());
+/// ```
+pub struct Profiler<'a> {
+    descriptor: &'a LV2Descriptor,
+    bundle_path: CString,
+    features: Features<'a>,
+    ports: Vec<PortInfo>,
+    sample_rate: f64,
+    block_size: u32,
+    blocks: usize,
+    atom_capacity: u32,
+    stimulus: Stimulus,
+    controls: Vec<(u32, f32)>,
+    mapper: UridMapper,
+}
+
+impl<'a> Profiler<'a> {
+    /// Create a profiler for the plugin described by `descriptor`.
+    ///
+    /// `ports` must describe the ports of the plugin, in index order.  If
+    /// `features` has no `urid:map`, atom types are mapped by the profiler.
+    pub fn new(
+        descriptor: &'a LV2Descriptor,
+        bundle_path: &CStr,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Self {
+        Profiler {
+            descriptor,
+            bundle_path: bundle_path.to_owned(),
+            features,
+            ports,
+            sample_rate: 48000.0,
+            block_size: 256,
+            blocks: 1000,
+            atom_capacity: 8192,
+            stimulus: Stimulus::Noise,
+            controls: Vec::new(),
+            mapper: UridMapper::new(),
+        }
+    }
+
+    /// Create a profiler for a plugin from a loaded library.
+    #[cfg(unix)]
+    pub fn for_plugin(
+        plugin: &'a LibraryPlugin,
+        features: Features<'a>,
+        ports: Vec<PortInfo>,
+    ) -> Self {
+        Self::new(
+            plugin.descriptor(),
+            plugin.library().bundle_path(),
+            features,
+            ports,
+        )
+    }
+
+    /// Set the sample rate, 48 kHz by default.
+    pub fn sample_rate(&mut self, sample_rate: f64) -> &mut Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the frames per `run()` call, 256 by default.
+    pub fn block_size(&mut self, block_size: u32) -> &mut Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Set the number of `run()` calls, 1000 by default.
+    pub fn blocks(&mut self, blocks: usize) -> &mut Self {
+        self.blocks = blocks;
+        self
+    }
+
+    /// Set the size of atom buffers in bytes, 8192 by default.
+    pub fn atom_capacity(&mut self, atom_capacity: u32) -> &mut Self {
+        self.atom_capacity = atom_capacity;
+        self
+    }
+
+    /// Set the input of every block, [`Stimulus::Noise`] by default.
+    pub fn stimulus(&mut self, stimulus: Stimulus) -> &mut Self {
+        self.stimulus = stimulus;
+        self
+    }
+
+    /// Set the value of control input `index`, 0 by default.
+    pub fn control(&mut self, index: u32, value: f32) -> &mut Self {
+        self.controls.retain(|(i, _)| *i != index);
+        self.controls.push((index, value));
+        self
+    }
+
+    /// Instantiate the plugin, run it for all blocks, and return the
+    /// timings.
+    pub fn run(&self) -> Result<Profile, InstanceError> {
+        let owned_map = self.mapper.map_feature();
+        let map = self.features.get::<LV2UridMap>().unwrap_or(&owned_map);
+        let mut buffers = PortBuffers::new(&self.ports, self.block_size, self.atom_capacity, map);
+        for &(index, value) in &self.controls {
+            buffers.set_control(index, value);
+        }
+        let midi_urid = map.map(LV2_MIDI__MIDIEVENT);
+
+        let start = Instant::now();
+        let mut instance = Instance::from_descriptor(
+            self.descriptor,
+            self.sample_rate,
+            &self.bundle_path,
+            self.features,
+            self.ports.clone(),
+        )?;
+        let instantiate = start.elapsed();
+        unsafe { buffers.connect(&mut instance)? };
+
+        let start = Instant::now();
+        instance.activate()?;
+        let activate = start.elapsed();
+
+        let mut noise = Noise(0x2545_f491);
+        let mut runs = Vec::with_capacity(self.blocks);
+        for _ in 0..self.blocks {
+            self.prepare(&mut buffers, &mut noise, midi_urid);
+            let start = Instant::now();
+            instance.run(self.block_size)?;
+            runs.push(start.elapsed());
+        }
+
+        let start = Instant::now();
+        instance.deactivate()?;
+        let deactivate = start.elapsed();
+
+        Ok(Profile {
+            uri: instance.uri().to_owned(),
+            sample_rate: self.sample_rate,
+            block_size: self.block_size,
+            stimulus: self.stimulus,
+            instantiate,
+            activate,
+            deactivate,
+            runs,
+        })
+    }
+
+    fn prepare(&self, buffers: &mut PortBuffers, noise: &mut Noise, midi_urid: LV2Urid) {
+        buffers.prepare_atoms();
+        for (index, port) in self.ports.iter().enumerate() {
+            let index = index as u32;
+            if !port.is_input() {
+                continue;
+            }
+            match port.port_type {
+                PortType::Audio | PortType::Cv => {
+                    let samples = buffers.samples_mut(index).unwrap();
+                    if self.stimulus == Stimulus::Silence {
+                        samples.fill(0.0);
+                    } else {
+                        samples.fill_with(|| noise.next());
+                    }
+                }
+                PortType::Atom if self.stimulus == Stimulus::DenseMidi => {
+                    // Alternate note on and note off.
+                    let events = (0..self.block_size).map(|frame| {
+                        (
+                            frame,
+                            [0x90 - (frame % 2) as u8 * 0x10, (frame % 128) as u8, 100],
+                        )
+                    });
+                    buffers.append_midi(index, midi_urid, events);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Profiler<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler")
+            .field("uri", &unsafe { CStr::from_ptr(self.descriptor.uri) })
+            .field("block_size", &self.block_size)
+            .field("blocks", &self.blocks)
+            .field("stimulus", &self.stimulus)
+            .finish()
+    }
+}
+
+/// A xorshift generator of white noise in `[-1, 1)`.
+struct Noise(u32);
+
+impl Noise {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / 8_388_608.0 - 1.0
+    }
+}
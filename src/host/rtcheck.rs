@@ -0,0 +1,282 @@
+//! Catching what a plugin must not do in `run()`: allocate, free or block.
+//!
+//! The checker has two parts.  [`RtCheckAllocator`] wraps the global
+//! allocator of a test binary, and [`rt_check`] marks a stretch of code on
+//! the current thread as real-time.  Every allocation, reallocation and
+//! deallocation the allocator sees inside a real-time stretch is recorded
+//! with a backtrace:
+//!
+//! ```no_run
+//! use lv2_raw::host::{RtCheckAllocator, rt_check};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: RtCheckAllocator = RtCheckAllocator::new();
+//!
+//! # fn run_plugin() {}
+//! let ((), report) = rt_check(|| run_plugin());
+//! assert!(report.is_ok(), "{report}");
+//! ```
+//!
+//! [`Instance::run_checked`](super::Instance::run_checked) and
+//! [`Conformance::hard_rt_capable`](super::Conformance::hard_rt_capable) do
+//! this around `run()`.
+//!
+//! The checker has blind spots:
+//!
+//! - Only memory from the Rust global allocator is seen.  Plugins written
+//!   in C or C++, and Rust plugins loaded from a library with its own
+//!   allocator, call `malloc()` directly.
+//! - System calls are not intercepted.  Blocking calls are only seen when
+//!   they are reported with [`rt_blocking`], as the host features of this
+//!   crate do, or when they allocate on the way.
+//! - Work the plugin hands to other threads is not checked.
+//!
+//! So a clean report means nothing was caught, not that `run()` is
+//! real-time safe.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The most violations recorded with a backtrace by one [`rt_check`].
+const MAX_RECORDED: usize = 16;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Whether the current thread is inside [`rt_check`] and not recording.
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+    static VIOLATIONS: RefCell<Vec<RtViolation>> = const { RefCell::new(Vec::new()) };
+    static OMITTED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A global allocator which records allocations made inside [`rt_check`],
+/// and passes everything on to another allocator, [`System`] by default.
+#[derive(Debug, Default)]
+pub struct RtCheckAllocator<A = System> {
+    inner: A,
+}
+
+impl RtCheckAllocator {
+    /// Create a checking wrapper around the system allocator.
+    pub const fn new() -> Self {
+        RtCheckAllocator { inner: System }
+    }
+}
+
+impl<A> RtCheckAllocator<A> {
+    /// Create a checking wrapper around `inner`.
+    pub const fn wrap(inner: A) -> Self {
+        RtCheckAllocator { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for RtCheckAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        note_installed();
+        record(RtViolationKind::Allocation {
+            size: layout.size(),
+        });
+        unsafe { self.inner.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        note_installed();
+        record(RtViolationKind::Allocation {
+            size: layout.size(),
+        });
+        unsafe { self.inner.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(RtViolationKind::Deallocation {
+            size: layout.size(),
+        });
+        unsafe { self.inner.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(RtViolationKind::Reallocation {
+            old_size: layout.size(),
+            new_size,
+        });
+        unsafe { self.inner.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn note_installed() {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Return true iff [`RtCheckAllocator`] is the global allocator, so that
+/// [`rt_check`] can see allocations.
+pub fn is_installed() -> bool {
+    // Make sure at least one allocation went through the global allocator.
+    drop(std::hint::black_box(Box::new(0u8)));
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Report a blocking call, such as taking a lock or doing I/O, if it is made
+/// inside [`rt_check`].
+///
+/// Host feature implementations which block call this, so that plugins
+/// which use them in `run()` are caught.
+pub fn rt_blocking(call: &'static str) {
+    record(RtViolationKind::Blocking { call });
+}
+
+fn record(kind: RtViolationKind) {
+    if !ACTIVE.try_with(Cell::get).unwrap_or(false) {
+        return;
+    }
+    // Capturing the backtrace and storing it allocates, which must not
+    // recurse.
+    ACTIVE.set(false);
+    let _ = VIOLATIONS.try_with(|violations| {
+        let mut violations = violations.borrow_mut();
+        if violations.len() < MAX_RECORDED {
+            violations.push(RtViolation {
+                kind,
+                backtrace: Backtrace::force_capture(),
+            });
+        } else {
+            OMITTED.set(OMITTED.get() + 1);
+        }
+    });
+    ACTIVE.set(true);
+}
+
+/// Something done in [`rt_check`] which is not real-time safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtViolationKind {
+    /// Memory was allocated.
+    Allocation {
+        /// Bytes requested.
+        size: usize,
+    },
+    /// Memory was freed.
+    Deallocation {
+        /// Bytes freed.
+        size: usize,
+    },
+    /// Memory was resized.
+    Reallocation {
+        /// Bytes before.
+        old_size: usize,
+        /// Bytes requested.
+        new_size: usize,
+    },
+    /// A blocking call was reported with [`rt_blocking`].
+    Blocking {
+        /// What was called.
+        call: &'static str,
+    },
+}
+
+impl fmt::Display for RtViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RtViolationKind::Allocation { size } => write!(f, "allocated {size} bytes"),
+            RtViolationKind::Deallocation { size } => write!(f, "freed {size} bytes"),
+            RtViolationKind::Reallocation { old_size, new_size } => {
+                write!(f, "reallocated {old_size} bytes to {new_size}")
+            }
+            RtViolationKind::Blocking { call } => write!(f, "called {call}"),
+        }
+    }
+}
+
+/// A violation, with where it happened.
+#[derive(Debug)]
+pub struct RtViolation {
+    /// What was done.
+    pub kind: RtViolationKind,
+    /// The stack when it was done.
+    pub backtrace: Backtrace,
+}
+
+impl fmt::Display for RtViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.kind, self.backtrace)
+    }
+}
+
+/// The result of [`rt_check`].
+#[derive(Debug, Default)]
+pub struct RtReport {
+    /// The first violations, in order.
+    pub violations: Vec<RtViolation>,
+    /// The number of violations after those in `violations`.
+    pub omitted: usize,
+}
+
+impl RtReport {
+    /// Return true iff no violations were caught.
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Return the number of violations caught.
+    pub fn count(&self) -> usize {
+        self.violations.len() + self.omitted
+    }
+}
+
+impl fmt::Display for RtReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return f.write_str("no real-time violations");
+        }
+        write!(f, "{} real-time violations", self.count())?;
+        for violation in &self.violations {
+            write!(f, "\n\n{violation}")?;
+        }
+        if self.omitted > 0 {
+            write!(f, "\n\nand {} more", self.omitted)?;
+        }
+        Ok(())
+    }
+}
+
+/// Call `f` as real-time code on the current thread, and return its result
+/// with the violations caught.
+///
+/// Allocations are only caught if [`RtCheckAllocator`] is the global
+/// allocator; see [`is_installed`].  Calls may be nested, and each reports
+/// only its own violations.
+pub fn rt_check<R>(f: impl FnOnce() -> R) -> (R, RtReport) {
+    /// Restores the state of an enclosing `rt_check`, even on panics.
+    struct Section {
+        active: bool,
+        violations: Vec<RtViolation>,
+        omitted: usize,
+    }
+
+    impl Drop for Section {
+        fn drop(&mut self) {
+            ACTIVE.set(false);
+            VIOLATIONS.set(std::mem::take(&mut self.violations));
+            OMITTED.set(self.omitted);
+            ACTIVE.set(self.active);
+        }
+    }
+
+    let section = Section {
+        active: ACTIVE.replace(false),
+        violations: VIOLATIONS.take(),
+        omitted: OMITTED.take(),
+    };
+    ACTIVE.set(true);
+    let result = f();
+    ACTIVE.set(false);
+    let report = RtReport {
+        violations: VIOLATIONS.take(),
+        omitted: OMITTED.take(),
+    };
+    drop(section);
+    (result, report)
+}
@@ -0,0 +1,272 @@
+//! Reading Standard MIDI Files.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Error returned when a MIDI file can not be read.
+#[derive(Debug)]
+pub enum SmfError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file is not a well-formed Standard MIDI File.
+    Invalid(String),
+}
+
+impl fmt::Display for SmfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmfError::Io(error) => error.fmt(f),
+            SmfError::Invalid(message) => write!(f, "invalid MIDI file: {message}"),
+        }
+    }
+}
+
+impl Error for SmfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SmfError::Io(error) => Some(error),
+            SmfError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SmfError {
+    fn from(error: io::Error) -> Self {
+        SmfError::Io(error)
+    }
+}
+
+/// A MIDI message at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmfEvent {
+    /// Seconds from the start of the file.
+    pub time: f64,
+    /// The message, with its status byte.  System exclusive messages start
+    /// with `0xF0`.
+    pub data: Vec<u8>,
+}
+
+/// The MIDI messages of a Standard MIDI File, in time order.
+///
+/// The tracks of format 1 and 2 files are merged, and times are converted
+/// to seconds with the tempo changes of all tracks.  Meta events are not
+/// kept.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MidiFile {
+    /// The messages, sorted by time.
+    pub events: Vec<SmfEvent>,
+}
+
+impl MidiFile {
+    /// Read a MIDI file.
+    pub fn open(path: impl AsRef<Path>) -> Result<MidiFile, SmfError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Return the time of the last message in seconds.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |e| e.time)
+    }
+
+    /// Parse the contents of a MIDI file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MidiFile, SmfError> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(4)? != b"MThd" {
+            return Err(SmfError::Invalid("no MThd header".to_owned()));
+        }
+        let size = reader.u32()? as usize;
+        let header = reader.take(size)?;
+        if header.len() < 6 {
+            return Err(SmfError::Invalid("header too short".to_owned()));
+        }
+        let tracks = u16::from_be_bytes([header[2], header[3]]);
+        let division = u16::from_be_bytes([header[4], header[5]]);
+
+        // (tick, track, index) orders events of the same tick as in the file.
+        let mut messages: Vec<(u64, usize, usize, Vec<u8>)> = Vec::new();
+        let mut tempos: Vec<(u64, u32)> = Vec::new();
+        let mut track = 0;
+        while track < usize::from(tracks) && !reader.is_empty() {
+            let id = reader.take(4)?;
+            let size = reader.u32()? as usize;
+            let chunk = reader.take(size)?;
+            if id != b"MTrk" {
+                continue;
+            }
+            parse_track(chunk, track, &mut messages, &mut tempos)?;
+            track += 1;
+        }
+
+        messages.sort_by_key(|(tick, track, index, _)| (*tick, *track, *index));
+        tempos.sort_by_key(|(tick, _)| *tick);
+        let timing = Timing::new(division, tempos);
+        Ok(MidiFile {
+            events: messages
+                .into_iter()
+                .map(|(tick, _, _, data)| SmfEvent {
+                    time: timing.seconds(tick),
+                    data,
+                })
+                .collect(),
+        })
+    }
+}
+
+fn parse_track(
+    chunk: &[u8],
+    track: usize,
+    messages: &mut Vec<(u64, usize, usize, Vec<u8>)>,
+    tempos: &mut Vec<(u64, u32)>,
+) -> Result<(), SmfError> {
+    let mut reader = Reader {
+        bytes: chunk,
+        pos: 0,
+    };
+    let mut tick = 0u64;
+    let mut running_status = None;
+    while !reader.is_empty() {
+        tick += u64::from(reader.vlq()?);
+        let mut status = reader.u8()?;
+        let mut first = None;
+        if status < 0x80 {
+            // Running status: this was the first data byte.
+            first = Some(status);
+            status = running_status
+                .ok_or_else(|| SmfError::Invalid("data byte without status".to_owned()))?;
+        }
+        match status {
+            0xff => {
+                let kind = reader.u8()?;
+                let size = reader.vlq()? as usize;
+                let data = reader.take(size)?;
+                running_status = None;
+                match kind {
+                    0x2f => break,
+                    0x51 if data.len() == 3 => {
+                        let tempo = u32::from_be_bytes([0, data[0], data[1], data[2]]);
+                        tempos.push((tick, tempo));
+                    }
+                    _ => {}
+                }
+            }
+            0xf0 | 0xf7 => {
+                let size = reader.vlq()? as usize;
+                let data = reader.take(size)?;
+                let mut message = Vec::with_capacity(data.len() + 1);
+                if status == 0xf0 {
+                    message.push(0xf0);
+                }
+                message.extend_from_slice(data);
+                messages.push((tick, track, messages.len(), message));
+                running_status = None;
+            }
+            0x80..=0xef => {
+                let length = if matches!(status & 0xf0, 0xc0 | 0xd0) {
+                    1
+                } else {
+                    2
+                };
+                let mut message = vec![status];
+                if let Some(first) = first {
+                    message.push(first);
+                }
+                while message.len() <= length {
+                    message.push(reader.u8()?);
+                }
+                messages.push((tick, track, messages.len(), message));
+                running_status = Some(status);
+            }
+            _ => {
+                return Err(SmfError::Invalid(format!(
+                    "unexpected status byte {status:#04x}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts ticks to seconds.
+enum Timing {
+    /// Ticks per quarter note, and tempo changes in microseconds per
+    /// quarter note.
+    Metrical(f64, Vec<(u64, u32)>),
+    /// Seconds per tick.
+    Timecode(f64),
+}
+
+impl Timing {
+    fn new(division: u16, tempos: Vec<(u64, u32)>) -> Self {
+        if division & 0x8000 != 0 {
+            let frames_per_second = -f64::from(division.to_be_bytes()[0] as i8);
+            let ticks_per_frame = f64::from(division & 0xff);
+            Timing::Timecode(1.0 / (frames_per_second * ticks_per_frame).max(1.0))
+        } else {
+            Timing::Metrical(f64::from(division.max(1)), tempos)
+        }
+    }
+
+    fn seconds(&self, tick: u64) -> f64 {
+        match self {
+            Timing::Timecode(seconds_per_tick) => tick as f64 * seconds_per_tick,
+            Timing::Metrical(ticks_per_quarter, tempos) => {
+                // 120 BPM until the first tempo change.
+                let (mut seconds, mut last_tick, mut tempo) = (0.0, 0, 500_000);
+                for &(change, new_tempo) in tempos.iter().take_while(|(t, _)| *t <= tick) {
+                    seconds +=
+                        (change - last_tick) as f64 * f64::from(tempo) / 1e6 / ticks_per_quarter;
+                    last_tick = change;
+                    tempo = new_tempo;
+                }
+                seconds + (tick - last_tick) as f64 * f64::from(tempo) / 1e6 / ticks_per_quarter
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SmfError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| SmfError::Invalid("unexpected end of file".to_owned()))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, SmfError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, SmfError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Read a variable-length quantity.
+    fn vlq(&mut self) -> Result<u32, SmfError> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            value = (value << 7) | u32::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(SmfError::Invalid(
+            "variable-length quantity too long".to_owned(),
+        ))
+    }
+}
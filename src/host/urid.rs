@@ -0,0 +1,159 @@
+//! A host-side implementation of the URID map and unmap features.
+
+use super::rtcheck::rt_blocking;
+use crate::urid::{LV2Urid, LV2UridMap, LV2UridUnmap};
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Table {
+    ids: HashMap<CString, LV2Urid>,
+    // Index `i` holds the URI mapped to `i + 1`.  The heap buffer of a
+    // `CString` never moves, so pointers handed out by `unmap` stay valid
+    // until the table is dropped.
+    uris: Vec<CString>,
+}
+
+/// Maps URIs to URIDs and back.
+///
+/// The mapper owns its URI table and hands out [`LV2UridMap`] and
+/// [`LV2UridUnmap`] feature structs whose `handle` points back into it.  It
+/// may be shared by any number of plugin instances on any number of threads.
+///
+/// URIDs start at 1 and are never reused, so 0 is only returned for invalid
+/// input (a NULL pointer).  Strings returned by `unmap` remain valid for the
+/// whole lifetime of the mapper.
+pub struct UridMapper {
+    // Boxed so the feature handles stay valid when the mapper itself moves.
+    table: Box<Mutex<Table>>,
+}
+
+impl Table {
+    fn map(lock: &Mutex<Table>, uri: &CStr) -> LV2Urid {
+        let mut table = lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&id) = table.ids.get(uri) {
+            return id;
+        }
+
+        let owned = CString::from(uri);
+        table.uris.push(owned.clone());
+        let id = table.uris.len() as LV2Urid;
+        table.ids.insert(owned, id);
+        id
+    }
+
+    fn unmap(lock: &Mutex<Table>, urid: LV2Urid) -> *const c_char {
+        let table = lock.lock().unwrap_or_else(|e| e.into_inner());
+        match (urid as usize).checked_sub(1) {
+            Some(i) if i < table.uris.len() => table.uris[i].as_ptr(),
+            _ => ptr::null(),
+        }
+    }
+}
+
+impl UridMapper {
+    /// Create an empty mapper.
+    pub fn new() -> Self {
+        UridMapper {
+            table: Box::new(Mutex::new(Table::default())),
+        }
+    }
+
+    /// Return the URID for `uri`, creating it if necessary.
+    pub fn map(&self, uri: impl AsRef<CStr>) -> LV2Urid {
+        Table::map(&self.table, uri.as_ref())
+    }
+
+    /// Return the URI for a previously mapped `urid`.
+    pub fn unmap(&self, urid: LV2Urid) -> Option<&CStr> {
+        let ptr = Table::unmap(&self.table, urid);
+        if ptr.is_null() {
+            None
+        } else {
+            // The string lives as long as the table, and thus as long as `self`.
+            unsafe { Some(CStr::from_ptr(ptr)) }
+        }
+    }
+
+    /// Return the number of URIs mapped so far.
+    pub fn len(&self) -> usize {
+        self.table
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .uris
+            .len()
+    }
+
+    /// Return true iff no URI has been mapped yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return an `LV2_URID__map` feature struct backed by this mapper.
+    ///
+    /// The returned struct is valid for as long as the mapper is alive.
+    pub fn map_feature(&self) -> LV2UridMap {
+        LV2UridMap {
+            handle: self.handle(),
+            map: map_trampoline,
+        }
+    }
+
+    /// Return an `LV2_URID__unmap` feature struct backed by this mapper.
+    ///
+    /// The returned struct is valid for as long as the mapper is alive.
+    pub fn unmap_feature(&self) -> LV2UridUnmap {
+        LV2UridUnmap {
+            handle: self.handle(),
+            unmap: unmap_trampoline,
+        }
+    }
+
+    fn handle(&self) -> *mut c_void {
+        &*self.table as *const Mutex<Table> as *mut c_void
+    }
+}
+
+impl Default for UridMapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for UridMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UridMapper")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Recover the mapper table from a feature handle.
+///
+/// # Safety
+///
+/// `handle` must come from [`UridMapper::map_feature`] or
+/// [`UridMapper::unmap_feature`] of a mapper that is still alive.
+unsafe fn table_from_handle<'a>(handle: *mut c_void) -> &'a Mutex<Table> {
+    unsafe { &*(handle as *const Mutex<Table>) }
+}
+
+extern "C" fn map_trampoline(handle: *mut c_void, uri: *const c_char) -> LV2Urid {
+    if handle.is_null() || uri.is_null() {
+        return 0;
+    }
+    // The table is locked, and may grow.
+    rt_blocking("urid:map");
+    unsafe { Table::map(table_from_handle(handle), CStr::from_ptr(uri)) }
+}
+
+extern "C" fn unmap_trampoline(handle: *mut c_void, urid: LV2Urid) -> *const c_char {
+    if handle.is_null() {
+        return ptr::null();
+    }
+    rt_blocking("urid:unmap");
+    unsafe { Table::unmap(table_from_handle(handle), urid) }
+}
@@ -0,0 +1,289 @@
+//! Reading and writing WAV files.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// The sample format of a WAV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavFormat {
+    /// Unsigned 8-bit integers.
+    Pcm8,
+    /// Signed 16-bit integers.
+    Pcm16,
+    /// Signed 24-bit integers.
+    Pcm24,
+    /// Signed 32-bit integers.
+    Pcm32,
+    /// 32-bit floats.
+    Float32,
+    /// 64-bit floats.
+    Float64,
+}
+
+impl WavFormat {
+    /// Return the number of bits per sample.
+    pub fn bits(self) -> u16 {
+        match self {
+            WavFormat::Pcm8 => 8,
+            WavFormat::Pcm16 => 16,
+            WavFormat::Pcm24 => 24,
+            WavFormat::Pcm32 | WavFormat::Float32 => 32,
+            WavFormat::Float64 => 64,
+        }
+    }
+
+    /// Return true iff samples are floats.
+    pub fn is_float(self) -> bool {
+        matches!(self, WavFormat::Float32 | WavFormat::Float64)
+    }
+
+    fn from_tag(tag: u16, bits: u16) -> Option<Self> {
+        Some(match (tag, bits) {
+            (FORMAT_PCM, 8) => WavFormat::Pcm8,
+            (FORMAT_PCM, 16) => WavFormat::Pcm16,
+            (FORMAT_PCM, 24) => WavFormat::Pcm24,
+            (FORMAT_PCM, 32) => WavFormat::Pcm32,
+            (FORMAT_FLOAT, 32) => WavFormat::Float32,
+            (FORMAT_FLOAT, 64) => WavFormat::Float64,
+            _ => return None,
+        })
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            WavFormat::Pcm8 => (f32::from(bytes[0]) - 128.0) / 128.0,
+            WavFormat::Pcm16 => f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0,
+            WavFormat::Pcm24 => {
+                // Sign-extend through the top byte of an i32.
+                let value = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+                value as f32 / 8_388_608.0
+            }
+            WavFormat::Pcm32 => {
+                let value = i32::from_le_bytes(bytes.try_into().unwrap());
+                (f64::from(value) / 2_147_483_648.0) as f32
+            }
+            WavFormat::Float32 => f32::from_le_bytes(bytes.try_into().unwrap()),
+            WavFormat::Float64 => f64::from_le_bytes(bytes.try_into().unwrap()) as f32,
+        }
+    }
+
+    fn encode(self, sample: f32, out: &mut Vec<u8>) {
+        // The inverse of `decode`, so integer samples survive a round trip.
+        let clamped = f64::from(sample).clamp(-1.0, 1.0);
+        let scaled = |range: f64| (clamped * range).round().min(range - 1.0);
+        match self {
+            WavFormat::Pcm8 => out.push((scaled(128.0) + 128.0) as u8),
+            WavFormat::Pcm16 => out.extend_from_slice(&(scaled(32768.0) as i16).to_le_bytes()),
+            WavFormat::Pcm24 => {
+                out.extend_from_slice(&(scaled(8_388_608.0) as i32).to_le_bytes()[..3]);
+            }
+            WavFormat::Pcm32 => {
+                out.extend_from_slice(&(scaled(2_147_483_648.0) as i32).to_le_bytes());
+            }
+            WavFormat::Float32 => out.extend_from_slice(&sample.to_le_bytes()),
+            WavFormat::Float64 => out.extend_from_slice(&f64::from(sample).to_le_bytes()),
+        }
+    }
+}
+
+/// Error returned when a WAV file can not be read or written.
+#[derive(Debug)]
+pub enum WavError {
+    /// The file could not be read or written.
+    Io(io::Error),
+    /// The file is not a well-formed WAV file.
+    Invalid(String),
+    /// The sample format is not supported.
+    Unsupported(String),
+}
+
+impl fmt::Display for WavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WavError::Io(error) => error.fmt(f),
+            WavError::Invalid(message) => write!(f, "invalid WAV file: {message}"),
+            WavError::Unsupported(message) => write!(f, "unsupported WAV file: {message}"),
+        }
+    }
+}
+
+impl Error for WavError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WavError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WavError {
+    fn from(error: io::Error) -> Self {
+        WavError::Io(error)
+    }
+}
+
+/// Audio from or for a WAV file, with the samples of each channel as `f32`.
+///
+/// Integer samples are scaled to `[-1, 1)` when read, and clipped to that
+/// range when written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wav {
+    /// Frames per second.
+    pub sample_rate: u32,
+    /// The sample format of the file.
+    pub format: WavFormat,
+    /// The samples of each channel.  All channels have the same length.
+    pub channels: Vec<Vec<f32>>,
+}
+
+impl Wav {
+    /// Return the number of frames.
+    pub fn frames(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+
+    /// Read a WAV file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Wav, WavError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Write a WAV file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), WavError> {
+        Ok(std::fs::write(path, self.to_bytes()?)?)
+    }
+
+    /// Parse the contents of a WAV file.
+    ///
+    /// PCM and float samples are supported, in plain or
+    /// `WAVE_FORMAT_EXTENSIBLE` files.  Chunks other than `fmt ` and `data`
+    /// are skipped.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Wav, WavError> {
+        let invalid = |message: &str| WavError::Invalid(message.to_owned());
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(invalid("no RIFF/WAVE header"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut rest = &bytes[12..];
+        while rest.len() >= 8 {
+            let id = &rest[0..4];
+            let size = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+            // Streamed files may have a placeholder size; take what is there.
+            let body = &rest[8..size.saturating_add(8).min(rest.len())];
+            match id {
+                b"fmt " => format = Some(parse_format(body)?),
+                b"data" => data = Some(body),
+                _ => {}
+            }
+            rest = &rest[size.saturating_add(8 + size % 2).min(rest.len())..];
+        }
+
+        let (format, channel_count, sample_rate) = format.ok_or_else(|| invalid("no fmt chunk"))?;
+        let data = data.ok_or_else(|| invalid("no data chunk"))?;
+        let sample_size = usize::from(format.bits() / 8);
+        let frame_size = sample_size * channel_count;
+        let frames = data.len() / frame_size;
+
+        let mut channels = vec![Vec::with_capacity(frames); channel_count];
+        for frame in data.chunks_exact(frame_size) {
+            for (channel, sample) in channels.iter_mut().zip(frame.chunks_exact(sample_size)) {
+                channel.push(format.decode(sample));
+            }
+        }
+        Ok(Wav {
+            sample_rate,
+            format,
+            channels,
+        })
+    }
+
+    /// Return the contents of a WAV file with this audio.
+    ///
+    /// Fails if the channels have different lengths, or the audio does not
+    /// fit the 32-bit sizes of a WAV file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WavError> {
+        let invalid = |message: &str| WavError::Invalid(message.to_owned());
+        let frames = self.frames();
+        if self.channels.iter().any(|channel| channel.len() != frames) {
+            return Err(invalid("channels have different lengths"));
+        }
+        let too_large = || invalid("too large");
+        let channels = u16::try_from(self.channels.len()).map_err(|_| too_large())?;
+        let bits = self.format.bits();
+        let block_align = channels.checked_mul(bits / 8).ok_or_else(too_large)?;
+        let byte_rate = self
+            .sample_rate
+            .checked_mul(u32::from(block_align))
+            .ok_or_else(too_large)?;
+        let data_size = frames
+            .checked_mul(usize::from(block_align))
+            .ok_or_else(too_large)?;
+        let data_size_u32 = u32::try_from(data_size).map_err(|_| too_large())?;
+        let riff_size = data_size_u32
+            .checked_add(36 + data_size_u32 % 2)
+            .ok_or_else(too_large)?;
+        let tag = if self.format.is_float() {
+            FORMAT_FLOAT
+        } else {
+            FORMAT_PCM
+        };
+
+        let mut out = Vec::with_capacity(44 + data_size);
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&riff_size.to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&tag.to_le_bytes());
+        out.extend_from_slice(&channels.to_le_bytes());
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&byte_rate.to_le_bytes());
+        out.extend_from_slice(&block_align.to_le_bytes());
+        out.extend_from_slice(&bits.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&data_size_u32.to_le_bytes());
+        for frame in 0..frames {
+            for channel in &self.channels {
+                self.format.encode(channel[frame], &mut out);
+            }
+        }
+        if data_size % 2 == 1 {
+            out.push(0);
+        }
+        Ok(out)
+    }
+}
+
+/// Return the sample format, channel count and sample rate of a `fmt `
+/// chunk.
+fn parse_format(body: &[u8]) -> Result<(WavFormat, usize, u32), WavError> {
+    if body.len() < 16 {
+        return Err(WavError::Invalid("fmt chunk too short".to_owned()));
+    }
+    let u16_at = |i: usize| u16::from_le_bytes([body[i], body[i + 1]]);
+    let mut tag = u16_at(0);
+    let channels = u16_at(2);
+    let sample_rate = u32::from_le_bytes(body[4..8].try_into().unwrap());
+    let bits = u16_at(14);
+    if tag == FORMAT_EXTENSIBLE {
+        if body.len() < 26 {
+            return Err(WavError::Invalid(
+                "extensible fmt chunk too short".to_owned(),
+            ));
+        }
+        // The sub-format GUID starts with the format tag.
+        tag = u16_at(24);
+    }
+    if channels == 0 {
+        return Err(WavError::Invalid("no channels".to_owned()));
+    }
+    let format = WavFormat::from_tag(tag, bits)
+        .ok_or_else(|| WavError::Unsupported(format!("format {tag} with {bits} bits")))?;
+    Ok((format, usize::from(channels), sample_rate))
+}
@@ -1,21 +1,59 @@
-//! Fast and easy queue abstraction.
+//! Raw bindings to the LV2 plugin interface, and safe helpers for plugins and
+//! hosts.
+//!
+//! The raw LV2 types and the plugin side need only `core` and `alloc`, so
+//! the crate can be used in `no_std` DSP code by turning off the default
+//! `std` feature.  The host, the bundle loader and the Turtle parser need
+//! `std`.
 
-extern crate libc;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod atom;
 pub mod atomutils;
+#[cfg(feature = "std")]
+pub mod bundle;
+#[cfg(feature = "std")]
+pub mod control;
 pub mod core;
 pub mod coreutils;
+#[cfg(feature = "std")]
+pub mod host;
+pub mod log;
 pub mod midi;
+pub mod options;
+pub mod plugin;
+pub mod pluginui;
+pub mod portprops;
+pub mod ports;
+pub mod state;
 pub mod time;
+#[cfg(feature = "std")]
+pub mod turtle;
 pub mod ui;
+pub mod units;
+pub mod uri;
 pub mod urid;
+pub mod urimap;
+pub mod worker;
 
 pub use atom::*;
 pub use atomutils::*;
 pub use core::*;
 pub use coreutils::*;
+pub use log::*;
 pub use midi::*;
+pub use options::*;
+pub use plugin::*;
+pub use pluginui::*;
+pub use portprops::*;
+pub use ports::*;
+pub use state::*;
 pub use time::*;
 pub use ui::*;
+pub use units::*;
+pub use uri::*;
 pub use urid::*;
+pub use urimap::*;
+pub use worker::*;
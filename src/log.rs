@@ -0,0 +1,93 @@
+// Documentation copied from http://lv2plug.in/ns/ext/log/log.h
+
+// Copyright text of the original C file:
+
+// Copyright 2012-2016 David Robillard <http://drobilla.net>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/log/log.html>.
+
+use core::ffi::*;
+
+use crate::coreutils::FeatureData;
+use crate::uri::Uri;
+use crate::urid::LV2Urid;
+
+pub const LV2_LOG_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log");
+pub const LV2_LOG_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#");
+
+pub const LV2_LOG__ENTRY: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#Entry");
+pub const LV2_LOG__ERROR: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#Error");
+pub const LV2_LOG__NOTE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#Note");
+pub const LV2_LOG__TRACE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#Trace");
+pub const LV2_LOG__WARNING: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#Warning");
+pub const LV2_LOG__LOG: Uri = Uri::new(c"http://lv2plug.in/ns/ext/log#log");
+
+/**
+   Opaque data to host data for LV2_Log_Log.
+*/
+pub type LV2LogHandle = *mut c_void;
+
+/**
+   Log feature (LV2_LOG__log)
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2LogLog {
+    /**
+       Opaque pointer to host data.
+
+       This MUST be passed to methods in this struct whenever they are called.
+       Otherwise, it must not be interpreted in any way.
+    */
+    pub handle: LV2LogHandle,
+
+    /**
+       Log a message, passing format parameters directly.
+
+       The API of this function matches that of the standard C printf function,
+       except for the addition of the first two parameters.  This function may
+       be called from any non-realtime context, or from any context if `type`
+       is LV2_LOG__Trace.
+    */
+    pub printf: unsafe extern "C" fn(
+        handle: LV2LogHandle,
+        type_: LV2Urid,
+        fmt: *const c_char,
+        ...
+    ) -> c_int,
+
+    /**
+       Log a message, passing format parameters in a va_list.
+
+       The API of this function matches that of the standard C vprintf
+       function, except for the addition of the first two parameters.  This
+       function may be called from any non-realtime context, or from any
+       context if `type` is LV2_LOG__Trace.
+
+       `va_list` has no stable Rust equivalent, so this is declared as a
+       pointer, which matches the ABI only on platforms where `va_list` is
+       passed as a pointer (e.g. x86-64).  Prefer `printf`.
+    */
+    pub vprintf: unsafe extern "C" fn(
+        handle: LV2LogHandle,
+        type_: LV2Urid,
+        fmt: *const c_char,
+        ap: *mut c_void,
+    ) -> c_int,
+}
+
+unsafe impl FeatureData for LV2LogLog {
+    const URI: Uri = LV2_LOG__LOG;
+}
@@ -18,50 +18,56 @@
 
 //! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/midi/midi.html>.
 
-pub static LV2_MIDI_URI: &[u8] = b"http://lv2plug.in/ns/ext/midi\0";
-pub static LV2_MIDI_PREFIX: &[u8] = b"http://lv2plug.in/ns/ext/midi#\0";
+use crate::uri::Uri;
 
-pub static LV2_MIDI__ACTIVESENSE: &[u8] = b"http://lv2plug.in/ns/ext/midi#ActiveSense\0";
-pub static LV2_MIDI__AFTERTOUCH: &[u8] = b"http://lv2plug.in/ns/ext/midi#Aftertouch\0";
-pub static LV2_MIDI__BENDER: &[u8] = b"http://lv2plug.in/ns/ext/midi#Bender\0";
-pub static LV2_MIDI__CHANNELPRESSURE: &[u8] = b"http://lv2plug.in/ns/ext/midi#ChannelPressure\0";
-pub static LV2_MIDI__CHUNK: &[u8] = b"http://lv2plug.in/ns/ext/midi#Chunk\0";
-pub static LV2_MIDI__CLOCK: &[u8] = b"http://lv2plug.in/ns/ext/midi#Clock\0";
-pub static LV2_MIDI__CONTINUE: &[u8] = b"http://lv2plug.in/ns/ext/midi#Continue\0";
-pub static LV2_MIDI__CONTROLLER: &[u8] = b"http://lv2plug.in/ns/ext/midi#Controller\0";
-pub static LV2_MIDI__MIDIEVENT: &[u8] = b"http://lv2plug.in/ns/ext/midi#MidiEvent\0";
-pub static LV2_MIDI__NOTEOFF: &[u8] = b"http://lv2plug.in/ns/ext/midi#NoteOff\0";
-pub static LV2_MIDI__NOTEON: &[u8] = b"http://lv2plug.in/ns/ext/midi#NoteOn\0";
-pub static LV2_MIDI__PROGRAMCHANGE: &[u8] = b"http://lv2plug.in/ns/ext/midi#ProgramChange\0";
-pub static LV2_MIDI__QUARTERFRAME: &[u8] = b"http://lv2plug.in/ns/ext/midi#QuarterFrame\0";
-pub static LV2_MIDI__RESET: &[u8] = b"http://lv2plug.in/ns/ext/midi#Reset\0";
-pub static LV2_MIDI__SONGPOSITION: &[u8] = b"http://lv2plug.in/ns/ext/midi#SongPosition\0";
-pub static LV2_MIDI__SONGSELECT: &[u8] = b"http://lv2plug.in/ns/ext/midi#SongSelect\0";
-pub static LV2_MIDI__START: &[u8] = b"http://lv2plug.in/ns/ext/midi#Start\0";
-pub static LV2_MIDI__STOP: &[u8] = b"http://lv2plug.in/ns/ext/midi#Stop\0";
-pub static LV2_MIDI__SYSTEMCOMMON: &[u8] = b"http://lv2plug.in/ns/ext/midi#SystemCommon\0";
-pub static LV2_MIDI__SYSTEMEXCLUSIVE: &[u8] = b"http://lv2plug.in/ns/ext/midi#SystemExclusive\0";
-pub static LV2_MIDI__SYSTEMMESSAGE: &[u8] = b"http://lv2plug.in/ns/ext/midi#SystemMessage\0";
-pub static LV2_MIDI__SYSTEMREALTIME: &[u8] = b"http://lv2plug.in/ns/ext/midi#SystemRealtime\0";
-pub static LV2_MIDI__TICK: &[u8] = b"http://lv2plug.in/ns/ext/midi#Tick\0";
-pub static LV2_MIDI__TUNEREQUEST: &[u8] = b"http://lv2plug.in/ns/ext/midi#TuneRequest\0";
-pub static LV2_MIDI__VOICEMESSAGE: &[u8] = b"http://lv2plug.in/ns/ext/midi#VoiceMessage\0";
-pub static LV2_MIDI__BENDERVALUE: &[u8] = b"http://lv2plug.in/ns/ext/midi#benderValue\0";
-pub static LV2_MIDI__BINDING: &[u8] = b"http://lv2plug.in/ns/ext/midi#binding\0";
-pub static LV2_MIDI__BYTENUMBER: &[u8] = b"http://lv2plug.in/ns/ext/midi#byteNumber\0";
-pub static LV2_MIDI__CHANNEL: &[u8] = b"http://lv2plug.in/ns/ext/midi#channel\0";
-pub static LV2_MIDI___CHUNK: &[u8] = b"http://lv2plug.in/ns/ext/midi#chunk\0";
-pub static LV2_MIDI__CONTROLLERNUMBER: &[u8] = b"http://lv2plug.in/ns/ext/midi#controllerNumber\0";
-pub static LV2_MIDI__CONTROLLERVALUE: &[u8] = b"http://lv2plug.in/ns/ext/midi#controllerValue\0";
-pub static LV2_MIDI__NOTENUMBER: &[u8] = b"http://lv2plug.in/ns/ext/midi#noteNumber\0";
-pub static LV2_MIDI__PRESSURE: &[u8] = b"http://lv2plug.in/ns/ext/midi#pressure\0";
-pub static LV2_MIDI__PROGRAMNUMBER: &[u8] = b"http://lv2plug.in/ns/ext/midi#programNumber\0";
-pub static LV2_MIDI__PROPERTY: &[u8] = b"http://lv2plug.in/ns/ext/midi#property\0";
-pub static LV2_MIDI__SONGNUMBER: &[u8] = b"http://lv2plug.in/ns/ext/midi#songNumber\0";
-pub static LV2_MIDI___SONGPOSITION: &[u8] = b"http://lv2plug.in/ns/ext/midi#songPosition\0";
-pub static LV2_MIDI__STATUS: &[u8] = b"http://lv2plug.in/ns/ext/midi#status\0";
-pub static LV2_MIDI__STATUSMASK: &[u8] = b"http://lv2plug.in/ns/ext/midi#statusMask\0";
-pub static LV2_MIDI__VELOCITY: &[u8] = b"http://lv2plug.in/ns/ext/midi#velocity\0";
+pub const LV2_MIDI_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi");
+pub const LV2_MIDI_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#");
+
+pub const LV2_MIDI__ACTIVESENSE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#ActiveSense");
+pub const LV2_MIDI__AFTERTOUCH: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Aftertouch");
+pub const LV2_MIDI__BENDER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Bender");
+pub const LV2_MIDI__CHANNELPRESSURE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/midi#ChannelPressure");
+pub const LV2_MIDI__CHUNK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Chunk");
+pub const LV2_MIDI__CLOCK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Clock");
+pub const LV2_MIDI__CONTINUE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Continue");
+pub const LV2_MIDI__CONTROLLER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Controller");
+pub const LV2_MIDI__MIDIEVENT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#MidiEvent");
+pub const LV2_MIDI__NOTEOFF: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#NoteOff");
+pub const LV2_MIDI__NOTEON: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#NoteOn");
+pub const LV2_MIDI__PROGRAMCHANGE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#ProgramChange");
+pub const LV2_MIDI__QUARTERFRAME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#QuarterFrame");
+pub const LV2_MIDI__RESET: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Reset");
+pub const LV2_MIDI__SONGPOSITION: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#SongPosition");
+pub const LV2_MIDI__SONGSELECT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#SongSelect");
+pub const LV2_MIDI__START: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Start");
+pub const LV2_MIDI__STOP: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Stop");
+pub const LV2_MIDI__SYSTEMCOMMON: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#SystemCommon");
+pub const LV2_MIDI__SYSTEMEXCLUSIVE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/midi#SystemExclusive");
+pub const LV2_MIDI__SYSTEMMESSAGE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#SystemMessage");
+pub const LV2_MIDI__SYSTEMREALTIME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#SystemRealtime");
+pub const LV2_MIDI__TICK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#Tick");
+pub const LV2_MIDI__TUNEREQUEST: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#TuneRequest");
+pub const LV2_MIDI__VOICEMESSAGE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#VoiceMessage");
+pub const LV2_MIDI__BENDERVALUE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#benderValue");
+pub const LV2_MIDI__BINDING: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#binding");
+pub const LV2_MIDI__BYTENUMBER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#byteNumber");
+pub const LV2_MIDI__CHANNEL: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#channel");
+pub const LV2_MIDI___CHUNK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#chunk");
+pub const LV2_MIDI__CONTROLLERNUMBER: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/midi#controllerNumber");
+pub const LV2_MIDI__CONTROLLERVALUE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/midi#controllerValue");
+pub const LV2_MIDI__NOTENUMBER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#noteNumber");
+pub const LV2_MIDI__PRESSURE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#pressure");
+pub const LV2_MIDI__PROGRAMNUMBER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#programNumber");
+pub const LV2_MIDI__PROPERTY: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#property");
+pub const LV2_MIDI__SONGNUMBER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#songNumber");
+pub const LV2_MIDI___SONGPOSITION: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#songPosition");
+pub const LV2_MIDI__STATUS: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#status");
+pub const LV2_MIDI__STATUSMASK: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#statusMask");
+pub const LV2_MIDI__VELOCITY: Uri = Uri::new(c"http://lv2plug.in/ns/ext/midi#velocity");
 
 /**
    MIDI Message Type.
@@ -0,0 +1,182 @@
+// Documentation copied from http://lv2plug.in/ns/ext/options/options.h
+
+// Copyright text of the original C file:
+
+// Copyright 2012-2016 David Robillard <http://drobilla.net>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/options/options.html>.
+
+use core::ffi::*;
+
+use crate::core::LV2Handle;
+use crate::coreutils::FeatureData;
+use crate::uri::Uri;
+use crate::urid::LV2Urid;
+
+pub const LV2_OPTIONS_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/options");
+pub const LV2_OPTIONS_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/options#");
+
+pub const LV2_OPTIONS__OPTION: Uri = Uri::new(c"http://lv2plug.in/ns/ext/options#Option");
+pub const LV2_OPTIONS__INTERFACE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/options#interface");
+pub const LV2_OPTIONS__OPTIONS: Uri = Uri::new(c"http://lv2plug.in/ns/ext/options#options");
+pub const LV2_OPTIONS__REQUIREDOPTION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/options#requiredOption");
+pub const LV2_OPTIONS__SUPPORTEDOPTION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/options#supportedOption");
+
+/**
+   The context of an Option, which defines the subject it applies to.
+*/
+pub type LV2OptionsContext = u32;
+
+/**
+   This option applies to the instance itself.  The subject must be
+   ignored.
+*/
+pub const LV2_OPTIONS_INSTANCE: LV2OptionsContext = 0;
+
+/**
+   This option applies to some named resource.  The subject is a URI mapped
+   to an integer (a LV2_URID, like the key)
+*/
+pub const LV2_OPTIONS_RESOURCE: LV2OptionsContext = 1;
+
+/**
+   This option applies to some blank node.  The subject is a blank node
+   identifier, which is valid only within the current local scope.
+*/
+pub const LV2_OPTIONS_BLANK: LV2OptionsContext = 2;
+
+/**
+   This option applies to a port on the instance.  The subject is the
+   port's index.
+*/
+pub const LV2_OPTIONS_PORT: LV2OptionsContext = 3;
+
+/**
+   An option.
+
+   This is a property with a subject, also known as a triple or statement.
+
+   This struct is useful anywhere a statement needs to be passed where no
+   memory ownership issues are present (since the value is a const pointer).
+
+   Options can be passed to an instance via the feature LV2_OPTIONS__options
+   with data pointed to an array of options terminated by a zeroed option, or
+   accessed/manipulated using LV2_Options_Interface.
+*/
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct LV2OptionsOption {
+    /// Context (type of subject).
+    pub context: LV2OptionsContext,
+    /// Subject.
+    pub subject: u32,
+    /// Key (property).
+    pub key: LV2Urid,
+    /// Size of value in bytes.
+    pub size: u32,
+    /// Type of value (datatype).
+    pub type_: LV2Urid,
+    /// Pointer to value (object).
+    pub value: *const c_void,
+}
+
+impl LV2OptionsOption {
+    /// The zeroed option which terminates an options array.
+    pub const END: LV2OptionsOption = LV2OptionsOption {
+        context: LV2_OPTIONS_INSTANCE,
+        subject: 0,
+        key: 0,
+        size: 0,
+        type_: 0,
+        value: core::ptr::null(),
+    };
+
+    /// Return true iff this is the zeroed option which ends an array.
+    #[inline]
+    pub fn is_end(&self) -> bool {
+        self.key == 0 && self.value.is_null()
+    }
+
+    /// Iterate over an options array, starting at this option.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be an element of an array terminated by a zeroed option,
+    /// such as the data of the `options#options` feature.
+    pub unsafe fn iter_array(&self) -> impl Iterator<Item = &LV2OptionsOption> {
+        let mut next = self as *const LV2OptionsOption;
+        core::iter::from_fn(move || unsafe {
+            let option = &*next;
+            if option.is_end() {
+                None
+            } else {
+                next = next.add(1);
+                Some(option)
+            }
+        })
+    }
+}
+
+/// The data of the `options#options` feature is the first element of an
+/// options array, see [`LV2OptionsOption::iter_array`].
+unsafe impl FeatureData for LV2OptionsOption {
+    const URI: Uri = LV2_OPTIONS__OPTIONS;
+}
+
+/** A status code for option functions. */
+pub type LV2OptionsStatus = u32;
+
+/// Completed successfully.
+pub const LV2_OPTIONS_SUCCESS: LV2OptionsStatus = 0;
+/// Unknown error.
+pub const LV2_OPTIONS_ERR_UNKNOWN: LV2OptionsStatus = 1;
+/// Invalid/unsupported subject.
+pub const LV2_OPTIONS_ERR_BAD_SUBJECT: LV2OptionsStatus = 1 << 1;
+/// Invalid/unsupported key.
+pub const LV2_OPTIONS_ERR_BAD_KEY: LV2OptionsStatus = 1 << 2;
+/// Invalid/unsupported value.
+pub const LV2_OPTIONS_ERR_BAD_VALUE: LV2OptionsStatus = 1 << 3;
+
+/**
+   Interface for dynamically setting options (LV2_OPTIONS__interface).
+*/
+#[repr(C)]
+pub struct LV2OptionsInterface {
+    /**
+       Get the given options.
+
+       Each element of the passed options array MUST have type, subject, and
+       key set.  All other fields (size, type, value) MUST be initialised to
+       zero, and are set to the option value if such an option is found.
+
+       This function is in the "instantiation" LV2 threading class, so no other
+       instance functions may be called concurrently.
+
+       @return Bitwise OR of LV2_Options_Status values.
+    */
+    pub get: extern "C" fn(instance: LV2Handle, options: *mut LV2OptionsOption) -> u32,
+
+    /**
+       Set the given options.
+
+       This function is in the "instantiation" LV2 threading class, so no other
+       instance functions may be called concurrently.
+
+       @return Bitwise OR of LV2_Options_Status values.
+    */
+    pub set: extern "C" fn(instance: LV2Handle, options: *const LV2OptionsOption) -> u32,
+}
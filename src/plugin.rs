@@ -0,0 +1,397 @@
+//! A safe trait for writing plugins, and the glue that exports them.
+//!
+//! Implement [`Plugin`] for each plugin type, then export all of them from
+//! the library with [`lv2_descriptors!`](crate::lv2_descriptors):
+//!
+//! ```
+//! use lv2_raw::*;
+//! use std::ffi::CStr;
+//! use std::os::raw::c_void;
+//!
+//! struct Amp {
+//!     gain: *const f32,
+//!     input: *const f32,
+//!     output: *mut f32,
+//! }
+//!
+//! impl Plugin for Amp {
+//!     const URI: Uri = Uri::new(c"urn:example:amp");
+//!     const PORTS: &'static [(PortType, PortDirection)] = &[
+//!         (PortType::Control, PortDirection::Input),
+//!         (PortType::Audio, PortDirection::Input),
+//!         (PortType::Audio, PortDirection::Output),
+//!     ];
+//!
+//!     fn instantiate(_rate: f64, _bundle_path: &CStr, _features: Features<'_>) -> Option<Self> {
+//!         Some(Amp {
+//!             gain: std::ptr::null(),
+//!             input: std::ptr::null(),
+//!             output: std::ptr::null_mut(),
+//!         })
+//!     }
+//!
+//!     fn connect_port(&mut self, port: u32, data: *mut c_void) {
+//!         match port {
+//!             0 => self.gain = data as *const f32,
+//!             1 => self.input = data as *const f32,
+//!             2 => self.output = data as *mut f32,
+//!             _ => {}
+//!         }
+//!     }
+//!
+//!     fn run(&mut self, sample_count: u32) {
+//!         let n = sample_count as usize;
+//!         if n == 0 {
+//!             return;
+//!         }
+//!         unsafe {
+//!             let gain = *self.gain;
+//!             let input = std::slice::from_raw_parts(self.input, n);
+//!             let output = std::slice::from_raw_parts_mut(self.output, n);
+//!             for (o, i) in output.iter_mut().zip(input) {
+//!                 *o = i * gain;
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! lv2_raw::lv2_descriptors!(Amp);
+//! # fn main() {}
+//! ```
+
+use crate::atom::{LV2_ATOM__SEQUENCE, LV2Atom, LV2AtomSequence, LV2AtomSequenceBody};
+use crate::core::{LV2Descriptor, LV2Feature, LV2Handle, PortDirection, PortType};
+use crate::coreutils::Features;
+use crate::log::{LV2_LOG__ERROR, LV2LogLog};
+use crate::uri::Uri;
+use crate::urid::{LV2Urid, LV2UridMap};
+use alloc::boxed::Box;
+use alloc::ffi::CString;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use core::any::Any;
+use core::ffi::{CStr, c_char, c_void};
+use core::ptr;
+
+/// A plugin, as seen from the inside.
+///
+/// The methods correspond to the functions in [`LV2Descriptor`], and are
+/// called in the order documented there: `instantiate()`, then any number of
+/// `connect_port()` calls, then zero or more `activate()`, `run()`...,
+/// `deactivate()` cycles, and finally the instance is dropped when the host
+/// calls `cleanup()`.
+pub trait Plugin: Sized + 'static {
+    /// A globally unique, case-sensitive identifier for this plugin.
+    const URI: Uri;
+
+    /// The type and direction of each port, in index order.
+    ///
+    /// This is used to silence the outputs of an instance that panicked
+    /// (see [`plugin_descriptor`]), so it must list every port.  Ports past
+    /// the end of the list are left alone.
+    const PORTS: &'static [(PortType, PortDirection)];
+
+    /// Create a new plugin instance.
+    ///
+    /// `bundle_path` includes the trailing directory separator.  Return
+    /// `None` if instantiation failed, e.g. because a required feature is
+    /// missing.  Initialisation that depends on the history of the instance
+    /// belongs in [`activate`](Plugin::activate) instead.
+    fn instantiate(sample_rate: f64, bundle_path: &CStr, features: Features<'_>) -> Option<Self>;
+
+    /// Connect a port to a memory location.
+    ///
+    /// `data` is NULL for an lv2:connectionOptional port the host left
+    /// unconnected.  This may be called at any time, and any number of
+    /// times, between `instantiate()` and `cleanup()`.  The same buffer may
+    /// be connected to several ports, including an input and an output
+    /// (unless the plugin is lv2:inPlaceBroken).
+    fn connect_port(&mut self, port: u32, data: *mut c_void);
+
+    /// Reset all state that depends on the history of the instance.
+    ///
+    /// Called once before the first `run()`, and again after every
+    /// `deactivate()` before `run()` is called again.
+    fn activate(&mut self) {}
+
+    /// Process a block of `sample_count` frames.
+    ///
+    /// `sample_count` may be 0, in which case only output ports that
+    /// represent a single instant in time (e.g. control ports) should be
+    /// updated.  This MUST NOT crash.
+    fn run(&mut self, sample_count: u32);
+
+    /// Counterpart to `activate()`.
+    ///
+    /// This must not fully reset the plugin's state, the host may save it
+    /// after deactivating.
+    fn deactivate(&mut self) {}
+
+    /// Return extension data for `uri`, or NULL if it is not supported.
+    fn extension_data(uri: &CStr) -> *const c_void {
+        let _ = uri;
+        ptr::null()
+    }
+}
+
+/// Return the descriptor for a plugin type.
+///
+/// The descriptor's functions forward to the [`Plugin`] implementation.  The
+/// instance handle owns a boxed `P`, which is dropped by `cleanup()`.  This
+/// is what [`lv2_descriptors!`](crate::lv2_descriptors) puts in the exported
+/// table.
+///
+/// A panic must not unwind into the host, so every call into the plugin is
+/// wrapped in [`catch_unwind`](std::panic::catch_unwind):
+///
+/// - if `instantiate()` panics, the host gets NULL;
+/// - if any other method panics, the instance is faulted: the plugin is
+///   not called again, `run()` writes silence to audio and CV outputs and
+///   an empty sequence to atom outputs (as listed in [`Plugin::PORTS`]),
+///   and `cleanup()` only drops the plugin;
+/// - if `extension_data()` panics, the host gets NULL.
+///
+/// The panic is reported with `log:Error` if the host provides `log:log`
+/// and `urid:map`.  None of this works if the plugin is built with
+/// `panic = "abort"`, or without the `std` feature, where panics are not
+/// caught.
+#[must_use]
+pub const fn plugin_descriptor<P: Plugin>() -> LV2Descriptor {
+    LV2Descriptor {
+        uri: P::URI.as_ptr(),
+        instantiate: instantiate::<P>,
+        connect_port: connect_port::<P>,
+        activate: Some(activate::<P>),
+        run: run::<P>,
+        deactivate: Some(deactivate::<P>),
+        cleanup: cleanup::<P>,
+        extension_data: Some(extension_data::<P>),
+    }
+}
+
+/// Reports panics through the host's log feature.
+#[derive(Clone, Copy)]
+pub(crate) struct PanicLog {
+    log: LV2LogLog,
+    error: LV2Urid,
+}
+
+impl PanicLog {
+    /// Return a log if `features` has `log:log` and `urid:map`.
+    pub(crate) fn new(features: Features<'_>) -> Option<Self> {
+        let log = features.get::<LV2LogLog>()?;
+        let map = features.get::<LV2UridMap>()?;
+        Some(PanicLog {
+            log: *log,
+            error: map.map(LV2_LOG__ERROR),
+        })
+    }
+
+    /// Log that `callback` of `uri` panicked with `payload`.
+    pub(crate) fn panicked(log: Option<Self>, uri: Uri, callback: &str, payload: &dyn Any) {
+        let Some(log) = log else {
+            return;
+        };
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic payload");
+        let text = format!(
+            "{}: panic in {callback}(): {message}\n",
+            uri.as_cstr().to_string_lossy()
+        );
+        let text = CString::new(text.replace('\0', "\\0")).unwrap_or_default();
+        unsafe { (log.log.printf)(log.log.handle, log.error, c"%s".as_ptr(), text.as_ptr()) };
+    }
+}
+
+/// Call `f`, and return the payload if it panicked.
+///
+/// Without `std` panics can not be caught, so `f` is just called.
+pub(crate) fn catch_unwind<R>(f: impl FnOnce() -> R) -> Result<R, Box<dyn Any + Send>> {
+    #[cfg(feature = "std")]
+    return std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    #[cfg(not(feature = "std"))]
+    return Ok(f());
+}
+
+/// The instance behind a handle.
+struct Wrapper<P> {
+    plugin: P,
+    /// Set once a method panicked, after which the plugin is not called.
+    faulted: bool,
+    /// The buffers connected to the ports in [`Plugin::PORTS`].
+    buffers: Box<[*mut c_void]>,
+    /// The URID of `atom:Sequence`, or 0 without `urid:map`.
+    sequence: LV2Urid,
+    log: Option<PanicLog>,
+}
+
+impl<P: Plugin> Wrapper<P> {
+    /// Call `f` on the plugin unless the instance is faulted.  Return false
+    /// if it is, or if `f` panicked.
+    fn call(&mut self, callback: &str, f: impl FnOnce(&mut P)) -> bool {
+        if self.faulted {
+            return false;
+        }
+        let plugin = &mut self.plugin;
+        match catch_unwind(|| f(plugin)) {
+            Ok(()) => true,
+            Err(payload) => {
+                self.faulted = true;
+                PanicLog::panicked(self.log, P::URI, callback, &*payload);
+                false
+            }
+        }
+    }
+
+    /// Write silence to the audio and CV outputs, and empty sequences to
+    /// the atom outputs.
+    fn silence(&mut self, sample_count: u32) {
+        for (&(port_type, direction), &data) in P::PORTS.iter().zip(&*self.buffers) {
+            if direction != PortDirection::Output || data.is_null() {
+                continue;
+            }
+            match port_type {
+                PortType::Audio | PortType::Cv => unsafe {
+                    ptr::write_bytes(data as *mut f32, 0, sample_count as usize);
+                },
+                PortType::Atom if self.sequence != 0 => unsafe {
+                    *(data as *mut LV2AtomSequence) = LV2AtomSequence {
+                        atom: LV2Atom {
+                            size: size_of::<LV2AtomSequenceBody>() as u32,
+                            type_: self.sequence,
+                        },
+                        body: LV2AtomSequenceBody { unit: 0, pad: 0 },
+                    };
+                },
+                PortType::Atom => unsafe {
+                    // An empty atom, for hosts without urid:map.
+                    (*(data as *mut LV2Atom)).size = 0;
+                },
+                PortType::Control => {}
+            }
+        }
+    }
+}
+
+/// Recover the instance from a handle.
+///
+/// # Safety
+///
+/// `instance` must be NULL or a handle returned by `instantiate::<P>` which
+/// has not been cleaned up yet.
+unsafe fn instance<'a, P: Plugin>(instance: LV2Handle) -> Option<&'a mut Wrapper<P>> {
+    unsafe { (instance as *mut Wrapper<P>).as_mut() }
+}
+
+extern "C" fn instantiate<P: Plugin>(
+    _descriptor: *const LV2Descriptor,
+    rate: f64,
+    bundle_path: *const c_char,
+    features: *const *const LV2Feature,
+) -> LV2Handle {
+    let bundle_path = if bundle_path.is_null() {
+        c""
+    } else {
+        unsafe { CStr::from_ptr(bundle_path) }
+    };
+    let features = unsafe { Features::from_raw(features) };
+    let log = PanicLog::new(features);
+
+    match catch_unwind(|| P::instantiate(rate, bundle_path, features)) {
+        Ok(Some(plugin)) => Box::into_raw(Box::new(Wrapper {
+            plugin,
+            faulted: false,
+            buffers: vec![ptr::null_mut(); P::PORTS.len()].into_boxed_slice(),
+            sequence: features
+                .get::<LV2UridMap>()
+                .map_or(0, |map| map.map(LV2_ATOM__SEQUENCE)),
+            log,
+        })) as LV2Handle,
+        Ok(None) => ptr::null_mut(),
+        Err(payload) => {
+            PanicLog::panicked(log, P::URI, "instantiate", &*payload);
+            ptr::null_mut()
+        }
+    }
+}
+
+extern "C" fn connect_port<P: Plugin>(handle: LV2Handle, port: u32, data: *mut c_void) {
+    if let Some(wrapper) = unsafe { instance::<P>(handle) } {
+        if let Some(buffer) = wrapper.buffers.get_mut(port as usize) {
+            *buffer = data;
+        }
+        wrapper.call("connect_port", |plugin| plugin.connect_port(port, data));
+    }
+}
+
+extern "C" fn activate<P: Plugin>(handle: LV2Handle) {
+    if let Some(wrapper) = unsafe { instance::<P>(handle) } {
+        wrapper.call("activate", P::activate);
+    }
+}
+
+extern "C" fn run<P: Plugin>(handle: LV2Handle, sample_count: u32) {
+    if let Some(wrapper) = unsafe { instance::<P>(handle) }
+        && !wrapper.call("run", |plugin| plugin.run(sample_count))
+    {
+        wrapper.silence(sample_count);
+    }
+}
+
+extern "C" fn deactivate<P: Plugin>(handle: LV2Handle) {
+    if let Some(wrapper) = unsafe { instance::<P>(handle) } {
+        wrapper.call("deactivate", P::deactivate);
+    }
+}
+
+extern "C" fn cleanup<P: Plugin>(handle: LV2Handle) {
+    if handle.is_null() {
+        return;
+    }
+    let wrapper = unsafe { Box::from_raw(handle as *mut Wrapper<P>) };
+    let log = wrapper.log;
+    if let Err(payload) = catch_unwind(|| drop(wrapper)) {
+        PanicLog::panicked(log, P::URI, "cleanup", &*payload);
+    }
+}
+
+extern "C" fn extension_data<P: Plugin>(uri: *const c_char) -> *const c_void {
+    if uri.is_null() {
+        return ptr::null();
+    }
+    let uri = unsafe { CStr::from_ptr(uri) };
+    catch_unwind(|| P::extension_data(uri)).unwrap_or(ptr::null())
+}
+
+/// Export plugins from a library.
+///
+/// This defines the `lv2_descriptor()` function which hosts look up in the
+/// plugin binary.  It returns the descriptor of each listed plugin type, in
+/// order, and NULL past the end of the list.  The descriptors are statics, so
+/// they are valid for as long as the library is loaded.
+///
+/// ```ignore
+/// lv2_raw::lv2_descriptors!(Amp, Fuzz, Delay);
+/// ```
+///
+/// Use this macro once per library, in a crate built as a `cdylib`.
+#[macro_export]
+macro_rules! lv2_descriptors {
+    ($($plugin:ty),+ $(,)?) => {
+        /// Return the descriptor of plugin number `index` in this library.
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lv2_descriptor(index: u32) -> *const $crate::core::LV2Descriptor {
+            static DESCRIPTORS: &[$crate::core::LV2Descriptor] =
+                &[$($crate::plugin::plugin_descriptor::<$plugin>()),+];
+
+            match DESCRIPTORS.get(index as usize) {
+                ::core::option::Option::Some(descriptor) => descriptor,
+                ::core::option::Option::None => ::core::ptr::null(),
+            }
+        }
+    };
+}
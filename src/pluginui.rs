@@ -0,0 +1,282 @@
+//! A safe trait for writing plugin UIs, and the glue that exports them.
+//!
+//! Implement [`PluginUi`] for each UI type, then export all of them from the
+//! UI library with [`lv2ui_descriptors!`](crate::lv2ui_descriptors):
+//!
+//! ```
+//! use lv2_raw::*;
+//! use std::ffi::CStr;
+//! use std::os::raw::c_void;
+//!
+//! struct AmpUi {
+//!     controller: UiController,
+//! }
+//!
+//! impl PluginUi for AmpUi {
+//!     const URI: Uri = Uri::new(c"urn:example:amp#ui");
+//!
+//!     fn instantiate(
+//!         _plugin_uri: &CStr,
+//!         _bundle_path: &CStr,
+//!         controller: UiController,
+//!         _features: Features<'_>,
+//!     ) -> Option<(Self, LV2UIWidget)> {
+//!         // Create the widget here.
+//!         Some((AmpUi { controller }, std::ptr::null_mut()))
+//!     }
+//!
+//!     fn port_event(&mut self, port: u32, _size: u32, format: u32, buffer: *const c_void) {
+//!         if port == 0 && format == 0 {
+//!             let gain = unsafe { *(buffer as *const f32) };
+//!             // Show the gain, and set it back to unity.
+//!             if gain != 1.0 {
+//!                 self.controller.write_control(0, 1.0);
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! lv2_raw::lv2ui_descriptors!(AmpUi);
+//! # fn main() {}
+//! ```
+
+use crate::core::LV2Feature;
+use crate::coreutils::Features;
+use crate::plugin::{PanicLog, catch_unwind};
+use crate::ui::{
+    LV2UIControllerRaw, LV2UIDescriptorRaw, LV2UIHandle, LV2UIWidget, LV2UIWriteFunctionRaw,
+};
+use crate::uri::Uri;
+use alloc::boxed::Box;
+use core::ffi::{CStr, c_char, c_uint, c_void};
+use core::ptr;
+
+/// The host's side of a UI: sends data to the plugin's input ports.
+#[derive(Debug, Clone, Copy)]
+pub struct UiController {
+    write_function: LV2UIWriteFunctionRaw,
+    controller: LV2UIControllerRaw,
+}
+
+impl UiController {
+    /// Send `buffer_size` bytes at `buffer` to port `port_index`, in the
+    /// format of `port_protocol` (0 for `ui:floatProtocol`).
+    ///
+    /// # Safety
+    ///
+    /// `buffer` must point to `buffer_size` readable bytes, in the format
+    /// `port_protocol` defines.
+    pub unsafe fn write(
+        &self,
+        port_index: u32,
+        buffer_size: u32,
+        port_protocol: u32,
+        buffer: *const c_void,
+    ) {
+        if let Some(write) = self.write_function {
+            write(
+                self.controller,
+                port_index,
+                buffer_size,
+                port_protocol,
+                buffer,
+            );
+        }
+    }
+
+    /// Set the value of control input `port_index`.
+    pub fn write_control(&self, port_index: u32, value: f32) {
+        unsafe {
+            self.write(
+                port_index,
+                size_of::<f32>() as u32,
+                0,
+                &value as *const f32 as *const c_void,
+            )
+        };
+    }
+}
+
+/// A plugin UI, as seen from the inside.
+///
+/// The methods correspond to the functions in [`LV2UIDescriptorRaw`].  The
+/// UI is dropped when the host calls `cleanup()`.
+pub trait PluginUi: Sized + 'static {
+    /// A globally unique identifier for this UI (not for its plugin).
+    const URI: Uri;
+
+    /// Create a new UI for the plugin `plugin_uri`, and return it with its
+    /// widget, whose type is defined by the UI class in the data file.
+    ///
+    /// `bundle_path` includes the trailing directory separator.  Return
+    /// `None` if instantiation failed.
+    fn instantiate(
+        plugin_uri: &CStr,
+        bundle_path: &CStr,
+        controller: UiController,
+        features: Features<'_>,
+    ) -> Option<(Self, LV2UIWidget)>;
+
+    /// Show that something happened at a plugin port.
+    ///
+    /// For control ports `format` is 0 and `buffer` points to a single
+    /// `f32`.  `buffer` is only valid for the duration of the call.
+    fn port_event(
+        &mut self,
+        port_index: u32,
+        buffer_size: u32,
+        format: u32,
+        buffer: *const c_void,
+    ) {
+        let _ = (port_index, buffer_size, format, buffer);
+    }
+
+    /// Return extension data for `uri`, or NULL if it is not supported.
+    fn extension_data(uri: &CStr) -> *const c_void {
+        let _ = uri;
+        ptr::null()
+    }
+}
+
+/// Return the descriptor for a UI type.
+///
+/// The descriptor's functions forward to the [`PluginUi`] implementation.
+/// This is what [`lv2ui_descriptors!`](crate::lv2ui_descriptors) puts in the
+/// exported table.
+///
+/// As with [`plugin_descriptor`](crate::plugin_descriptor), every call is
+/// wrapped in [`catch_unwind`](std::panic::catch_unwind): a panic in
+/// `instantiate()` or `extension_data()` returns NULL, and after a panic in
+/// `port_event()` the UI gets no more events.  Panics are reported with
+/// `log:Error` if the host provides `log:log` and `urid:map`.  Functions of
+/// extension data, such as the idle interface, are not wrapped, and without
+/// the `std` feature panics are not caught at all.
+#[must_use]
+pub const fn ui_descriptor<U: PluginUi>() -> LV2UIDescriptorRaw {
+    LV2UIDescriptorRaw {
+        uri: U::URI.as_ptr(),
+        instantiate_raw: instantiate::<U>,
+        cleanup: cleanup::<U>,
+        port_event: Some(port_event::<U>),
+        extension_data: Some(extension_data::<U>),
+    }
+}
+
+/// The UI behind a handle.
+struct Wrapper<U> {
+    ui: U,
+    /// Set once a method panicked, after which the UI is not called.
+    faulted: bool,
+    log: Option<PanicLog>,
+}
+
+extern "C" fn instantiate<U: PluginUi>(
+    _descriptor: *const LV2UIDescriptorRaw,
+    plugin_uri: *const c_char,
+    bundle_path: *const c_char,
+    write_function: LV2UIWriteFunctionRaw,
+    controller: LV2UIControllerRaw,
+    widget: *mut LV2UIWidget,
+    features: *const *const LV2Feature,
+) -> LV2UIHandle {
+    let string = |s: *const c_char| {
+        if s.is_null() {
+            c""
+        } else {
+            unsafe { CStr::from_ptr(s) }
+        }
+    };
+    let (plugin_uri, bundle_path) = (string(plugin_uri), string(bundle_path));
+    let features = unsafe { Features::from_raw(features) };
+    let log = PanicLog::new(features);
+    let controller = UiController {
+        write_function,
+        controller,
+    };
+
+    match catch_unwind(|| U::instantiate(plugin_uri, bundle_path, controller, features)) {
+        Ok(Some((ui, ui_widget))) => {
+            if !widget.is_null() {
+                unsafe { *widget = ui_widget };
+            }
+            Box::into_raw(Box::new(Wrapper {
+                ui,
+                faulted: false,
+                log,
+            })) as LV2UIHandle
+        }
+        Ok(None) => ptr::null_mut(),
+        Err(payload) => {
+            PanicLog::panicked(log, U::URI, "instantiate", &*payload);
+            ptr::null_mut()
+        }
+    }
+}
+
+extern "C" fn cleanup<U: PluginUi>(handle: LV2UIHandle) {
+    if handle.is_null() {
+        return;
+    }
+    let wrapper = unsafe { Box::from_raw(handle as *mut Wrapper<U>) };
+    let log = wrapper.log;
+    if let Err(payload) = catch_unwind(|| drop(wrapper)) {
+        PanicLog::panicked(log, U::URI, "cleanup", &*payload);
+    }
+}
+
+extern "C" fn port_event<U: PluginUi>(
+    handle: LV2UIHandle,
+    port_index: c_uint,
+    buffer_size: c_uint,
+    format: c_uint,
+    buffer: *const c_void,
+) {
+    let Some(wrapper) = (unsafe { (handle as *mut Wrapper<U>).as_mut() }) else {
+        return;
+    };
+    if wrapper.faulted {
+        return;
+    }
+    let ui = &mut wrapper.ui;
+    let result = catch_unwind(|| ui.port_event(port_index, buffer_size, format, buffer));
+    if let Err(payload) = result {
+        wrapper.faulted = true;
+        PanicLog::panicked(wrapper.log, U::URI, "port_event", &*payload);
+    }
+}
+
+extern "C" fn extension_data<U: PluginUi>(uri: *const c_char) -> *const c_void {
+    if uri.is_null() {
+        return ptr::null();
+    }
+    let uri = unsafe { CStr::from_ptr(uri) };
+    catch_unwind(|| U::extension_data(uri)).unwrap_or(ptr::null())
+}
+
+/// Export plugin UIs from a library.
+///
+/// This defines the `lv2ui_descriptor()` function which hosts look up in the
+/// UI binary.  It returns the descriptor of each listed UI type, in order,
+/// and NULL past the end of the list.
+///
+/// ```ignore
+/// lv2_raw::lv2ui_descriptors!(AmpUi, DelayUi);
+/// ```
+///
+/// Use this macro once per library, in a crate built as a `cdylib`.
+#[macro_export]
+macro_rules! lv2ui_descriptors {
+    ($($ui:ty),+ $(,)?) => {
+        /// Return the descriptor of UI number `index` in this library.
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lv2ui_descriptor(index: u32) -> *const $crate::ui::LV2UIDescriptorRaw {
+            static DESCRIPTORS: &[$crate::ui::LV2UIDescriptorRaw] =
+                &[$($crate::pluginui::ui_descriptor::<$ui>()),+];
+
+            match DESCRIPTORS.get(index as usize) {
+                ::core::option::Option::Some(descriptor) => descriptor,
+                ::core::option::Option::None => ::core::ptr::null(),
+            }
+        }
+    };
+}
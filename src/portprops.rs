@@ -0,0 +1,53 @@
+// Documentation copied from http://lv2plug.in/ns/ext/port-props/port-props.h
+
+// Copyright text of the original C file:
+
+// Copyright 2012-2016 David Robillard <http://drobilla.net>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/port-props/port-props.html>.
+
+//! Various port properties, see <http://lv2plug.in/ns/ext/port-props> for
+//! details.
+//!
+//! Like lv2core port properties, these are objects of `lv2:portProperty` in
+//! the data of a plugin.  This module only defines their URIs.
+
+use crate::uri::Uri;
+
+pub const LV2_PORT_PROPS_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/port-props");
+pub const LV2_PORT_PROPS_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/port-props#");
+
+pub const LV2_PORT_PROPS__CAUSESARTIFACTS: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#causesArtifacts");
+pub const LV2_PORT_PROPS__CONTINUOUSCV: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#continuousCV");
+pub const LV2_PORT_PROPS__DISCRETECV: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#discreteCV");
+pub const LV2_PORT_PROPS__DISPLAYPRIORITY: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#displayPriority");
+pub const LV2_PORT_PROPS__EXPENSIVE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#expensive");
+pub const LV2_PORT_PROPS__HASSTRICTBOUNDS: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#hasStrictBounds");
+pub const LV2_PORT_PROPS__LOGARITHMIC: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#logarithmic");
+pub const LV2_PORT_PROPS__NOTAUTOMATIC: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#notAutomatic");
+pub const LV2_PORT_PROPS__NOTONGUI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/port-props#notOnGUI");
+pub const LV2_PORT_PROPS__RANGESTEPS: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#rangeSteps");
+pub const LV2_PORT_PROPS__SUPPORTSSTRICTBOUNDS: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/port-props#supportsStrictBounds");
+pub const LV2_PORT_PROPS__TRIGGER: Uri = Uri::new(c"http://lv2plug.in/ns/ext/port-props#trigger");
@@ -0,0 +1,463 @@
+//! Typed wrappers for the port buffers of a plugin.
+//!
+//! A [`Plugin`](crate::Plugin) gets its buffers as raw pointers in
+//! `connect_port()`.  These wrappers keep such a pointer with what the
+//! plugin needs to use it: the default of an input the host left
+//! unconnected, and the length of a block.
+//!
+//! CV ports (`lv2:CVPort`) carry a control signal at audio rate, with the
+//! same range and meaning as a control port.  [`CvSignal`] is what a plugin
+//! reads from a control or CV input, so a parameter can be driven by either:
+//!
+//! ```
+//! use lv2_raw::{ControlInput, CvInput};
+//!
+//! struct Vca {
+//!     gain: ControlInput,
+//!     modulation: CvInput,
+//! }
+//!
+//! impl Vca {
+//!     fn gain_at(&self, sample_count: u32, frame: usize) -> f32 {
+//!         let gain = self.gain.signal();
+//!         let modulation = unsafe { self.modulation.signal(sample_count) };
+//!         gain.get(frame) * modulation.get(frame)
+//!     }
+//! }
+//!
+//! // Unconnected, both ports read as their defaults.
+//! let vca = Vca {
+//!     gain: ControlInput::new(0.5),
+//!     modulation: CvInput::new(1.0),
+//! };
+//! assert_eq!(vca.gain_at(64, 10), 0.5);
+//! ```
+
+use core::ffi::c_void;
+use core::{ptr, slice};
+
+/// The value of a control or CV input over one block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CvSignal<'a> {
+    /// One value per frame, from a connected CV port.
+    Samples(&'a [f32]),
+    /// The same value for every frame, from a control port or an
+    /// unconnected CV port.
+    Constant(f32),
+}
+
+impl CvSignal<'_> {
+    /// Return the value at `frame`.  Frames past the end of the block have
+    /// the last value.
+    pub fn get(&self, frame: usize) -> f32 {
+        match *self {
+            CvSignal::Samples(samples) => match samples.get(frame) {
+                Some(&value) => value,
+                None => samples.last().copied().unwrap_or(0.0),
+            },
+            CvSignal::Constant(value) => value,
+        }
+    }
+
+    /// Return the value at the start of the block, for a parameter which is
+    /// only updated once per block.
+    pub fn value(&self) -> f32 {
+        self.get(0)
+    }
+
+    /// Return true iff the value is the same for every frame.
+    pub fn is_constant(&self) -> bool {
+        match self {
+            CvSignal::Samples(samples) => samples.windows(2).all(|w| w[0] == w[1]),
+            CvSignal::Constant(_) => true,
+        }
+    }
+}
+
+/// A control input.
+#[derive(Debug)]
+pub struct ControlInput {
+    data: *const f32,
+    default: f32,
+}
+
+impl ControlInput {
+    /// Create an unconnected port, which reads as `default`.
+    pub const fn new(default: f32) -> Self {
+        ControlInput {
+            data: ptr::null(),
+            default,
+        }
+    }
+
+    /// Connect the port to the buffer the host passed to `connect_port()`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be NULL or point to an `f32` which stays valid until the
+    /// port is connected again or dropped, since [`value`](Self::value)
+    /// reads it.
+    pub unsafe fn connect(&mut self, data: *mut c_void) {
+        self.data = data as *const f32;
+    }
+
+    /// Return true iff the host connected a buffer.
+    pub fn is_connected(&self) -> bool {
+        !self.data.is_null()
+    }
+
+    /// Return the value of the port, or the default if it is unconnected.
+    pub fn value(&self) -> f32 {
+        if self.data.is_null() {
+            self.default
+        } else {
+            unsafe { *self.data }
+        }
+    }
+
+    /// Return the value as a signal, to use in place of a CV input.
+    pub fn signal(&self) -> CvSignal<'static> {
+        CvSignal::Constant(self.value())
+    }
+}
+
+/// A CV input.
+#[derive(Debug)]
+pub struct CvInput {
+    data: *const f32,
+    default: f32,
+}
+
+impl CvInput {
+    /// Create an unconnected port, which reads as `default` on every frame.
+    pub const fn new(default: f32) -> Self {
+        CvInput {
+            data: ptr::null(),
+            default,
+        }
+    }
+
+    /// Connect the port to the buffer the host passed to `connect_port()`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be NULL or point to a buffer which stays valid until the
+    /// port is connected again or dropped, and which holds as many frames as
+    /// every block passed to [`signal`](Self::signal).
+    pub unsafe fn connect(&mut self, data: *mut c_void) {
+        self.data = data as *const f32;
+    }
+
+    /// Return true iff the host connected a buffer.
+    pub fn is_connected(&self) -> bool {
+        !self.data.is_null()
+    }
+
+    /// Return the signal of a block of `sample_count` frames, or the default
+    /// if the port is unconnected.
+    ///
+    /// # Safety
+    ///
+    /// This must be called in `run(sample_count)`, so the buffer holds
+    /// `sample_count` frames, and the buffer must not be written while the
+    /// signal is in use.  The host may connect an output to the same buffer
+    /// unless the plugin requires `lv2:inPlaceBroken`, so read the signal
+    /// before writing such an output.
+    pub unsafe fn signal(&self, sample_count: u32) -> CvSignal<'_> {
+        if self.data.is_null() {
+            CvSignal::Constant(self.default)
+        } else {
+            CvSignal::Samples(unsafe { slice::from_raw_parts(self.data, sample_count as usize) })
+        }
+    }
+}
+
+/// A CV output.
+#[derive(Debug)]
+pub struct CvOutput {
+    data: *mut f32,
+}
+
+impl CvOutput {
+    /// Create an unconnected port.
+    pub const fn new() -> Self {
+        CvOutput {
+            data: ptr::null_mut(),
+        }
+    }
+
+    /// Connect the port to the buffer the host passed to `connect_port()`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be NULL or point to a buffer which stays valid until the
+    /// port is connected again or dropped, and which holds as many frames as
+    /// every block passed to [`samples`](Self::samples).
+    pub unsafe fn connect(&mut self, data: *mut c_void) {
+        self.data = data as *mut f32;
+    }
+
+    /// Return true iff the host connected a buffer.
+    pub fn is_connected(&self) -> bool {
+        !self.data.is_null()
+    }
+
+    /// Return the buffer of a block of `sample_count` frames, or `None` if
+    /// the port is unconnected.
+    ///
+    /// # Safety
+    ///
+    /// As for [`CvInput::signal`]: this must be called in
+    /// `run(sample_count)`, and no input which shares the buffer may be read
+    /// while the slice is in use.
+    pub unsafe fn samples(&mut self, sample_count: u32) -> Option<&mut [f32]> {
+        if self.data.is_null() {
+            None
+        } else {
+            Some(unsafe { slice::from_raw_parts_mut(self.data, sample_count as usize) })
+        }
+    }
+
+    /// Write `signal` to a block of `sample_count` frames, e.g. a control
+    /// value, which is held for the whole block.
+    ///
+    /// # Safety
+    ///
+    /// See [`samples`](Self::samples).  In particular, `signal` must not be
+    /// read from an input which shares the buffer of this output.
+    pub unsafe fn write(&mut self, sample_count: u32, signal: CvSignal<'_>) {
+        if let Some(samples) = unsafe { self.samples(sample_count) } {
+            for (frame, sample) in samples.iter_mut().enumerate() {
+                *sample = signal.get(frame);
+            }
+        }
+    }
+}
+
+impl Default for CvOutput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The control output through which a plugin reports its latency, in
+/// frames.
+///
+/// Describe the port with `lv2:designation lv2:latency`, pass its buffer to
+/// [`connect`](Self::connect) in `connect_port()`, and call
+/// [`report`](Self::report) in every `run()`.  Hosts call `run(0)` to read
+/// the latency before processing any audio, so report it before returning
+/// early on an empty block.
+#[derive(Debug)]
+pub struct LatencyPort {
+    data: *mut f32,
+}
+
+impl LatencyPort {
+    /// Create an unconnected port.
+    pub const fn new() -> Self {
+        LatencyPort {
+            data: ptr::null_mut(),
+        }
+    }
+
+    /// Connect the port to the buffer the host passed to `connect_port()`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be NULL or point to an `f32` which stays valid until the
+    /// port is connected again or dropped, since [`report`](Self::report)
+    /// writes it.
+    pub unsafe fn connect(&mut self, data: *mut c_void) {
+        self.data = data as *mut f32;
+    }
+
+    /// Write the latency to the port, if it is connected.
+    pub fn report(&self, frames: u32) {
+        if !self.data.is_null() {
+            unsafe { *self.data = frames as f32 };
+        }
+    }
+}
+
+impl Default for LatencyPort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A gain which fades linearly between 0 and 1 over a fixed number of
+/// frames, across blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fade {
+    gain: f32,
+    step: f32,
+    on: bool,
+}
+
+impl Fade {
+    /// Create a fade which takes `frames` frames from one end to the other,
+    /// and starts settled on or off.
+    pub const fn new(frames: u32, on: bool) -> Self {
+        Fade {
+            gain: if on { 1.0 } else { 0.0 },
+            step: if frames == 0 {
+                1.0
+            } else {
+                1.0 / frames as f32
+            },
+            on,
+        }
+    }
+
+    /// Start fading towards 1 if `on`, and towards 0 otherwise.
+    pub fn set(&mut self, on: bool) {
+        self.on = on;
+    }
+
+    /// Return true iff the fade is towards 1.
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Return the gain reached at the end of the last block.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Return true iff the gain has reached 1, or 0 if the fade is off.
+    pub fn is_settled(&self) -> bool {
+        self.gain == self.target()
+    }
+
+    /// Jump to the end of the fade, e.g. when a plugin is activated.
+    pub fn settle(&mut self) {
+        self.gain = self.target();
+    }
+
+    /// Return the gain of every frame of the next block of `sample_count`
+    /// frames, and advance to its end.
+    pub fn block(&mut self, sample_count: u32) -> FadeBlock {
+        let block = FadeBlock {
+            gain: self.gain,
+            step: self.step,
+            target: self.target(),
+            frames: sample_count,
+        };
+        let distance = self.step * sample_count as f32;
+        self.gain = if self.on {
+            (self.gain + distance).min(1.0)
+        } else {
+            (self.gain - distance).max(0.0)
+        };
+        block
+    }
+
+    fn target(&self) -> f32 {
+        if self.on { 1.0 } else { 0.0 }
+    }
+}
+
+/// The gains of one block of a [`Fade`], frame by frame.
+#[derive(Debug, Clone)]
+pub struct FadeBlock {
+    gain: f32,
+    step: f32,
+    target: f32,
+    frames: u32,
+}
+
+impl Iterator for FadeBlock {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.frames == 0 {
+            return None;
+        }
+        self.frames -= 1;
+        self.gain = if self.target > self.gain {
+            (self.gain + self.step).min(self.target)
+        } else {
+            (self.gain - self.step).max(self.target)
+        };
+        Some(self.gain)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.frames as usize, Some(self.frames as usize))
+    }
+}
+
+impl ExactSizeIterator for FadeBlock {}
+
+/// The control input designated `lv2:enabled`, through which hosts bypass
+/// a plugin: it is on (enabled) when greater than 0.
+///
+/// Instead of switching between the processed and the dry signal, which
+/// clicks, mix them with the gain of [`block`](Self::block), which fades
+/// over several blocks:
+///
+/// ```
+/// use lv2_raw::EnabledPort;
+///
+/// let mut enabled = EnabledPort::new(64);
+/// let (dry, mut out) = ([0.5f32; 16], [0.0f32; 16]);
+/// for ((out, dry), gain) in out.iter_mut().zip(dry).zip(enabled.block(16)) {
+///     let wet = dry * 2.0;
+///     *out = wet * gain + dry * (1.0 - gain);
+/// }
+/// // Unconnected, the port is enabled, so nothing fades.
+/// assert_eq!(out, [1.0; 16]);
+/// ```
+///
+/// When [`is_bypassed`](Self::is_bypassed), the plugin may just copy its
+/// inputs to its outputs.  Call [`settle`](Self::settle) in `activate()`.
+#[derive(Debug)]
+pub struct EnabledPort {
+    port: ControlInput,
+    fade: Fade,
+}
+
+impl EnabledPort {
+    /// Create an unconnected, enabled port which fades over `fade_frames`
+    /// frames.
+    pub const fn new(fade_frames: u32) -> Self {
+        EnabledPort {
+            port: ControlInput::new(1.0),
+            fade: Fade::new(fade_frames, true),
+        }
+    }
+
+    /// Connect the port to the buffer the host passed to `connect_port()`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`ControlInput::connect`].
+    pub unsafe fn connect(&mut self, data: *mut c_void) {
+        unsafe { self.port.connect(data) };
+    }
+
+    /// Return true iff the host enables the plugin.
+    pub fn is_enabled(&self) -> bool {
+        self.port.value() > 0.0
+    }
+
+    /// Return true iff the plugin is disabled and the fade is over.
+    pub fn is_bypassed(&self) -> bool {
+        !self.is_enabled() && self.fade.gain() == 0.0
+    }
+
+    /// Jump to the gain of the current value of the port, without fading.
+    pub fn settle(&mut self) {
+        self.fade.set(self.is_enabled());
+        self.fade.settle();
+    }
+
+    /// Return the gain of the processed signal on every frame of a block of
+    /// `sample_count` frames, from 0 (bypassed) to 1 (enabled).  The dry
+    /// signal gets 1 minus that.
+    pub fn block(&mut self, sample_count: u32) -> FadeBlock {
+        self.fade.set(self.is_enabled());
+        self.fade.block(sample_count)
+    }
+}
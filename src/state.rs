@@ -0,0 +1,305 @@
+// Documentation copied from http://lv2plug.in/ns/ext/state/state.h
+
+// Copyright text of the original C file:
+
+// Copyright 2010-2016 David Robillard <http://drobilla.net>
+// Copyright 2010 Leonard Ritter <paniq@paniq.org>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/state/state.html>.
+
+use core::ffi::*;
+
+use crate::core::{LV2Feature, LV2Handle};
+use crate::coreutils::FeatureData;
+use crate::uri::Uri;
+
+pub const LV2_STATE_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state");
+pub const LV2_STATE_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#");
+
+pub const LV2_STATE__STATE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#State");
+pub const LV2_STATE__INTERFACE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#interface");
+pub const LV2_STATE__LOADDEFAULTSTATE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/state#loadDefaultState");
+pub const LV2_STATE__FREEPATH: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#freePath");
+pub const LV2_STATE__MAKEPATH: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#makePath");
+pub const LV2_STATE__MAPPATH: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#mapPath");
+pub const LV2_STATE___STATE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#state");
+pub const LV2_STATE__THREADSAFERESTORE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/state#threadSafeRestore");
+pub const LV2_STATE__STATECHANGED: Uri = Uri::new(c"http://lv2plug.in/ns/ext/state#StateChanged");
+
+pub type LV2StateHandle = *mut c_void;
+pub type LV2StateFreePathHandle = *mut c_void;
+pub type LV2StateMapPathHandle = *mut c_void;
+pub type LV2StateMakePathHandle = *mut c_void;
+
+/**
+   Flags describing value characteristics.
+
+   These flags are used along with the value's type URI to determine how to
+   (de-)serialise the value data, or whether it is even possible to do so.
+*/
+pub type LV2StateFlags = u32;
+
+/**
+   Plain Old Data.
+
+   Values with this flag contain no pointers or references to other areas
+   of memory.  It is safe to copy POD values with a simple memcpy and store
+   them for the duration of the process.  A POD value is not necessarily
+   safe to trasmit between processes or machines (for example, filenames
+   are POD), see LV2_STATE_IS_PORTABLE for details.
+*/
+pub const LV2_STATE_IS_POD: LV2StateFlags = 1;
+
+/**
+   Portable (architecture independent) data.
+
+   Values with this flag are in a format that is usable on any
+   architecture.  A portable value saved on one machine can be restored on
+   another machine regardless of architecture.  The format of portable
+   values MUST NOT depend on architecture-specific properties like
+   endianness or alignment.  Portable values MUST NOT contain filenames.
+*/
+pub const LV2_STATE_IS_PORTABLE: LV2StateFlags = 1 << 1;
+
+/**
+   Native data.
+
+   This flag is used by the host to indicate that the saved data is only
+   going to be used locally in the currently running process (for things
+   like instance duplication or snapshots), so the plugin should use the
+   most efficient representation possible and not worry about serialisation
+   and portability.
+*/
+pub const LV2_STATE_IS_NATIVE: LV2StateFlags = 1 << 2;
+
+/** A status code for state functions. */
+pub type LV2StateStatus = u32;
+
+/// Completed successfully.
+pub const LV2_STATE_SUCCESS: LV2StateStatus = 0;
+/// Unknown error.
+pub const LV2_STATE_ERR_UNKNOWN: LV2StateStatus = 1;
+/// Failed due to unsupported type.
+pub const LV2_STATE_ERR_BAD_TYPE: LV2StateStatus = 2;
+/// Failed due to unsupported flags.
+pub const LV2_STATE_ERR_BAD_FLAGS: LV2StateStatus = 3;
+/// Failed due to missing features.
+pub const LV2_STATE_ERR_NO_FEATURE: LV2StateStatus = 4;
+/// Failed due to missing property.
+pub const LV2_STATE_ERR_NO_PROPERTY: LV2StateStatus = 5;
+/// Failed due to insufficient space.
+pub const LV2_STATE_ERR_NO_SPACE: LV2StateStatus = 6;
+
+/**
+   A host-provided function to store a property.
+
+   @param handle Must be the handle passed to LV2_State_Interface.save().
+   @param key The key to store `value` under (URID).
+   @param value Pointer to the value to be stored.
+   @param size The size of `value` in bytes.
+   @param type The type of `value` (URID).
+   @param flags LV2_State_Flags for `value`.
+   @return 0 on success, otherwise a non-zero error code.
+*/
+pub type LV2StateStoreFunction = extern "C" fn(
+    handle: LV2StateHandle,
+    key: u32,
+    value: *const c_void,
+    size: usize,
+    type_: u32,
+    flags: u32,
+) -> LV2StateStatus;
+
+/**
+   A host-provided function to retrieve a property.
+
+   @param handle Must be the handle passed to LV2_State_Interface.restore().
+   @param key The key of the property to retrieve (URID).
+   @param size (Output) If non-NULL, set to the size of the restored value.
+   @param type (Output) If non-NULL, set to the type of the restored value.
+   @param flags (Output) If non-NULL, set to the flags for the restored value.
+   @return A pointer to the restored value (object), or NULL if no value
+   has been stored under `key`.
+*/
+pub type LV2StateRetrieveFunction = extern "C" fn(
+    handle: LV2StateHandle,
+    key: u32,
+    size: *mut usize,
+    type_: *mut u32,
+    flags: *mut u32,
+) -> *const c_void;
+
+/**
+   LV2 Plugin State Interface.
+
+   When the plugin's extension_data is called with argument
+   LV2_STATE__interface, the plugin MUST return an LV2_State_Interface
+   structure, which remains valid for the lifetime of the plugin.
+
+   The host can use the contained function pointers to save and restore the
+   state of a plugin instance at any time, provided the threading restrictions
+   of the functions are met.
+*/
+#[repr(C)]
+pub struct LV2StateInterface {
+    /**
+       Save plugin state using a host-provided `store` callback.
+
+       The plugin is expected to store everything necessary to completely
+       restore its state later.  Plugins SHOULD store simple POD data whenever
+       possible, and consider the possibility of state being restored much
+       later on a different machine.
+
+       This function has its own special threading class: it may not be called
+       concurrently with any "Instantiation" function, but it may be called
+       concurrently with functions in any other class, unless the definition of
+       that class prohibits it (for example, it may not be called concurrently
+       with a "Discovery" function, but it may be called concurrently with an
+       "Audio" function.  The plugin is responsible for any locking or
+       lock-free techniques necessary to make this possible.
+    */
+    pub save: extern "C" fn(
+        instance: LV2Handle,
+        store: LV2StateStoreFunction,
+        handle: LV2StateHandle,
+        flags: u32,
+        features: *const *const LV2Feature,
+    ) -> LV2StateStatus,
+
+    /**
+       Restore plugin state using a host-provided `retrieve` callback.
+
+       The state MUST NOT be restored concurrently with any "Audio" or
+       "Instantiation" function, unless the plugin has the
+       state:threadSafeRestore feature.
+    */
+    pub restore: extern "C" fn(
+        instance: LV2Handle,
+        retrieve: LV2StateRetrieveFunction,
+        handle: LV2StateHandle,
+        flags: u32,
+        features: *const *const LV2Feature,
+    ) -> LV2StateStatus,
+}
+
+/**
+   Feature data for state:mapPath (LV2_STATE__mapPath).
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2StateMapPath {
+    /**
+       Opaque host data.
+    */
+    pub handle: LV2StateMapPathHandle,
+
+    /**
+       Map an absolute path to an abstract path for use in plugin state.
+
+       @param handle MUST be the `handle` member of this struct.
+       @param absolute_path The absolute path of a file.
+       @return An abstract path suitable for use in plugin state.
+
+       The plugin MUST use this function to map any paths that will be stored
+       in plugin state.  The returned value is an abstract path which MAY not
+       be an actual file system path; absolute_path() MUST be used to map
+       it to an actual path in order to use the file.
+
+       The caller is responsible for freeing the returned value with free().
+    */
+    pub abstract_path:
+        extern "C" fn(handle: LV2StateMapPathHandle, absolute_path: *const c_char) -> *mut c_char,
+
+    /**
+       Map an abstract path from plugin state to an absolute path.
+
+       @param handle MUST be the `handle` member of this struct.
+       @param abstract_path An abstract path (typically from plugin state).
+       @return An absolute file system path.
+
+       The plugin MUST use this function in order to actually open or otherwise
+       use any paths loaded from plugin state.
+
+       The caller is responsible for freeing the returned value with free().
+    */
+    pub absolute_path:
+        extern "C" fn(handle: LV2StateMapPathHandle, abstract_path: *const c_char) -> *mut c_char,
+}
+
+/**
+   Feature data for state:makePath (LV2_STATE__makePath).
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2StateMakePath {
+    /**
+       Opaque host data.
+    */
+    pub handle: LV2StateMakePathHandle,
+
+    /**
+       Return a path the plugin may use to create a new file.
+       @param handle MUST be the `handle` member of this struct.
+       @param path The path of the new file within a namespace unique to this
+       plugin instance.
+       @return The absolute path to use for the new file.
+
+       This function can be used by plugins to create files and directories,
+       either at state saving time (if this feature is passed to
+       LV2_State_Interface.save()) or any time (if this feature is passed to
+       LV2_Descriptor.instantiate()).
+
+       The caller is responsible for freeing the returned value with free().
+    */
+    pub path: extern "C" fn(handle: LV2StateMakePathHandle, path: *const c_char) -> *mut c_char,
+}
+
+/**
+   Feature data for state:freePath (LV2_STATE__freePath).
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2StateFreePath {
+    /**
+       Opaque host data.
+    */
+    pub handle: LV2StateFreePathHandle,
+
+    /**
+       Free a path returned by a state feature.
+
+       @param handle MUST be the `handle` member of this struct.
+       @param path The path previously returned by a state feature.
+
+       This function can be used by plugins to free paths allocated by the host
+       and returned by state features (LV2_State_Map_Path.abstract_path(),
+       LV2_State_Map_Path.absolute_path(), and LV2_State_Make_Path.path()).
+    */
+    pub free_path: extern "C" fn(handle: LV2StateFreePathHandle, path: *mut c_char),
+}
+
+unsafe impl FeatureData for LV2StateMapPath {
+    const URI: Uri = LV2_STATE__MAPPATH;
+}
+
+unsafe impl FeatureData for LV2StateMakePath {
+    const URI: Uri = LV2_STATE__MAKEPATH;
+}
+
+unsafe impl FeatureData for LV2StateFreePath {
+    const URI: Uri = LV2_STATE__FREEPATH;
+}
@@ -26,19 +26,22 @@
 //! Note the time extension is purely data, this header merely defines URIs for
 //! convenience.
 
-pub static LV2_TIME_URI: &[u8] = b"http://lv2plug.in/ns/ext/time\0";
-pub static LV2_TIME_PREFIX: &[u8] = b"http://lv2plug.in/ns/ext/time#\0";
-
-pub static LV2_TIME__TIME: &[u8] = b"http://lv2plug.in/ns/ext/time#Time\0";
-pub static LV2_TIME__POSITION: &[u8] = b"http://lv2plug.in/ns/ext/time#Position\0";
-pub static LV2_TIME__RATE: &[u8] = b"http://lv2plug.in/ns/ext/time#Rate\0";
-pub static LV2_TIME___POSITION: &[u8] = b"http://lv2plug.in/ns/ext/time#position\0";
-pub static LV2_TIME__BARBEAT: &[u8] = b"http://lv2plug.in/ns/ext/time#barBeat\0";
-pub static LV2_TIME__BAR: &[u8] = b"http://lv2plug.in/ns/ext/time#bar\0";
-pub static LV2_TIME__BEAT: &[u8] = b"http://lv2plug.in/ns/ext/time#beat\0";
-pub static LV2_TIME__BEATUNIT: &[u8] = b"http://lv2plug.in/ns/ext/time#beatUnit\0";
-pub static LV2_TIME__BEATSPERBAR: &[u8] = b"http://lv2plug.in/ns/ext/time#beatsPerBar\0";
-pub static LV2_TIME__BEATSPERMINUTE: &[u8] = b"http://lv2plug.in/ns/ext/time#beatsPerMinute\0";
-pub static LV2_TIME__FRAME: &[u8] = b"http://lv2plug.in/ns/ext/time#frame\0";
-pub static LV2_TIME__FRAMESPERSECOND: &[u8] = b"http://lv2plug.in/ns/ext/time#framesPerSecond\0";
-pub static LV2_TIME__SPEED: &[u8] = b"http://lv2plug.in/ns/ext/time#speed\0";
+use crate::uri::Uri;
+
+pub const LV2_TIME_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time");
+pub const LV2_TIME_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#");
+
+pub const LV2_TIME__TIME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#Time");
+pub const LV2_TIME__POSITION: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#Position");
+pub const LV2_TIME__RATE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#Rate");
+pub const LV2_TIME___POSITION: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#position");
+pub const LV2_TIME__BARBEAT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#barBeat");
+pub const LV2_TIME__BAR: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#bar");
+pub const LV2_TIME__BEAT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#beat");
+pub const LV2_TIME__BEATUNIT: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#beatUnit");
+pub const LV2_TIME__BEATSPERBAR: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#beatsPerBar");
+pub const LV2_TIME__BEATSPERMINUTE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#beatsPerMinute");
+pub const LV2_TIME__FRAME: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#frame");
+pub const LV2_TIME__FRAMESPERSECOND: Uri =
+    Uri::new(c"http://lv2plug.in/ns/ext/time#framesPerSecond");
+pub const LV2_TIME__SPEED: Uri = Uri::new(c"http://lv2plug.in/ns/ext/time#speed");
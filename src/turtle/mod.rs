@@ -0,0 +1,433 @@
+//! A small, self-contained reader for Turtle, the RDF syntax LV2 data is
+//! written in.
+//!
+//! This covers what LV2 bundles use: `@prefix`/`@base` (and their SPARQL
+//! forms), IRIs and prefixed names, blank nodes and `[ ... ]` property lists,
+//! collections, and string, numeric and boolean literals.  Parsed triples are
+//! kept in a [`Graph`], which has just enough querying to extract plugin
+//! data; see [`bundle`](crate::bundle) for that.  A [`Writer`] produces
+//! documents this parser, and any other, can read back.
+//!
+//! ```
+//! use lv2_raw::turtle::{Graph, RDF__TYPE, Term};
+//! use lv2_raw::LV2_CORE__PLUGIN;
+//!
+//! let graph = Graph::parse(
+//!     "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+//!      <amp> a lv2:Plugin ; lv2:binary <amp.so> .",
+//!     "file:///usr/lib/lv2/amp.lv2/manifest.ttl",
+//! )
+//! .unwrap();
+//! let plugin = Term::iri("file:///usr/lib/lv2/amp.lv2/amp");
+//! assert!(graph.contains(&plugin, RDF__TYPE, &LV2_CORE__PLUGIN.into()));
+//! ```
+
+mod parser;
+mod writer;
+
+pub use writer::{Object, Properties, Writer};
+
+use crate::uri::Uri;
+use std::error::Error;
+use std::fmt;
+
+pub const RDF_PREFIX: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#");
+pub const RDFS_PREFIX: Uri = Uri::new(c"http://www.w3.org/2000/01/rdf-schema#");
+pub const DOAP_PREFIX: Uri = Uri::new(c"http://usefulinc.com/ns/doap#");
+pub const XSD_PREFIX: Uri = Uri::new(c"http://www.w3.org/2001/XMLSchema#");
+
+pub const RDF__TYPE: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+pub const RDF__FIRST: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+pub const RDF__REST: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+pub const RDF__NIL: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#nil");
+pub const RDF__VALUE: Uri = Uri::new(c"http://www.w3.org/1999/02/22-rdf-syntax-ns#value");
+pub const RDFS__LABEL: Uri = Uri::new(c"http://www.w3.org/2000/01/rdf-schema#label");
+pub const RDFS__SEEALSO: Uri = Uri::new(c"http://www.w3.org/2000/01/rdf-schema#seeAlso");
+pub const DOAP__NAME: Uri = Uri::new(c"http://usefulinc.com/ns/doap#name");
+pub const XSD__BOOLEAN: Uri = Uri::new(c"http://www.w3.org/2001/XMLSchema#boolean");
+pub const XSD__DECIMAL: Uri = Uri::new(c"http://www.w3.org/2001/XMLSchema#decimal");
+pub const XSD__DOUBLE: Uri = Uri::new(c"http://www.w3.org/2001/XMLSchema#double");
+pub const XSD__INTEGER: Uri = Uri::new(c"http://www.w3.org/2001/XMLSchema#integer");
+
+/// A literal value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Literal {
+    /// The lexical form, with escapes resolved.
+    pub value: String,
+    /// The datatype IRI, or `None` for plain strings.
+    pub datatype: Option<String>,
+    /// The language tag of a language-tagged string.
+    pub language: Option<String>,
+}
+
+/// An RDF term: the subject, predicate or object of a triple.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    /// An absolute IRI.
+    Iri(String),
+    /// A blank node, with a label that is unique within its graph.
+    Blank(String),
+    /// A literal.
+    Literal(Literal),
+}
+
+impl Term {
+    /// Create an IRI term.
+    pub fn iri(iri: impl AsRef<str>) -> Term {
+        Term::Iri(iri.as_ref().to_owned())
+    }
+
+    /// Create a plain string literal.
+    pub fn string(value: impl Into<String>) -> Term {
+        Term::Literal(Literal {
+            value: value.into(),
+            datatype: None,
+            language: None,
+        })
+    }
+
+    /// Create a literal with a datatype.
+    pub fn typed(value: impl Into<String>, datatype: impl AsRef<str>) -> Term {
+        Term::Literal(Literal {
+            value: value.into(),
+            datatype: Some(datatype.as_ref().to_owned()),
+            language: None,
+        })
+    }
+
+    /// Return the IRI, if this is one.
+    pub fn as_iri(&self) -> Option<&str> {
+        match self {
+            Term::Iri(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    /// Return the literal, if this is one.
+    pub fn as_literal(&self) -> Option<&Literal> {
+        match self {
+            Term::Literal(literal) => Some(literal),
+            _ => None,
+        }
+    }
+
+    /// Return the lexical form, if this is a literal.
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_literal().map(|l| l.value.as_str())
+    }
+
+    /// Return the value of a numeric literal.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_str()?.trim().parse().ok()
+    }
+
+    /// Return the value of an integer literal.
+    pub fn as_i64(&self) -> Option<i64> {
+        let value = self.as_str()?.trim();
+        value.strip_prefix('+').unwrap_or(value).parse().ok()
+    }
+
+    /// Return the value of a boolean literal.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_str()? {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Return true iff this is a blank node.
+    pub fn is_blank(&self) -> bool {
+        matches!(self, Term::Blank(_))
+    }
+}
+
+impl From<Uri> for Term {
+    fn from(uri: Uri) -> Term {
+        Term::iri(uri.as_str())
+    }
+}
+
+impl fmt::Display for Term {
+    /// Write the term in N-Triples syntax.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Iri(iri) => write!(f, "<{iri}>"),
+            Term::Blank(label) => write!(f, "_:{label}"),
+            Term::Literal(literal) => {
+                f.write_str("\"")?;
+                for c in literal.value.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                f.write_str("\"")?;
+                if let Some(language) = &literal.language {
+                    write!(f, "@{language}")
+                } else if let Some(datatype) = &literal.datatype {
+                    write!(f, "^^<{datatype}>")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// A statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub subject: Term,
+    /// The predicate, which is always an IRI.
+    pub predicate: String,
+    pub object: Term,
+}
+
+/// Error returned for malformed Turtle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurtleError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for TurtleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for TurtleError {}
+
+/// A set of triples, possibly from several documents.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    triples: Vec<Triple>,
+    blank_count: usize,
+}
+
+impl Graph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a document, resolving relative IRIs against `base`.
+    pub fn parse(text: &str, base: &str) -> Result<Graph, TurtleError> {
+        let mut graph = Graph::new();
+        graph.load(text, base)?;
+        Ok(graph)
+    }
+
+    /// Parse a document and add its triples.
+    ///
+    /// Blank node labels are local to each document, so blank nodes of
+    /// different documents never clash.  On error, the triples read before
+    /// the error are kept.
+    pub fn load(&mut self, text: &str, base: &str) -> Result<(), TurtleError> {
+        parser::Parser::new(text, base, self).parse()
+    }
+
+    /// Return a fresh blank node.
+    pub fn new_blank(&mut self) -> Term {
+        self.blank_count += 1;
+        Term::Blank(format!("b{}", self.blank_count))
+    }
+
+    /// Add a triple.
+    pub fn insert(&mut self, subject: Term, predicate: impl Into<String>, object: Term) {
+        self.triples.push(Triple {
+            subject,
+            predicate: predicate.into(),
+            object,
+        });
+    }
+
+    /// Return all triples, in document order.
+    pub fn triples(&self) -> &[Triple] {
+        &self.triples
+    }
+
+    /// Return the number of triples.
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    /// Return true iff there are no triples.
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+
+    /// Return true iff the graph contains the given triple.
+    pub fn contains(&self, subject: &Term, predicate: impl AsRef<str>, object: &Term) -> bool {
+        let predicate = predicate.as_ref();
+        self.triples
+            .iter()
+            .any(|t| t.subject == *subject && t.predicate == predicate && t.object == *object)
+    }
+
+    /// Iterate over the objects of all triples with the given subject and
+    /// predicate.
+    pub fn objects<'a>(
+        &'a self,
+        subject: &'a Term,
+        predicate: impl AsRef<str> + 'a,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.triples
+            .iter()
+            .filter(move |t| t.subject == *subject && t.predicate == predicate.as_ref())
+            .map(|t| &t.object)
+    }
+
+    /// Return the first object with the given subject and predicate.
+    pub fn object(&self, subject: &Term, predicate: impl AsRef<str>) -> Option<&Term> {
+        let predicate = predicate.as_ref();
+        self.triples
+            .iter()
+            .find(|t| t.subject == *subject && t.predicate == predicate)
+            .map(|t| &t.object)
+    }
+
+    /// Iterate over the subjects of all triples with the given predicate and
+    /// object.
+    pub fn subjects<'a>(
+        &'a self,
+        predicate: impl AsRef<str> + 'a,
+        object: &'a Term,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.triples
+            .iter()
+            .filter(move |t| t.predicate == predicate.as_ref() && t.object == *object)
+            .map(|t| &t.subject)
+    }
+
+    /// Return the elements of the collection starting at `head`, or `None` if
+    /// it is not a well-formed list.
+    pub fn list(&self, head: &Term) -> Option<Vec<&Term>> {
+        let mut items = Vec::new();
+        let mut node = head;
+        while node.as_iri() != Some(RDF__NIL.as_str()) {
+            items.push(self.object(node, RDF__FIRST)?);
+            node = self.object(node, RDF__REST)?;
+            if items.len() > self.triples.len() {
+                // A cycle.
+                return None;
+            }
+        }
+        Some(items)
+    }
+}
+
+/// Resolve a possibly relative IRI reference against an absolute base IRI.
+pub fn resolve_iri(base: &str, reference: &str) -> String {
+    if has_scheme(reference) {
+        return remove_dot_segments_of(reference);
+    }
+    let (scheme, rest) = match base.find(':') {
+        Some(i) if has_scheme(base) => (&base[..=i], &base[i + 1..]),
+        _ => ("", base),
+    };
+    if reference.starts_with("//") {
+        return remove_dot_segments_of(&format!("{scheme}{reference}"));
+    }
+
+    // Split the base into authority, path and query.
+    let (authority, path) = match rest.strip_prefix("//") {
+        Some(after) => {
+            let end = after.find(['/', '?', '#']).unwrap_or(after.len());
+            (&rest[..end + 2], &after[end..])
+        }
+        None => ("", rest),
+    };
+    let path = &path[..path.find('#').unwrap_or(path.len())];
+    let (path, query) = match path.find('?') {
+        Some(i) => (&path[..i], &path[i..]),
+        None => (path, ""),
+    };
+
+    let resolved = if reference.is_empty() {
+        format!("{path}{query}")
+    } else if reference.starts_with('#') {
+        format!("{path}{query}{reference}")
+    } else if reference.starts_with('?') {
+        format!("{path}{reference}")
+    } else if reference.starts_with('/') {
+        reference.to_owned()
+    } else {
+        let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+        let dir = if dir.is_empty() && !authority.is_empty() {
+            "/"
+        } else {
+            dir
+        };
+        format!("{dir}{reference}")
+    };
+    format!("{scheme}{authority}{}", remove_dot_segments(&resolved))
+}
+
+fn has_scheme(iri: &str) -> bool {
+    let mut chars = iri.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && iri.find(':').is_some_and(|i| {
+            iri[..i]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+}
+
+fn remove_dot_segments_of(iri: &str) -> String {
+    let Some(i) = iri.find(':') else {
+        return iri.to_owned();
+    };
+    let (scheme, rest) = iri.split_at(i + 1);
+    match rest.strip_prefix("//") {
+        Some(after) => {
+            let end = after.find(['/', '?', '#']).unwrap_or(after.len());
+            format!(
+                "{scheme}//{}{}",
+                &after[..end],
+                remove_dot_segments(&after[end..])
+            )
+        }
+        None => iri.to_owned(),
+    }
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let end = path.find(['?', '#']).unwrap_or(path.len());
+    let (path, suffix) = path.split_at(end);
+    if !path.contains("./") && !path.ends_with("/.") && !path.ends_with("/..") {
+        return format!("{path}{suffix}");
+    }
+
+    let mut output: Vec<&str> = Vec::new();
+    let segments: Vec<&str> = path.split('/').collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match *segment {
+            "." => {
+                if last {
+                    output.push("");
+                }
+            }
+            ".." => {
+                if output.len() > 1 {
+                    output.pop();
+                }
+                if last {
+                    output.push("");
+                }
+            }
+            segment => output.push(segment),
+        }
+    }
+    format!("{}{suffix}", output.join("/"))
+}
@@ -0,0 +1,551 @@
+//! A recursive descent parser for Turtle.
+
+use super::{
+    Graph, Literal, RDF__FIRST, RDF__NIL, RDF__REST, RDF__TYPE, Term, TurtleError, XSD__BOOLEAN,
+    XSD__DECIMAL, XSD__DOUBLE, XSD__INTEGER, resolve_iri,
+};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, TurtleError>;
+
+/// How deeply collections and blank node property lists may nest, so a
+/// hostile document can not overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+pub(super) struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    base: String,
+    prefixes: HashMap<String, String>,
+    blanks: HashMap<String, Term>,
+    depth: usize,
+    graph: &'a mut Graph,
+}
+
+impl<'a> Parser<'a> {
+    pub(super) fn new(text: &str, base: &str, graph: &'a mut Graph) -> Self {
+        Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            base: base.to_owned(),
+            prefixes: HashMap::new(),
+            blanks: HashMap::new(),
+            depth: 0,
+            graph,
+        }
+    }
+
+    pub(super) fn parse(mut self) -> Result<()> {
+        loop {
+            self.skip_ws();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+            self.statement()?;
+        }
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T> {
+        let before = &self.chars[..self.pos.min(self.chars.len())];
+        let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+        Err(TurtleError {
+            line,
+            column,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    /// Match a keyword case-insensitively, if it is followed by whitespace.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let n = keyword.len();
+        let matches = keyword
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i).is_some_and(|p| p.eq_ignore_ascii_case(&c)))
+            && self.peek_at(n).is_some_and(char::is_whitespace);
+        if matches {
+            self.pos += n;
+        }
+        matches
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.pos += 1;
+            } else if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            match self.peek() {
+                Some(found) => self.error(format!("expected '{c}', found '{found}'")),
+                None => self.error(format!("expected '{c}', found end of input")),
+            }
+        }
+    }
+
+    fn statement(&mut self) -> Result<()> {
+        if self.starts_with("@prefix") {
+            self.pos += "@prefix".len();
+            self.prefix_id()?;
+            self.expect('.')
+        } else if self.starts_with("@base") {
+            self.pos += "@base".len();
+            self.base_id()?;
+            self.expect('.')
+        } else if self.keyword("PREFIX") {
+            self.prefix_id()
+        } else if self.keyword("BASE") {
+            self.base_id()
+        } else {
+            self.triples()?;
+            self.expect('.')
+        }
+    }
+
+    fn prefix_id(&mut self) -> Result<()> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ':' && is_name_char(c)) {
+            self.pos += 1;
+        }
+        let prefix: String = self.chars[start..self.pos].iter().collect();
+        if self.next() != Some(':') {
+            return self.error("expected a prefix name");
+        }
+        self.skip_ws();
+        let iri = self.iri_ref()?;
+        self.prefixes.insert(prefix, iri);
+        Ok(())
+    }
+
+    fn base_id(&mut self) -> Result<()> {
+        self.skip_ws();
+        self.base = self.iri_ref()?;
+        Ok(())
+    }
+
+    fn triples(&mut self) -> Result<()> {
+        self.skip_ws();
+        if self.peek() == Some('[') {
+            let subject = self.blank_property_list()?;
+            self.skip_ws();
+            if self.peek() != Some('.') {
+                self.predicate_object_list(&subject)?;
+            }
+            Ok(())
+        } else {
+            let subject = match self.peek() {
+                Some('(') => self.collection()?,
+                _ => self.resource()?,
+            };
+            self.predicate_object_list(&subject)
+        }
+    }
+
+    fn predicate_object_list(&mut self, subject: &Term) -> Result<()> {
+        loop {
+            let predicate = self.verb()?;
+            loop {
+                let object = self.object()?;
+                self.graph
+                    .insert(subject.clone(), predicate.clone(), object);
+                self.skip_ws();
+                if self.peek() == Some(',') {
+                    self.pos += 1;
+                } else {
+                    break;
+                }
+            }
+
+            // Any number of ';' may follow, but then another verb is optional.
+            self.skip_ws();
+            if self.peek() != Some(';') {
+                return Ok(());
+            }
+            while self.peek() == Some(';') {
+                self.pos += 1;
+                self.skip_ws();
+            }
+            if matches!(self.peek(), Some('.' | ']' | '}') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<String> {
+        self.skip_ws();
+        if self.peek() == Some('a')
+            && self
+                .peek_at(1)
+                .is_none_or(|c| c.is_whitespace() || "<[(\"'_".contains(c))
+        {
+            self.pos += 1;
+            return Ok(RDF__TYPE.as_str().to_owned());
+        }
+        match self.resource()? {
+            Term::Iri(iri) => Ok(iri),
+            _ => self.error("expected a predicate"),
+        }
+    }
+
+    fn object(&mut self) -> Result<Term> {
+        self.skip_ws();
+        match self.peek() {
+            Some('[') => self.blank_property_list(),
+            Some('(') => self.collection(),
+            Some('"' | '\'') => self.literal(),
+            Some(c) if c.is_ascii_digit() || "+-.".contains(c) => self.number(),
+            Some(_) if self.word_is("true") => {
+                self.pos += 4;
+                Ok(Term::typed("true", XSD__BOOLEAN))
+            }
+            Some(_) if self.word_is("false") => {
+                self.pos += 5;
+                Ok(Term::typed("false", XSD__BOOLEAN))
+            }
+            _ => self.resource(),
+        }
+    }
+
+    /// Return true iff the input continues with `word` as a whole word.
+    fn word_is(&self, word: &str) -> bool {
+        self.starts_with(word)
+            && self
+                .peek_at(word.len())
+                .is_none_or(|c| !is_name_char(c) && c != ':')
+    }
+
+    /// Parse an IRI, prefixed name, or labelled blank node.
+    fn resource(&mut self) -> Result<Term> {
+        self.skip_ws();
+        match self.peek() {
+            Some('<') => Ok(Term::Iri(self.iri_ref()?)),
+            Some('_') if self.peek_at(1) == Some(':') => {
+                self.pos += 2;
+                let label = self.name();
+                if label.is_empty() {
+                    return self.error("expected a blank node label");
+                }
+                let graph = &mut *self.graph;
+                Ok(self
+                    .blanks
+                    .entry(label)
+                    .or_insert_with(|| graph.new_blank())
+                    .clone())
+            }
+            Some(c) if c == ':' || is_name_char(c) => self.prefixed_name(),
+            Some(c) => self.error(format!("unexpected '{c}'")),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn iri_ref(&mut self) -> Result<String> {
+        if self.next() != Some('<') {
+            return self.error("expected '<'");
+        }
+        let mut iri = String::new();
+        loop {
+            match self.next() {
+                Some('>') => break,
+                Some('\\') => iri.push(self.unicode_escape()?),
+                Some(c) if c.is_whitespace() || c == '<' => {
+                    return self.error("invalid character in IRI");
+                }
+                Some(c) => iri.push(c),
+                None => return self.error("unterminated IRI"),
+            }
+        }
+        Ok(resolve_iri(&self.base, &iri))
+    }
+
+    /// Read a run of name characters, without a trailing '.'.
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        // The position and length of the name up to its last character
+        // which is not an unescaped '.'.
+        let mut end = (self.pos, 0);
+        while let Some(c) = self.peek() {
+            if c == '\\'
+                && let Some(escaped) = self.peek_at(1)
+            {
+                name.push(escaped);
+                self.pos += 2;
+                end = (self.pos, name.len());
+                continue;
+            }
+            if !is_name_char(c) && c != ':' && c != '%' {
+                break;
+            }
+            name.push(c);
+            self.pos += 1;
+            if c != '.' {
+                end = (self.pos, name.len());
+            }
+        }
+        let (pos, len) = end;
+        self.pos = pos;
+        name.truncate(len);
+        name
+    }
+
+    fn prefixed_name(&mut self) -> Result<Term> {
+        let start = self.pos;
+        let name = self.name();
+        let Some(colon) = name.find(':') else {
+            self.pos = start;
+            return self.error(format!("expected a prefixed name, found '{name}'"));
+        };
+        let (prefix, local) = (&name[..colon], &name[colon + 1..]);
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Ok(Term::Iri(format!("{namespace}{local}"))),
+            None => {
+                self.pos = start;
+                self.error(format!("undefined prefix '{prefix}:'"))
+            }
+        }
+    }
+
+    /// Run `f` one level of nesting deeper.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return self.error("too deeply nested");
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn blank_property_list(&mut self) -> Result<Term> {
+        self.expect('[')?;
+        self.nested(Self::blank_property_list_body)
+    }
+
+    fn blank_property_list_body(&mut self) -> Result<Term> {
+        let node = self.graph.new_blank();
+        self.skip_ws();
+        if self.peek() != Some(']') {
+            self.predicate_object_list(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn collection(&mut self) -> Result<Term> {
+        self.expect('(')?;
+        self.nested(Self::collection_body)
+    }
+
+    fn collection_body(&mut self) -> Result<Term> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.peek() == Some(')') {
+                self.pos += 1;
+                break;
+            }
+            if self.peek().is_none() {
+                return self.error("unterminated collection");
+            }
+            items.push(self.object()?);
+        }
+
+        let mut list = Term::from(RDF__NIL);
+        for item in items.into_iter().rev() {
+            let node = self.graph.new_blank();
+            self.graph.insert(node.clone(), RDF__FIRST.as_str(), item);
+            self.graph.insert(node.clone(), RDF__REST.as_str(), list);
+            list = node;
+        }
+        Ok(list)
+    }
+
+    fn literal(&mut self) -> Result<Term> {
+        let quote = self.next().unwrap();
+        let long = self.peek() == Some(quote) && self.peek_at(1) == Some(quote);
+        if long {
+            self.pos += 2;
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                None => return self.error("unterminated string"),
+                Some(c) if c == quote => {
+                    if !long {
+                        break;
+                    }
+                    if self.peek() == Some(quote) && self.peek_at(1) == Some(quote) {
+                        self.pos += 2;
+                        // Quotes right before the closing ones belong to the string.
+                        while self.peek() == Some(quote) {
+                            value.push(quote);
+                            self.pos += 1;
+                        }
+                        break;
+                    }
+                    value.push(c);
+                }
+                Some('\\') => value.push(self.string_escape()?),
+                Some('\n' | '\r') if !long => return self.error("newline in string"),
+                Some(c) => value.push(c),
+            }
+        }
+
+        let mut literal = Literal {
+            value,
+            datatype: None,
+            language: None,
+        };
+        if self.peek() == Some('@') {
+            self.pos += 1;
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                self.pos += 1;
+            }
+            if self.pos == start {
+                return self.error("expected a language tag");
+            }
+            literal.language = Some(self.chars[start..self.pos].iter().collect());
+        } else if self.starts_with("^^") {
+            self.pos += 2;
+            match self.resource()? {
+                Term::Iri(datatype) => literal.datatype = Some(datatype),
+                _ => return self.error("expected a datatype IRI"),
+            }
+        }
+        Ok(Term::Literal(literal))
+    }
+
+    fn string_escape(&mut self) -> Result<char> {
+        match self.peek() {
+            Some('u' | 'U') => self.unicode_escape(),
+            Some(c) => {
+                self.pos += 1;
+                Ok(match c {
+                    't' => '\t',
+                    'b' => '\u{8}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    'f' => '\u{c}',
+                    '"' | '\'' | '\\' => c,
+                    _ => return self.error(format!("invalid escape '\\{c}'")),
+                })
+            }
+            None => self.error("unterminated escape"),
+        }
+    }
+
+    /// Parse the rest of a `\uXXXX` or `\UXXXXXXXX` escape.
+    fn unicode_escape(&mut self) -> Result<char> {
+        let digits = match self.next() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return self.error("invalid escape"),
+        };
+        let hex: String = (0..digits).filter_map(|_| self.next()).collect();
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .filter(|_| hex.len() == digits)
+            .and_then(char::from_u32)
+            .map_or_else(|| self.error("invalid unicode escape"), Ok)
+    }
+
+    /// Return true iff an exponent with at least one digit starts `offset`
+    /// characters ahead.
+    fn exponent_at(&self, offset: usize) -> bool {
+        if !matches!(self.peek_at(offset), Some('e' | 'E')) {
+            return false;
+        }
+        let offset = if matches!(self.peek_at(offset + 1), Some('+' | '-')) {
+            offset + 2
+        } else {
+            offset + 1
+        };
+        self.peek_at(offset).is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn number(&mut self) -> Result<Term> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.pos += 1;
+        }
+        let digits = |p: &mut Self| {
+            let start = p.pos;
+            while p.peek().is_some_and(|c| c.is_ascii_digit()) {
+                p.pos += 1;
+            }
+            p.pos - start
+        };
+
+        let mut datatype = XSD__INTEGER;
+        let mut count = digits(self);
+        // A '.' belongs to the number if digits follow, or if it ends the
+        // mantissa of a double like `1.e5`; otherwise it ends the statement.
+        if self.peek() == Some('.')
+            && (self.peek_at(1).is_some_and(|c| c.is_ascii_digit())
+                || (count > 0 && self.exponent_at(1)))
+        {
+            self.pos += 1;
+            count += digits(self);
+            datatype = XSD__DECIMAL;
+        }
+        if count == 0 {
+            self.pos = start;
+            return self.error("expected a number");
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return self.error("expected an exponent");
+            }
+            datatype = XSD__DOUBLE;
+        }
+        let value: String = self.chars[start..self.pos].iter().collect();
+        Ok(Term::typed(value, datatype))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || (!c.is_ascii() && c != '\u{a0}')
+}
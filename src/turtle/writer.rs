@@ -0,0 +1,259 @@
+//! A writer for Turtle documents.
+
+use super::{Literal, RDF__TYPE, Term, XSD__BOOLEAN, XSD__DECIMAL, XSD__DOUBLE, XSD__INTEGER};
+use crate::uri::Uri;
+
+/// The predicates and objects of a subject.
+pub type Properties = Vec<(String, Vec<Object>)>;
+
+/// The object of a statement written by a [`Writer`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Object {
+    /// A term.
+    Term(Term),
+    /// An anonymous blank node with its properties, written as `[ ... ]`.
+    Node(Properties),
+}
+
+impl From<Term> for Object {
+    fn from(term: Term) -> Object {
+        Object::Term(term)
+    }
+}
+
+impl From<Uri> for Object {
+    fn from(uri: Uri) -> Object {
+        Object::Term(uri.into())
+    }
+}
+
+/// Writes Turtle documents.
+///
+/// IRIs in a declared namespace are written as prefixed names, and numbers
+/// and booleans in their short form.  Relative IRIs can be written with
+/// [`Term::Iri`] as well; they are resolved against the location of the
+/// document when it is read.
+///
+/// ```
+/// use lv2_raw::turtle::{Graph, Object, RDF__TYPE, Term, Writer};
+/// use lv2_raw::{LV2_CORE__BINARY, LV2_CORE__PLUGIN, LV2_CORE_PREFIX};
+///
+/// let mut writer = Writer::new();
+/// writer.prefix("lv2", LV2_CORE_PREFIX);
+/// writer.statement(
+///     &Term::iri("urn:example:amp"),
+///     &[
+///         (RDF__TYPE.as_str().to_owned(), vec![LV2_CORE__PLUGIN.into()]),
+///         (LV2_CORE__BINARY.as_str().to_owned(), vec![Object::Term(Term::iri("amp.so"))]),
+///     ],
+/// );
+/// let text = writer.finish();
+/// assert!(text.contains("<urn:example:amp>\n    a lv2:Plugin ;\n    lv2:binary <amp.so> .\n"));
+///
+/// let graph = Graph::parse(&text, "file:///amp.lv2/manifest.ttl").unwrap();
+/// assert_eq!(graph.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Writer {
+    prefixes: Vec<(String, String)>,
+    out: String,
+}
+
+impl Writer {
+    /// Create a writer for an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a prefix for `namespace`, and use it for the IRIs written
+    /// afterwards.
+    pub fn prefix(&mut self, name: &str, namespace: impl AsRef<str>) -> &mut Self {
+        let namespace = namespace.as_ref();
+        self.out.push_str(&format!("@prefix {name}: "));
+        write_iriref(&mut self.out, namespace);
+        self.out.push_str(" .\n");
+        self.prefixes.push((name.to_owned(), namespace.to_owned()));
+        self
+    }
+
+    /// Write a subject with its properties.  Nothing is written if there are
+    /// no properties.
+    pub fn statement(&mut self, subject: &Term, properties: &[(String, Vec<Object>)]) -> &mut Self {
+        if properties.is_empty() {
+            return self;
+        }
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        let subject = self.term(subject);
+        self.out.push_str(&subject);
+        self.out.push('\n');
+        let mut text = String::new();
+        self.write_properties(&mut text, properties, 1);
+        self.out.push_str(&text);
+        self.out.push_str(" .\n");
+        self
+    }
+
+    /// Return the document.
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    /// Return a term in Turtle syntax, abbreviated where possible.
+    pub fn term(&self, term: &Term) -> String {
+        match term {
+            Term::Iri(iri) => self.iri(iri),
+            Term::Blank(label) => format!("_:{label}"),
+            Term::Literal(literal) => self.literal(literal),
+        }
+    }
+
+    fn iri(&self, iri: &str) -> String {
+        let prefixed = self
+            .prefixes
+            .iter()
+            .filter_map(|(name, namespace)| {
+                let local = iri.strip_prefix(namespace.as_str())?;
+                is_simple_local_name(local).then(|| (namespace.len(), format!("{name}:{local}")))
+            })
+            .max_by_key(|(length, _)| *length);
+        match prefixed {
+            Some((_, prefixed)) => prefixed,
+            None => {
+                let mut out = String::new();
+                write_iriref(&mut out, iri);
+                out
+            }
+        }
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        if let Some(datatype) = &literal.datatype {
+            let value = &literal.value;
+            let short = match datatype.as_str() {
+                t if t == XSD__INTEGER.as_str() => is_integer(value),
+                t if t == XSD__DECIMAL.as_str() => is_decimal(value),
+                t if t == XSD__DOUBLE.as_str() => is_double(value),
+                t if t == XSD__BOOLEAN.as_str() => value == "true" || value == "false",
+                _ => false,
+            };
+            if short {
+                return value.clone();
+            }
+        }
+
+        let mut out = String::from("\"");
+        for c in literal.value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        if let Some(language) = &literal.language {
+            out.push('@');
+            out.push_str(language);
+        } else if let Some(datatype) = &literal.datatype {
+            out.push_str("^^");
+            out.push_str(&self.iri(datatype));
+        }
+        out
+    }
+
+    fn write_properties(
+        &self,
+        out: &mut String,
+        properties: &[(String, Vec<Object>)],
+        depth: usize,
+    ) {
+        let indent = "    ".repeat(depth);
+        for (i, (predicate, objects)) in properties.iter().enumerate() {
+            if i > 0 {
+                out.push_str(" ;\n");
+            }
+            out.push_str(&indent);
+            if predicate == RDF__TYPE.as_str() {
+                out.push('a');
+            } else {
+                out.push_str(&self.iri(predicate));
+            }
+            for (j, object) in objects.iter().enumerate() {
+                out.push_str(if j > 0 { " , " } else { " " });
+                match object {
+                    Object::Term(term) => out.push_str(&self.term(term)),
+                    Object::Node(properties) if properties.is_empty() => out.push_str("[]"),
+                    Object::Node(properties) => {
+                        out.push_str("[\n");
+                        self.write_properties(out, properties, depth + 1);
+                        out.push('\n');
+                        out.push_str(&indent);
+                        out.push(']');
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn write_iriref(out: &mut String, iri: &str) {
+    out.push('<');
+    for c in iri.chars() {
+        if c <= ' ' || "<>\"{}|^`\\".contains(c) {
+            out.push_str(&format!("\\u{:04X}", c as u32));
+        } else {
+            out.push(c);
+        }
+    }
+    out.push('>');
+}
+
+/// Return true iff `local` can be written after a prefix without escapes.
+fn is_simple_local_name(local: &str) -> bool {
+    let mut chars = local.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn unsigned(s: &str) -> &str {
+    s.strip_prefix(['+', '-']).unwrap_or(s)
+}
+
+fn is_integer(s: &str) -> bool {
+    digits(unsigned(s))
+}
+
+fn is_decimal(s: &str) -> bool {
+    match unsigned(s).split_once('.') {
+        Some((whole, fraction)) => (whole.is_empty() || digits(whole)) && digits(fraction),
+        None => false,
+    }
+}
+
+fn is_double(s: &str) -> bool {
+    match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            let mantissa = unsigned(mantissa);
+            let mantissa = match mantissa.split_once('.') {
+                Some((whole, fraction)) => {
+                    (digits(whole) || whole.is_empty())
+                        && (digits(fraction) || fraction.is_empty())
+                        && !(whole.is_empty() && fraction.is_empty())
+                }
+                None => digits(mantissa),
+            };
+            mantissa && is_integer(exponent)
+        }
+        None => false,
+    }
+}
@@ -23,9 +23,56 @@
 
 //! Documentation of the corresponding C header files: <http://lv2plug.in/ns/extensions/ui/ui.html>.
 
-use std::os::raw::*;
+use core::ffi::*;
 
 use crate::LV2Feature;
+use crate::uri::Uri;
+
+pub const LV2_UI_URI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui");
+pub const LV2_UI_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#");
+
+pub const LV2_UI__COCOAUI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#CocoaUI");
+pub const LV2_UI__GTK3UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#Gtk3UI");
+pub const LV2_UI__GTKUI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#GtkUI");
+pub const LV2_UI__PORTNOTIFICATION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#PortNotification");
+pub const LV2_UI__PORTPROTOCOL: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#PortProtocol");
+pub const LV2_UI__QT4UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#Qt4UI");
+pub const LV2_UI__QT5UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#Qt5UI");
+pub const LV2_UI__UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#UI");
+pub const LV2_UI__WINDOWSUI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#WindowsUI");
+pub const LV2_UI__X11UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#X11UI");
+pub const LV2_UI__BACKGROUNDCOLOR: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#backgroundColor");
+pub const LV2_UI__BINARY: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#binary");
+pub const LV2_UI__FIXEDSIZE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#fixedSize");
+pub const LV2_UI__FLOATPROTOCOL: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#floatProtocol");
+pub const LV2_UI__FOREGROUNDCOLOR: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#foregroundColor");
+pub const LV2_UI__IDLEINTERFACE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#idleInterface");
+pub const LV2_UI__NOUSERRESIZE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#noUserResize");
+pub const LV2_UI__NOTIFYTYPE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#notifyType");
+pub const LV2_UI__PARENT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#parent");
+pub const LV2_UI__PEAKPROTOCOL: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#peakProtocol");
+pub const LV2_UI__PLUGIN: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#plugin");
+pub const LV2_UI__PORTINDEX: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#portIndex");
+pub const LV2_UI__PORTMAP: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#portMap");
+pub const LV2_UI___PORTNOTIFICATION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#portNotification");
+pub const LV2_UI__PORTSUBSCRIBE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#portSubscribe");
+pub const LV2_UI__PROTOCOL: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#protocol");
+pub const LV2_UI__REQUESTVALUE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#requestValue");
+pub const LV2_UI__RESIZE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#resize");
+pub const LV2_UI__SCALEFACTOR: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#scaleFactor");
+pub const LV2_UI__SHOWINTERFACE: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/ui#showInterface");
+pub const LV2_UI__TOUCH: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#touch");
+pub const LV2_UI___UI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#ui");
+pub const LV2_UI__UPDATERATE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#updateRate");
+pub const LV2_UI__WINDOWTITLE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/ui#windowTitle");
 
 /**
    A pointer to UI instance internals.
@@ -171,6 +218,10 @@ pub struct LV2UIDescriptorRaw {
     pub extension_data: Option<extern "C" fn(*const c_char) -> *const c_void>,
 }
 
+// Like LV2Descriptor, a UI descriptor is a static URI and function pointers,
+// so UI libraries can keep their descriptors in statics.
+unsafe impl Sync for LV2UIDescriptorRaw {}
+
 /**
    UI Idle Interface (LV2_UI__idleInterface)
 
@@ -0,0 +1,221 @@
+// Documentation copied from http://lv2plug.in/ns/extensions/units/units.h
+
+// Copyright text of the original C file:
+
+// Copyright 2012-2016 David Robillard <http://drobilla.net>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/extensions/units/units.html>.
+
+//! Units for LV2 values, see <http://lv2plug.in/ns/extensions/units> for
+//! details.
+
+use crate::uri::Uri;
+
+pub const LV2_UNITS_URI: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units");
+pub const LV2_UNITS_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#");
+
+pub const LV2_UNITS__CONVERSION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/units#Conversion");
+pub const LV2_UNITS__UNIT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#Unit");
+pub const LV2_UNITS__BAR: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#bar");
+pub const LV2_UNITS__BEAT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#beat");
+pub const LV2_UNITS__BPM: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#bpm");
+pub const LV2_UNITS__CENT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#cent");
+pub const LV2_UNITS__CM: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#cm");
+pub const LV2_UNITS__COEF: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#coef");
+pub const LV2_UNITS___CONVERSION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/units#conversion");
+pub const LV2_UNITS__DB: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#db");
+pub const LV2_UNITS__DEGREE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#degree");
+pub const LV2_UNITS__FRAME: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#frame");
+pub const LV2_UNITS__HZ: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#hz");
+pub const LV2_UNITS__INCH: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#inch");
+pub const LV2_UNITS__KHZ: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#khz");
+pub const LV2_UNITS__KM: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#km");
+pub const LV2_UNITS__M: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#m");
+pub const LV2_UNITS__MHZ: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#mhz");
+pub const LV2_UNITS__MIDINOTE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#midiNote");
+pub const LV2_UNITS__MILE: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#mile");
+pub const LV2_UNITS__MIN: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#min");
+pub const LV2_UNITS__MM: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#mm");
+pub const LV2_UNITS__MS: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#ms");
+pub const LV2_UNITS__NAME: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#name");
+pub const LV2_UNITS__OCT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#oct");
+pub const LV2_UNITS__PC: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#pc");
+pub const LV2_UNITS__PREFIXCONVERSION: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/units#prefixConversion");
+pub const LV2_UNITS__RENDER: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#render");
+pub const LV2_UNITS__S: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#s");
+pub const LV2_UNITS__SEMITONE12TET: Uri =
+    Uri::new(c"http://lv2plug.in/ns/extensions/units#semitone12TET");
+pub const LV2_UNITS__SYMBOL: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#symbol");
+pub const LV2_UNITS___UNIT: Uri = Uri::new(c"http://lv2plug.in/ns/extensions/units#unit");
+
+/// A unit defined by this extension (the object of `units:unit`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Bars.
+    Bar,
+    /// Beats.
+    Beat,
+    /// Beats per minute.
+    Bpm,
+    /// Cents, hundredths of a semitone.
+    Cent,
+    /// Centimetres.
+    Cm,
+    /// A coefficient, a factor without a unit.
+    Coef,
+    /// Decibels.
+    Db,
+    /// Degrees.
+    Degree,
+    /// Audio frames.
+    Frame,
+    /// Hertz.
+    Hz,
+    /// Inches.
+    Inch,
+    /// Kilohertz.
+    Khz,
+    /// Kilometres.
+    Km,
+    /// Metres.
+    M,
+    /// Megahertz.
+    Mhz,
+    /// MIDI note numbers.
+    MidiNote,
+    /// Miles.
+    Mile,
+    /// Minutes.
+    Min,
+    /// Millimetres.
+    Mm,
+    /// Milliseconds.
+    Ms,
+    /// Octaves.
+    Oct,
+    /// Percent.
+    Pc,
+    /// Seconds.
+    S,
+    /// Semitones in 12-tone equal temperament.
+    Semitone12Tet,
+}
+
+impl Unit {
+    /// All units of this extension.
+    pub const ALL: [Unit; 24] = [
+        Unit::Bar,
+        Unit::Beat,
+        Unit::Bpm,
+        Unit::Cent,
+        Unit::Cm,
+        Unit::Coef,
+        Unit::Db,
+        Unit::Degree,
+        Unit::Frame,
+        Unit::Hz,
+        Unit::Inch,
+        Unit::Khz,
+        Unit::Km,
+        Unit::M,
+        Unit::Mhz,
+        Unit::MidiNote,
+        Unit::Mile,
+        Unit::Min,
+        Unit::Mm,
+        Unit::Ms,
+        Unit::Oct,
+        Unit::Pc,
+        Unit::S,
+        Unit::Semitone12Tet,
+    ];
+
+    /// Return the URI of this unit.
+    #[must_use]
+    pub const fn uri(self) -> Uri {
+        match self {
+            Unit::Bar => LV2_UNITS__BAR,
+            Unit::Beat => LV2_UNITS__BEAT,
+            Unit::Bpm => LV2_UNITS__BPM,
+            Unit::Cent => LV2_UNITS__CENT,
+            Unit::Cm => LV2_UNITS__CM,
+            Unit::Coef => LV2_UNITS__COEF,
+            Unit::Db => LV2_UNITS__DB,
+            Unit::Degree => LV2_UNITS__DEGREE,
+            Unit::Frame => LV2_UNITS__FRAME,
+            Unit::Hz => LV2_UNITS__HZ,
+            Unit::Inch => LV2_UNITS__INCH,
+            Unit::Khz => LV2_UNITS__KHZ,
+            Unit::Km => LV2_UNITS__KM,
+            Unit::M => LV2_UNITS__M,
+            Unit::Mhz => LV2_UNITS__MHZ,
+            Unit::MidiNote => LV2_UNITS__MIDINOTE,
+            Unit::Mile => LV2_UNITS__MILE,
+            Unit::Min => LV2_UNITS__MIN,
+            Unit::Mm => LV2_UNITS__MM,
+            Unit::Ms => LV2_UNITS__MS,
+            Unit::Oct => LV2_UNITS__OCT,
+            Unit::Pc => LV2_UNITS__PC,
+            Unit::S => LV2_UNITS__S,
+            Unit::Semitone12Tet => LV2_UNITS__SEMITONE12TET,
+        }
+    }
+
+    /// Return the unit for a URI, if it is one of this extension.
+    #[must_use]
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|u| u.uri() == uri)
+    }
+
+    /// Return the `units:symbol` of this unit, as shown after a value.
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Unit::Bar => "bars",
+            Unit::Beat => "beats",
+            Unit::Bpm => "BPM",
+            Unit::Cent => "ct",
+            Unit::Cm => "cm",
+            Unit::Coef => "",
+            Unit::Db => "dB",
+            Unit::Degree => "deg",
+            Unit::Frame => "frames",
+            Unit::Hz => "Hz",
+            Unit::Inch => "in",
+            Unit::Khz => "kHz",
+            Unit::Km => "km",
+            Unit::M => "m",
+            Unit::Mhz => "MHz",
+            Unit::MidiNote => "note",
+            Unit::Mile => "mi",
+            Unit::Min => "min",
+            Unit::Mm => "mm",
+            Unit::Ms => "ms",
+            Unit::Oct => "oct",
+            Unit::Pc => "%",
+            Unit::S => "s",
+            Unit::Semitone12Tet => "semi",
+        }
+    }
+}
+
+impl From<Unit> for Uri {
+    fn from(u: Unit) -> Uri {
+        u.uri()
+    }
+}
@@ -0,0 +1,138 @@
+//! URI constants.
+//!
+//! Every URI defined by this crate is a [`Uri`]: a NUL-terminated, UTF-8
+//! `&'static CStr` that can be handed to C as-is and read from Rust as a
+//! `&str`.
+
+use core::ffi::{CStr, c_char};
+use core::fmt;
+use core::ops::Deref;
+
+/// A static, NUL-terminated URI.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uri(&'static CStr);
+
+impl Uri {
+    /// Wrap a C string literal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uri` is not valid UTF-8.  In a `const` this is a compile
+    /// time error.
+    #[must_use]
+    pub const fn new(uri: &'static CStr) -> Self {
+        if uri.to_str().is_err() {
+            panic!("URI is not valid UTF-8");
+        }
+        Uri(uri)
+    }
+
+    /// Return the URI as a C string.
+    #[inline]
+    #[must_use]
+    pub const fn as_cstr(self) -> &'static CStr {
+        self.0
+    }
+
+    /// Return the URI as a string slice, without the trailing NUL.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self.0.to_str() {
+            Ok(s) => s,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Return a pointer to the NUL-terminated URI, as expected by C APIs.
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(self) -> *const c_char {
+        self.0.as_ptr()
+    }
+
+    /// Return true iff the C string at `uri` is equal to this URI.
+    ///
+    /// A NULL `uri` is never equal.
+    ///
+    /// # Safety
+    ///
+    /// `uri` must be NULL or point to a valid NUL-terminated string.
+    #[inline]
+    pub unsafe fn matches(self, uri: *const c_char) -> bool {
+        unsafe { !uri.is_null() && CStr::from_ptr(uri) == self.0 }
+    }
+}
+
+impl Deref for Uri {
+    type Target = CStr;
+
+    #[inline]
+    fn deref(&self) -> &CStr {
+        self.0
+    }
+}
+
+impl AsRef<CStr> for Uri {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.0
+    }
+}
+
+impl AsRef<str> for Uri {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<Uri> for &'static CStr {
+    #[inline]
+    fn from(uri: Uri) -> Self {
+        uri.0
+    }
+}
+
+impl From<Uri> for &'static str {
+    #[inline]
+    fn from(uri: Uri) -> Self {
+        uri.as_str()
+    }
+}
+
+impl PartialEq<CStr> for Uri {
+    fn eq(&self, other: &CStr) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&CStr> for Uri {
+    fn eq(&self, other: &&CStr) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<str> for Uri {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Uri {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for Uri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Uri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
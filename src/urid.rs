@@ -20,21 +20,31 @@
 
 //! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/urid/urid.html>.
 
-use std::os::raw::*;
+use core::error::Error;
+use core::ffi::*;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::core::LV2Feature;
+use crate::coreutils::{FeatureData, lv2_features_data};
+use crate::uri::Uri;
+use crate::urimap::{LV2_URI_MAP_URI, LV2UriMapFeature};
 
 pub type LV2Urid = u32;
 pub type LV2UridMapHandle = *mut c_void;
+pub type LV2UridUnmapHandle = *mut c_void;
 
-pub static LV2_URID_URI: &str = "http://lv2plug.in/ns/ext/urid";
-pub static LV2_URID_PREFIX: &str = "http://lv2plug.in/ns/ext/urid#";
+pub const LV2_URID_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/urid");
+pub const LV2_URID_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/urid#");
 
-pub static LV2_URID__MAP: &str = "http://lv2plug.in/ns/ext/urid#map";
-pub static LV2_URID__UNMAP: &str = "http://lv2plug.in/ns/ext/urid#unmap";
+pub const LV2_URID__MAP: Uri = Uri::new(c"http://lv2plug.in/ns/ext/urid#map");
+pub const LV2_URID__UNMAP: Uri = Uri::new(c"http://lv2plug.in/ns/ext/urid#unmap");
 
 /**
    URID Map Feature (LV2_URID__map)
 */
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct LV2UridMap {
     /**
        Opaque pointer to host data.
@@ -67,3 +77,296 @@ pub struct LV2UridMap {
     */
     pub map: extern "C" fn(handle: LV2UridMapHandle, uri: *const c_char) -> LV2Urid,
 }
+
+/**
+   URI Unmap Feature (LV2_URID__unmap)
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2UridUnmap {
+    /**
+       Opaque pointer to host data.
+
+       This MUST be passed to unmap() whenever it is called.
+       Otherwise, it must not be interpreted in any way.
+    */
+    pub handle: LV2UridUnmapHandle,
+
+    /**
+       Get the URI for a previously mapped numeric ID.
+
+       Returns NULL if `urid` is not yet mapped.  Otherwise, the corresponding
+       URI is returned in a canonical form.  This MAY not be the exact same
+       string that was originally passed to LV2_URID_Map::map(), but it MUST be
+       an identical URI according to the URI syntax specification (RFC3986).  A
+       non-NULL return for a given `urid` will always be the same for the life
+       of the plugin.  Plugins that intend to perform string comparison on
+       unmapped URIs SHOULD first canonicalise URI strings with a call to
+       map_uri() followed by a call to unmap_uri().
+
+       @param handle Must be the callback_data member of this struct.
+       @param urid The ID to be mapped back to the URI string.
+    */
+    pub unmap: extern "C" fn(handle: LV2UridUnmapHandle, urid: LV2Urid) -> *const c_char,
+}
+
+unsafe impl FeatureData for LV2UridMap {
+    const URI: Uri = LV2_URID__MAP;
+}
+
+unsafe impl FeatureData for LV2UridUnmap {
+    const URI: Uri = LV2_URID__UNMAP;
+}
+
+impl LV2UridMap {
+    /// Get the numeric ID of `uri`.
+    ///
+    /// This is a convenience wrapper around the `map` callback, so that a
+    /// [`Uri`] constant (or any other C string) can be passed directly.
+    #[inline]
+    pub fn map(&self, uri: impl AsRef<CStr>) -> LV2Urid {
+        (self.map)(self.handle, uri.as_ref().as_ptr())
+    }
+}
+
+impl LV2UridUnmap {
+    /// Get the URI for a previously mapped numeric ID.
+    ///
+    /// This is a convenience wrapper around the `unmap` callback.  Returns
+    /// `None` if the host returned NULL.
+    #[inline]
+    pub fn unmap(&self, urid: LV2Urid) -> Option<&CStr> {
+        let uri = (self.unmap)(self.handle, urid);
+        if uri.is_null() {
+            None
+        } else {
+            // The host guarantees the string stays valid for the life of the plugin.
+            unsafe { Some(CStr::from_ptr(uri)) }
+        }
+    }
+}
+
+/// A URID map backed by whichever mapping feature the host provides.
+///
+/// Hosts that predate the URID extension only offer the deprecated
+/// `uri-map` feature ([`LV2UriMapFeature`]).  This adapter presents either
+/// one as an [`LV2UridMap`], so the rest of the plugin only has to deal with
+/// one interface.  It dereferences to `LV2UridMap`, and can thus be passed
+/// anywhere a `&LV2UridMap` is expected.
+#[derive(Clone, Copy)]
+pub struct UridMapAdapter {
+    map: LV2UridMap,
+    legacy: bool,
+}
+
+impl UridMapAdapter {
+    /// Find a mapping feature in a features array.
+    ///
+    /// `urid#map` is preferred; `uri-map` is used only if the host does not
+    /// provide it.  Returns `None` if neither feature is present.
+    ///
+    /// # Safety
+    ///
+    /// `features` must be NULL or a valid, NULL terminated features array.
+    /// The feature data must outlive the adapter, which is always the case
+    /// for the features passed to `instantiate()` and a plugin instance.
+    pub unsafe fn from_features(features: *const *const LV2Feature) -> Option<Self> {
+        unsafe {
+            if let Some(map) = lv2_features_data(features, LV2_URID__MAP.as_ptr()) {
+                return Some(Self::from_urid_map(&*(map.as_ptr() as *const LV2UridMap)));
+            }
+            lv2_features_data(features, LV2_URI_MAP_URI.as_ptr())
+                .map(|uri_map| Self::from_uri_map(uri_map.as_ptr() as *const LV2UriMapFeature))
+        }
+    }
+
+    /// Wrap a `urid#map` feature.
+    pub fn from_urid_map(map: &LV2UridMap) -> Self {
+        UridMapAdapter {
+            map: *map,
+            legacy: false,
+        }
+    }
+
+    /// Wrap a legacy `uri-map` feature.
+    ///
+    /// URIs are mapped with a NULL context.
+    ///
+    /// # Safety
+    ///
+    /// `feature` must point to a valid `LV2UriMapFeature` which outlives the
+    /// adapter.
+    pub unsafe fn from_uri_map(feature: *const LV2UriMapFeature) -> Self {
+        UridMapAdapter {
+            map: LV2UridMap {
+                handle: feature as *mut c_void,
+                map: uri_map_trampoline,
+            },
+            legacy: true,
+        }
+    }
+
+    /// Return the adapter as a URID map feature struct.
+    #[inline]
+    pub fn as_urid_map(&self) -> &LV2UridMap {
+        &self.map
+    }
+
+    /// Return true iff the adapter is backed by the deprecated uri-map feature.
+    #[inline]
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+}
+
+impl Deref for UridMapAdapter {
+    type Target = LV2UridMap;
+
+    #[inline]
+    fn deref(&self) -> &LV2UridMap {
+        &self.map
+    }
+}
+
+extern "C" fn uri_map_trampoline(handle: LV2UridMapHandle, uri: *const c_char) -> LV2Urid {
+    let feature = unsafe { &*(handle as *const LV2UriMapFeature) };
+    (feature.uri_to_id)(feature.callback_data, core::ptr::null(), uri)
+}
+
+/// Error returned by the constructor of a [`urid_table!`](crate::urid_table)
+/// struct when the host failed to map one of its URIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UridTableError {
+    /// Name of the field that could not be mapped.
+    pub field: &'static str,
+    /// The URI that was mapped to 0.
+    pub uri: Uri,
+}
+
+impl fmt::Display for UridTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "host mapped <{}> ({}) to 0", self.uri, self.field)
+    }
+}
+
+impl Error for UridTableError {}
+
+/// A URID paired with an optional unmap feature, for `Debug` output.
+///
+/// This is what [`urid_table!`](crate::urid_table) structs print for each
+/// field: the numeric ID, followed by the URI when it can be unmapped.
+pub struct UnmappedUrid<'a> {
+    /// The URID to print.
+    pub urid: LV2Urid,
+    /// Unmap feature used to look up the URI, if any.
+    pub unmap: Option<&'a LV2UridUnmap>,
+}
+
+impl fmt::Debug for UnmappedUrid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unmap.and_then(|unmap| unmap.unmap(self.urid)) {
+            Some(uri) => write!(f, "{} <{}>", self.urid, uri.to_string_lossy()),
+            None => write!(f, "{}", self.urid),
+        }
+    }
+}
+
+/// Declare a struct of URIDs that are mapped together.
+///
+/// Each field is declared with the URI it is mapped from, which may be any
+/// [`Uri`] constant, such as `LV2_ATOM__SEQUENCE` or a plugin's own.  The
+/// macro generates:
+///
+/// - `new(map, unmap)`, which maps every URI through the host's
+///   [`LV2UridMap`] and fails with a [`UridTableError`] naming the first URI
+///   the host mapped to 0.  `unmap` is kept for the `Debug` implementation
+///   and may be `None`.
+/// - A `Debug` implementation which prints each URID along with its URI, as
+///   returned by the unmap feature.
+///
+/// ```
+/// use lv2_raw::*;
+///
+/// const MY_GAIN: Uri = Uri::new(c"urn:example:amp#gain");
+///
+/// lv2_raw::urid_table! {
+///     /// URIDs used by the amp plugin.
+///     pub struct AmpUrids {
+///         pub atom_sequence: LV2_ATOM__SEQUENCE,
+///         pub midi_event: LV2_MIDI__MIDIEVENT,
+///         pub gain: MY_GAIN,
+///     }
+/// }
+///
+/// # #[cfg(feature = "std")] {
+/// let mapper = lv2_raw::host::UridMapper::new();
+/// let urids = AmpUrids::new(&mapper.map_feature(), Some(&mapper.unmap_feature())).unwrap();
+/// assert_eq!(urids.gain, mapper.map(MY_GAIN));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! urid_table {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field_vis:vis $field:ident : $uri:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $crate::urid::LV2Urid,
+            )*
+            __unmap: ::core::option::Option<$crate::urid::LV2UridUnmap>,
+        }
+
+        impl $name {
+            /// Map every URI in the table.
+            ///
+            /// Fails if the host maps any of them to 0.
+            #[allow(dead_code)]
+            $vis fn new(
+                map: &$crate::urid::LV2UridMap,
+                unmap: ::core::option::Option<&$crate::urid::LV2UridUnmap>,
+            ) -> ::core::result::Result<Self, $crate::urid::UridTableError> {
+                ::core::result::Result::Ok($name {
+                    $(
+                        $field: {
+                            let uri: $crate::uri::Uri = $uri;
+                            match map.map(uri) {
+                                0 => {
+                                    return ::core::result::Result::Err($crate::urid::UridTableError {
+                                        field: ::core::stringify!($field),
+                                        uri,
+                                    });
+                                }
+                                id => id,
+                            }
+                        },
+                    )*
+                    __unmap: unmap.copied(),
+                })
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!($name))
+                    $(
+                        .field(
+                            ::core::stringify!($field),
+                            &$crate::urid::UnmappedUrid {
+                                urid: self.$field,
+                                unmap: self.__unmap.as_ref(),
+                            },
+                        )
+                    )*
+                    .finish()
+            }
+        }
+    };
+}
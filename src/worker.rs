@@ -0,0 +1,171 @@
+// Documentation copied from http://lv2plug.in/ns/ext/worker/worker.h
+
+// Copyright text of the original C file:
+
+// Copyright 2012-2016 David Robillard <http://drobilla.net>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THIS SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Documentation of the corresponding C header files: <http://lv2plug.in/ns/ext/worker/worker.html>.
+
+use core::ffi::*;
+
+use crate::core::LV2Handle;
+use crate::coreutils::FeatureData;
+use crate::uri::Uri;
+
+pub const LV2_WORKER_URI: Uri = Uri::new(c"http://lv2plug.in/ns/ext/worker");
+pub const LV2_WORKER_PREFIX: Uri = Uri::new(c"http://lv2plug.in/ns/ext/worker#");
+
+pub const LV2_WORKER__INTERFACE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/worker#interface");
+pub const LV2_WORKER__SCHEDULE: Uri = Uri::new(c"http://lv2plug.in/ns/ext/worker#schedule");
+
+/**
+   Status code for worker functions.
+*/
+pub type LV2WorkerStatus = u32;
+
+/// Completed successfully.
+pub const LV2_WORKER_SUCCESS: LV2WorkerStatus = 0;
+/// Unknown error.
+pub const LV2_WORKER_ERR_UNKNOWN: LV2WorkerStatus = 1;
+/// Failed due to lack of space.
+pub const LV2_WORKER_ERR_NO_SPACE: LV2WorkerStatus = 2;
+
+/** Opaque handle for LV2_Worker_Interface::work(). */
+pub type LV2WorkerRespondHandle = *mut c_void;
+
+/**
+   A function to respond to run() from the worker method.
+
+   The `data` MUST be safe for the host to copy and later pass to
+   work_response(), and the host MUST guarantee that it will be eventually
+   passed to work_response() if this function returns LV2_WORKER_SUCCESS.
+*/
+pub type LV2WorkerRespondFunction = extern "C" fn(
+    handle: LV2WorkerRespondHandle,
+    size: u32,
+    data: *const c_void,
+) -> LV2WorkerStatus;
+
+/**
+   Plugin Worker Interface.
+
+   This is the interface provided by the plugin to implement a worker method.
+   The plugin's extension_data() method should return an LV2_Worker_Interface
+   when called with LV2_WORKER__interface as its argument.
+*/
+#[repr(C)]
+pub struct LV2WorkerInterface {
+    /**
+       The worker method.  This is called by the host in a non-realtime context
+       as requested, possibly with an arbitrary message to handle.
+
+       A response can be sent to run() using `respond`.  The plugin MUST NOT
+       make any assumptions about which thread calls this method, except that
+       there are no real-time requirements and only one call may be executed at
+       a time.  That is, the host MAY call this method from any non-real-time
+       thread, but MUST NOT make concurrent calls to this method from several
+       threads.
+
+       @param instance The LV2 instance this is a method on.
+       @param respond  A function for sending a response to run().
+       @param handle   Must be passed to `respond` if it is called.
+       @param size     The size of `data`.
+       @param data     Data from run(), or NULL.
+    */
+    pub work: extern "C" fn(
+        instance: LV2Handle,
+        respond: LV2WorkerRespondFunction,
+        handle: LV2WorkerRespondHandle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2WorkerStatus,
+
+    /**
+       Handle a response from the worker.  This is called by the host in the
+       run() context when a response from the worker is ready.
+
+       @param instance The LV2 instance this is a method on.
+       @param size     The size of `body`.
+       @param body     Message body, or NULL.
+    */
+    pub work_response:
+        extern "C" fn(instance: LV2Handle, size: u32, body: *const c_void) -> LV2WorkerStatus,
+
+    /**
+       Called when all responses for this cycle have been delivered.
+
+       Since work_response() may be called after run() finished, this provides
+       a hook for code that must run after the cycle is completed.
+
+       This field may be NULL if the plugin has no use for it.  Otherwise, the
+       host MUST call it after every run(), regardless of whether or not any
+       responses were sent that cycle.
+    */
+    pub end_run: Option<extern "C" fn(instance: LV2Handle) -> LV2WorkerStatus>,
+}
+
+/** Opaque handle for LV2_Worker_Schedule. */
+pub type LV2WorkerScheduleHandle = *mut c_void;
+
+/**
+   Schedule Worker Host Feature.
+
+   The host passes this feature to provide a schedule_work() function, which
+   the plugin can use to schedule a worker call from run().
+*/
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct LV2WorkerSchedule {
+    /**
+       Opaque host data.
+    */
+    pub handle: LV2WorkerScheduleHandle,
+
+    /**
+       Request from run() that the host call the worker.
+
+       This function is in the audio threading class.  It should be called from
+       run() to request that the host call the work() method in a non-realtime
+       context with the given arguments.
+
+       This function is always safe to call from run(), but it is not
+       guaranteed that the worker is actually called from a different thread.
+       In particular, when free-wheeling (e.g. for offline rendering), the
+       worker may be executed immediately.  This allows single-threaded
+       processing with sample accuracy and avoids timing problems when run() is
+       executing much faster or slower than real-time.
+
+       Plugins SHOULD be written in such a way that if the worker runs
+       immediately, and responses from the worker are delivered immediately,
+       the effect of the work takes place immediately with sample accuracy.
+
+       The `data` MUST be safe for the host to copy and later pass to work(),
+       and the host MUST guarantee that it will be eventually passed to work()
+       if this function returns LV2_WORKER_SUCCESS.
+
+       @param handle The handle field of this struct.
+       @param size   The size of `data`.
+       @param data   Message to pass to work(), or NULL.
+    */
+    pub schedule_work: extern "C" fn(
+        handle: LV2WorkerScheduleHandle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2WorkerStatus,
+}
+
+unsafe impl FeatureData for LV2WorkerSchedule {
+    const URI: Uri = LV2_WORKER__SCHEDULE;
+}
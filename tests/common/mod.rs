@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use lv2_raw::bundle::{PluginDescription, PortDescription, write_bundle};
+use lv2_raw::{LV2_CORE__LATENCY, PortDirection, PortType};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+
+/// Path of the `amp` example library, which `cargo test` builds.
+pub fn example_library() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    let dir = exe.parent().unwrap().parent().unwrap();
+    dir.join("examples")
+        .join(format!("{DLL_PREFIX}amp{DLL_SUFFIX}"))
+}
+
+/// Create a fresh, empty temporary directory for one test.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lv2_raw-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Make a bundle `<name>.lv2` in `dir` for the plugin `uri` of the
+/// examples library, with `ports`.
+pub fn example_bundle(dir: &Path, name: &str, uri: &str, ports: Vec<PortDescription>) -> PathBuf {
+    let bundle = dir.join(format!("{name}.lv2"));
+    let library = example_library();
+    let binary = PathBuf::from(library.file_name().unwrap());
+    let plugin = PluginDescription {
+        uri: uri.to_owned(),
+        binary: Some(binary.clone()),
+        ports,
+        ..Default::default()
+    };
+    write_bundle(&bundle, &[plugin]).unwrap();
+    std::fs::copy(&library, bundle.join(binary)).unwrap();
+    bundle
+}
+
+/// Describe a port of an example plugin.
+fn port(
+    index: u32,
+    symbol: &str,
+    port_type: PortType,
+    direction: PortDirection,
+) -> PortDescription {
+    PortDescription {
+        index,
+        symbol: symbol.to_owned(),
+        port_type: Some(port_type),
+        direction: Some(direction),
+        ..Default::default()
+    }
+}
+
+/// Make a bundle for the `amp` example in `dir`.
+pub fn amp_bundle(dir: &Path) -> PathBuf {
+    let ports = vec![
+        PortDescription {
+            default: Some(1.0),
+            ..port(0, "gain", PortType::Control, PortDirection::Input)
+        },
+        port(1, "in", PortType::Audio, PortDirection::Input),
+        port(2, "out", PortType::Audio, PortDirection::Output),
+    ];
+    example_bundle(dir, "amp", "urn:lv2_raw:examples:amp", ports)
+}
+
+/// Make a bundle for the `delay` example in `dir`.
+pub fn delay_bundle(dir: &Path) -> PathBuf {
+    let ports = vec![
+        port(0, "in", PortType::Audio, PortDirection::Input),
+        port(1, "out", PortType::Audio, PortDirection::Output),
+        PortDescription {
+            designation: Some(LV2_CORE__LATENCY.as_str().to_owned()),
+            ..port(2, "latency", PortType::Control, PortDirection::Output)
+        },
+    ];
+    example_bundle(dir, "delay", "urn:lv2_raw:examples:delay", ports)
+}
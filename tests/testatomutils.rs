@@ -1,4 +1,5 @@
 use lv2_raw::*;
+use std::ffi::c_void;
 use std::mem;
 
 const BUFFER_SIZE: usize = 64; // Need to define constant for buffer allocation
@@ -72,19 +73,19 @@ fn get_buf() -> State {
 
     let mut state = State { buf, current: 0 };
 
-    let p = &sequence as *const LV2AtomSequence as *const libc::c_void;
+    let p = &sequence as *const LV2AtomSequence as *const c_void;
     state.append(p, s_seq);
 
     // Event 1
-    let p = &event1 as *const LV2AtomEvent as *const libc::c_void;
+    let p = &event1 as *const LV2AtomEvent as *const c_void;
     state.append(p, s_ev);
-    let p = &ATOM_DATA_1 as *const u64 as *const libc::c_void;
+    let p = &ATOM_DATA_1 as *const u64 as *const c_void;
     state.append(p, s_atom);
 
     // Event 2
-    let p = &event2 as *const LV2AtomEvent as *const libc::c_void;
+    let p = &event2 as *const LV2AtomEvent as *const c_void;
     state.append(p, s_ev);
-    let p = &ATOM_DATA_2 as *const u64 as *const libc::c_void;
+    let p = &ATOM_DATA_2 as *const u64 as *const c_void;
     state.append(p, s_atom);
 
     state
@@ -138,10 +139,14 @@ struct State {
 }
 
 impl State {
-    fn append(&mut self, p: *const libc::c_void, size: isize) {
-        let p1 = &mut self.buf[0] as *mut u8 as *mut libc::c_void;
+    fn append(&mut self, p: *const c_void, size: isize) {
+        let p1 = &mut self.buf[0] as *mut u8 as *mut c_void;
         unsafe {
-            libc::memcpy(p1.offset(self.current), p, size as usize);
+            std::ptr::copy_nonoverlapping(
+                p as *const u8,
+                (p1 as *mut u8).offset(self.current),
+                size as usize,
+            );
         }
         self.current += size;
     }
@@ -0,0 +1,162 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::PortDescription;
+#[cfg(unix)]
+use lv2_raw::host::{BufferError, FeatureList, Instance, Library};
+use lv2_raw::host::{PortBuffers, PortInfo, UridMapper};
+use lv2_raw::*;
+
+mod common;
+#[cfg(unix)]
+use common::example_library;
+
+#[cfg(unix)]
+fn amp_ports() -> Vec<PortInfo> {
+    vec![
+        PortInfo::new(PortType::Control, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ]
+}
+
+#[cfg(unix)]
+#[test]
+fn run_amp() {
+    let mapper = UridMapper::new();
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:amp")
+        .unwrap();
+    let features = FeatureList::new();
+    let mut instance = Instance::new(&plugin, 48000.0, features.features(), amp_ports()).unwrap();
+    let mut buffers = PortBuffers::new(instance.ports(), 4, 1024, &mapper.map_feature());
+
+    unsafe { buffers.connect(&mut instance).unwrap() };
+    assert!(buffers.set_control(0, 2.0));
+    assert!(!buffers.set_control(1, 2.0));
+    buffers
+        .samples_mut(1)
+        .unwrap()
+        .copy_from_slice(&[1.0, 2.0, 3.0, 4.0]);
+    instance.activate().unwrap();
+    instance.run(4).unwrap();
+    assert_eq!(buffers.samples(2).unwrap(), [2.0, 4.0, 6.0, 8.0]);
+
+    // Growing the block reallocates, and reconnects.
+    unsafe { buffers.set_block_size(&mut instance, 6).unwrap() };
+    buffers.samples_mut(1).unwrap().fill(0.5);
+    instance.run(6).unwrap();
+    assert_eq!(buffers.samples(2).unwrap(), [1.0; 6]);
+
+    // In place.
+    unsafe { buffers.share(&mut instance, 1, 2).unwrap() };
+    assert!(buffers.is_shared(2));
+    instance.run(6).unwrap();
+    assert_eq!(buffers.samples(1).unwrap(), [1.0; 6]);
+    assert_eq!(buffers.samples(2).unwrap(), [1.0; 6]);
+
+    unsafe {
+        assert_eq!(
+            buffers.share(&mut instance, 0, 2),
+            Err(BufferError::CanNotShare {
+                input: 0,
+                output: 2
+            })
+        );
+        assert_eq!(
+            buffers.share(&mut instance, 2, 1),
+            Err(BufferError::CanNotShare {
+                input: 2,
+                output: 1
+            })
+        );
+        assert_eq!(
+            buffers.share(&mut instance, 9, 10),
+            Err(BufferError::NoSuchPort(9))
+        );
+        assert_eq!(
+            buffers.share(&mut instance, 1, 9),
+            Err(BufferError::NoSuchPort(9))
+        );
+        buffers.unshare(&mut instance, 2).unwrap();
+    }
+    assert!(!buffers.is_shared(2));
+    instance.run(6).unwrap();
+    assert_eq!(buffers.samples(1).unwrap(), [1.0; 6]);
+    assert_eq!(buffers.samples(2).unwrap(), [2.0; 6]);
+}
+
+#[test]
+fn atom_buffers() {
+    let mapper = UridMapper::new();
+    let ports = [
+        PortInfo::new(PortType::Atom, PortDirection::Input),
+        PortInfo::new(PortType::Atom, PortDirection::Output),
+        PortInfo::new(PortType::Cv, PortDirection::Output),
+    ];
+    let mut buffers = PortBuffers::new(&ports, 16, 256, &mapper.map_feature());
+    assert_eq!(buffers.samples(2).unwrap().len(), 16);
+    assert!(buffers.control(2).is_none());
+
+    let input = unsafe { &*buffers.sequence(0).unwrap() };
+    assert_eq!(input.atom.type_, mapper.map(LV2_ATOM__SEQUENCE));
+    assert_eq!(input.atom.size, 8);
+    let output = unsafe { &*buffers.sequence(1).unwrap() };
+    assert_eq!(output.atom.type_, mapper.map(LV2_ATOM__CHUNK));
+    assert_eq!(output.atom.size, 256 - 8);
+
+    let event = LV2AtomEvent {
+        time_in_frames: 3,
+        body: LV2Atom {
+            size: 0,
+            type_: mapper.map(LV2_MIDI__MIDIEVENT),
+        },
+    };
+    let capacity = buffers.atom_capacity();
+    let sequence = buffers.sequence_mut(0).unwrap();
+    assert!(!unsafe { lv2_atom_sequence_append_event(sequence, capacity, &event) }.is_null());
+    assert_eq!(unsafe { (*buffers.sequence(0).unwrap()).atom.size }, 8 + 16);
+    assert_eq!(buffers.atom_bytes(0).unwrap().len(), 256);
+
+    buffers.prepare_atoms();
+    assert_eq!(unsafe { (*buffers.sequence(0).unwrap()).atom.size }, 8);
+}
+
+#[test]
+fn cv_buffers() {
+    let mapper = UridMapper::new();
+    let ports = [
+        PortInfo::new(PortType::Control, PortDirection::Input),
+        PortInfo::new(PortType::Cv, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Cv, PortDirection::Input),
+    ];
+    let mut buffers = PortBuffers::new(&ports, 4, 256, &mapper.map_feature());
+    assert_eq!(buffers.samples(1).unwrap(), [0.0; 4]);
+
+    assert!(buffers.set_cv(1, 0.5));
+    assert_eq!(buffers.samples(1).unwrap(), [0.5; 4]);
+    assert!(!buffers.set_cv(0, 0.5));
+    assert!(!buffers.set_cv(2, 0.5));
+    assert!(!buffers.set_default(2, 0.5));
+    assert_eq!(buffers.samples(2).unwrap(), [0.0; 4]);
+
+    let port = |index, symbol: &str, port_type, default| PortDescription {
+        index,
+        symbol: symbol.to_owned(),
+        port_type: Some(port_type),
+        direction: Some(PortDirection::Input),
+        default,
+        ..Default::default()
+    };
+    buffers.set_defaults(&[
+        port(0, "level", PortType::Control, Some(0.25)),
+        port(1, "pitch", PortType::Cv, Some(-1.0)),
+        port(2, "in", PortType::Audio, Some(1.0)),
+        port(3, "mod", PortType::Cv, None),
+    ]);
+    assert_eq!(buffers.control(0), Some(0.25));
+    assert_eq!(buffers.samples(1).unwrap(), [-1.0; 4]);
+    assert_eq!(buffers.samples(2).unwrap(), [0.0; 4]);
+    assert_eq!(buffers.samples(3).unwrap(), [0.0; 4]);
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::{Bundle, BundleError, file_uri_to_path, path_to_file_uri};
+use lv2_raw::*;
+use std::fs;
+
+mod common;
+use common::temp_dir;
+
+const MANIFEST: &str = r#"
+@prefix lv2:  <http://lv2plug.in/ns/lv2core#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+<urn:test:amp>
+    a lv2:Plugin ;
+    lv2:binary <amp.so> ;
+    rdfs:seeAlso <amp.ttl> .
+"#;
+
+const DATA: &str = r#"
+@prefix doap: <http://usefulinc.com/ns/doap#> .
+@prefix lv2:  <http://lv2plug.in/ns/lv2core#> .
+@prefix urid: <http://lv2plug.in/ns/ext/urid#> .
+@prefix pprops: <http://lv2plug.in/ns/ext/port-props#> .
+
+<urn:test:amp>
+    doap:name "Simple Amp" ;
+    lv2:requiredFeature urid:map ;
+    lv2:optionalFeature lv2:hardRTCapable ;
+    lv2:extensionData <http://lv2plug.in/ns/ext/state#interface> ;
+    lv2:port [
+        a lv2:AudioPort , lv2:OutputPort ;
+        lv2:index 2 ;
+        lv2:symbol "out" ;
+        lv2:name "Out"
+    ] , [
+        a lv2:InputPort , lv2:ControlPort ;
+        lv2:index 0 ;
+        lv2:symbol "gain" ;
+        lv2:name "Gain" ;
+        lv2:default 0.0 ;
+        lv2:minimum -90 ;
+        lv2:maximum 24.0 ;
+        lv2:portProperty pprops:logarithmic , lv2:connectionOptional
+    ] , [
+        a lv2:AudioPort , lv2:InputPort ;
+        lv2:index 1 ;
+        lv2:symbol "in" ;
+        lv2:name "In"
+    ] .
+"#;
+
+#[test]
+fn load_bundle() {
+    let dir = temp_dir("bundle").join("amp.lv2");
+    fs::create_dir(&dir).unwrap();
+    fs::write(dir.join("manifest.ttl"), MANIFEST).unwrap();
+    fs::write(dir.join("amp.ttl"), DATA).unwrap();
+
+    let bundle = Bundle::load(&dir).unwrap();
+    assert_eq!(bundle.plugins.len(), 1);
+    let amp = bundle.plugin("urn:test:amp").unwrap();
+    assert_eq!(amp.name.as_deref(), Some("Simple Amp"));
+    assert_eq!(amp.binary.as_deref(), Some(dir.join("amp.so").as_path()));
+    assert_eq!(amp.data_files, [dir.join("amp.ttl")]);
+    assert_eq!(amp.required_features, [LV2_URID__MAP.as_str()]);
+    assert_eq!(amp.optional_features, [LV2_CORE__HARDRTCAPABLE.as_str()]);
+    assert_eq!(amp.extension_data, [LV2_STATE__INTERFACE.as_str()]);
+
+    let symbols: Vec<_> = amp.ports.iter().map(|p| p.symbol.as_str()).collect();
+    assert_eq!(symbols, ["gain", "in", "out"]);
+    let gain = amp.port_by_symbol("gain").unwrap();
+    assert_eq!(gain.name.as_deref(), Some("Gain"));
+    assert_eq!(gain.port_type, Some(PortType::Control));
+    assert_eq!(gain.direction, Some(PortDirection::Input));
+    assert_eq!(
+        (gain.default, gain.minimum, gain.maximum),
+        (Some(0.0), Some(-90.0), Some(24.0))
+    );
+    assert!(gain.has_property("http://lv2plug.in/ns/ext/port-props#logarithmic"));
+
+    let infos = amp.port_infos().unwrap();
+    assert_eq!(infos[0].properties, [PortProperty::ConnectionOptional]);
+    assert_eq!(infos[2].port_type, PortType::Audio);
+    assert!(infos[2].is_output());
+}
+
+#[test]
+fn bundle_errors() {
+    let dir = temp_dir("bundle-errors");
+    match Bundle::load(dir.join("missing.lv2")) {
+        Err(BundleError::Io { path, .. }) => assert!(path.ends_with("manifest.ttl")),
+        other => panic!("unexpected {other:?}"),
+    }
+
+    fs::write(dir.join("manifest.ttl"), MANIFEST).unwrap();
+    fs::write(dir.join("amp.ttl"), "<urn:test:amp> <urn:p> .").unwrap();
+    match Bundle::load(&dir) {
+        Err(BundleError::Turtle { path, error }) => {
+            assert!(path.ends_with("amp.ttl"));
+            assert_eq!(error.line, 1);
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+
+    fs::write(
+        dir.join("amp.ttl"),
+        "<urn:test:amp> <http://lv2plug.in/ns/lv2core#port> [
+            <http://lv2plug.in/ns/lv2core#index> 1 ;
+            <http://lv2plug.in/ns/lv2core#symbol> \"x\" ] .",
+    )
+    .unwrap();
+    let err = Bundle::load(&dir).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "<urn:test:amp>: port indices are not 0..1, found 1"
+    );
+}
+
+#[test]
+fn file_uris() {
+    let path = std::env::temp_dir()
+        .join("a b%c:d.lv2")
+        .join("manifest.ttl");
+    let uri = path_to_file_uri(&path);
+    assert!(uri.starts_with("file:///"), "{uri}");
+    assert!(!uri.contains(' ') && !uri.contains('\\'), "{uri}");
+    assert_eq!(file_uri_to_path(&uri), Some(path));
+
+    let localhost = uri.replacen("file://", "file://localhost", 1);
+    assert!(file_uri_to_path(&localhost).is_some());
+    let remote = uri.replacen("file://", "file://example.org", 1);
+    assert_eq!(file_uri_to_path(&remote), None);
+    assert_eq!(file_uri_to_path("http://example.org/x"), None);
+}
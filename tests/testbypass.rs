@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::PortDescription;
+use lv2_raw::control::ControlPort;
+#[cfg(unix)]
+use lv2_raw::host::{Bypass, FeatureList, Instance, Library, PortBuffers, UridMapper};
+use lv2_raw::host::{PortInfo, enabled_port};
+use lv2_raw::*;
+
+mod common;
+#[cfg(unix)]
+use common::example_library;
+
+fn invert_ports() -> Vec<PortInfo> {
+    vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ]
+}
+
+#[test]
+fn find_enabled_port() {
+    let mut ports = invert_ports();
+    assert_eq!(enabled_port(&ports), None);
+    ports.push(
+        PortInfo::new(PortType::Control, PortDirection::Output).with_designation(LV2_CORE__ENABLED),
+    );
+    assert_eq!(enabled_port(&ports), None);
+    ports.push(
+        PortInfo::new(PortType::Control, PortDirection::Input).with_designation(LV2_CORE__ENABLED),
+    );
+    assert_eq!(enabled_port(&ports), Some(3));
+
+    let port = ControlPort::from_description(&PortDescription {
+        symbol: "enabled".to_owned(),
+        port_type: Some(PortType::Control),
+        direction: Some(PortDirection::Input),
+        designation: Some(LV2_CORE__ENABLED.as_str().to_owned()),
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(port.is_enabled_port());
+}
+
+#[cfg(unix)]
+fn run_invert(ports: Vec<PortInfo>, check: impl FnOnce(&mut Instance<'_>, &mut PortBuffers)) {
+    let mapper = UridMapper::new();
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:invert")
+        .unwrap();
+    let features = FeatureList::new();
+    let mut instance = Instance::new(&plugin, 48000.0, features.features(), ports).unwrap();
+    let mut buffers = PortBuffers::new(instance.ports(), 8, 1024, &mapper.map_feature());
+    unsafe { buffers.connect(&mut instance).unwrap() };
+    buffers.samples_mut(0).unwrap().fill(1.0);
+    instance.activate().unwrap();
+    check(&mut instance, &mut buffers);
+}
+
+#[cfg(unix)]
+#[test]
+fn host_crossfade() {
+    run_invert(invert_ports(), |instance, buffers| {
+        let mut bypass = Bypass::new(instance.ports(), 8, 4);
+        assert_eq!(bypass.enabled_port(), None);
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(buffers.samples(1).unwrap(), [-1.0; 8]);
+
+        bypass.set_enabled(false);
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(
+            buffers.samples(1).unwrap(),
+            [-0.5, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0]
+        );
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(buffers.samples(1).unwrap(), [1.0; 8]);
+
+        bypass.set_enabled(true);
+        bypass.run(instance, buffers, 2).unwrap();
+        assert_eq!(&buffers.samples(1).unwrap()[..2], [0.5, 0.0]);
+        bypass.settle();
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(buffers.samples(1).unwrap(), [-1.0; 8]);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn drives_enabled_port() {
+    let mut ports = invert_ports();
+    ports.push(
+        PortInfo::new(PortType::Control, PortDirection::Input).with_designation(LV2_CORE__ENABLED),
+    );
+    run_invert(ports, |instance, buffers| {
+        let mut bypass = Bypass::new(instance.ports(), 8, 4);
+        assert_eq!(bypass.enabled_port(), Some(2));
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(buffers.control(2), Some(1.0));
+
+        // The plugin does the fade, so its output is left alone.
+        bypass.set_enabled(false);
+        assert!(!bypass.is_enabled());
+        bypass.run(instance, buffers, 8).unwrap();
+        assert_eq!(buffers.control(2), Some(0.0));
+        assert_eq!(buffers.samples(1).unwrap(), [-1.0; 8]);
+    });
+}
@@ -0,0 +1,242 @@
+#![cfg(feature = "std")]
+
+#[cfg(unix)]
+use lv2_raw::host::Library;
+use lv2_raw::host::{Check, Conformance, FeatureList, PortInfo, RtCheckAllocator, UridMapper};
+use lv2_raw::plugin::plugin_descriptor;
+use lv2_raw::*;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+
+mod common;
+#[cfg(unix)]
+use common::example_library;
+
+#[global_allocator]
+static ALLOCATOR: RtCheckAllocator = RtCheckAllocator::new();
+
+/// Breaks several rules: it writes past short blocks, can not run in place,
+/// and claims to support every extension.
+struct Sloppy {
+    input: *const f32,
+    output: *mut f32,
+}
+
+impl Plugin for Sloppy {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:sloppy");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+    ];
+
+    fn instantiate(_: f64, _: &CStr, _: Features<'_>) -> Option<Self> {
+        Some(Sloppy {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        let n = sample_count as usize;
+        // Short blocks always fit in the buffers of the harness.
+        let end = if n < 8 { n + 1 } else { n };
+        unsafe {
+            for i in 0..end {
+                *self.output.add(i) = 0.0;
+            }
+            for i in 0..n {
+                *self.output.add(i) += *self.input.add(i);
+            }
+        }
+    }
+
+    fn extension_data(_: &CStr) -> *const c_void {
+        c"anything".as_ptr() as *const c_void
+    }
+}
+
+/// Copies its atom input to its atom output.
+struct Thru {
+    input: *const LV2AtomSequence,
+    output: *mut LV2AtomSequence,
+}
+
+impl Plugin for Thru {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:thru");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Atom, PortDirection::Input),
+        (PortType::Atom, PortDirection::Output),
+    ];
+
+    fn instantiate(_: f64, _: &CStr, _: Features<'_>) -> Option<Self> {
+        Some(Thru {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const LV2AtomSequence,
+            1 => self.output = data as *mut LV2AtomSequence,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, _: u32) {
+        unsafe {
+            let capacity = (*self.output).atom.size;
+            let size = (*self.input).atom.size;
+            assert!(size <= capacity);
+            ptr::copy_nonoverlapping(
+                self.input as *const u8,
+                self.output as *mut u8,
+                size_of::<LV2Atom>() + size as usize,
+            );
+        }
+    }
+}
+
+/// Claims to have written more than fits in its atom output.
+struct Overflow {
+    input: *const LV2AtomSequence,
+    output: *mut LV2AtomSequence,
+}
+
+impl Plugin for Overflow {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:overflow");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Atom, PortDirection::Input),
+        (PortType::Atom, PortDirection::Output),
+    ];
+
+    fn instantiate(_: f64, _: &CStr, _: Features<'_>) -> Option<Self> {
+        Some(Overflow {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const LV2AtomSequence,
+            1 => self.output = data as *mut LV2AtomSequence,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, _: u32) {
+        unsafe {
+            (*self.output).atom.type_ = (*self.input).atom.type_;
+            (*self.output).atom.size += 8;
+        }
+    }
+}
+
+static SLOPPY: LV2Descriptor = plugin_descriptor::<Sloppy>();
+static THRU: LV2Descriptor = plugin_descriptor::<Thru>();
+static OVERFLOW: LV2Descriptor = plugin_descriptor::<Overflow>();
+
+#[cfg(unix)]
+#[test]
+fn amp_conforms() {
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:amp")
+        .unwrap();
+    let features = FeatureList::new();
+    let ports = vec![
+        PortInfo::new(PortType::Control, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ];
+    let mut harness = Conformance::for_plugin(&plugin, features.features(), ports);
+    harness.control(0, 0.5).block_size(64).hard_rt_capable();
+    let report = harness.run();
+    assert!(report.is_ok(), "{report}");
+    assert_eq!(report.checks, Check::ALL);
+}
+
+#[test]
+fn violations() {
+    let features = FeatureList::new();
+    let ports = vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ];
+    let report = Conformance::new(&SLOPPY, c"/", features.features(), ports.clone()).run();
+    assert!(!report.is_ok());
+
+    assert_eq!(
+        report.violations_of(Check::Instantiate).count(),
+        0,
+        "{report}"
+    );
+    assert!(
+        report
+            .violations_of(Check::RunZero)
+            .any(|v| v.message == "port 1: wrote frame 0 of a 0 frame block"),
+        "{report}"
+    );
+    assert!(
+        report
+            .violations_of(Check::BlockSizes)
+            .any(|v| v.message == "port 1: wrote frame 1 of a 1 frame block"),
+        "{report}"
+    );
+    assert!(
+        report.violations_of(Check::InPlace).any(|v| {
+            v.message
+                .starts_with("port 1 sharing the buffer of port 0 differs")
+        }),
+        "{report}"
+    );
+    assert_eq!(
+        report.violations_of(Check::ExtensionData).count(),
+        1,
+        "{report}"
+    );
+    assert_eq!(
+        report.violations_of(Check::ActivationCycles).count(),
+        0,
+        "{report}"
+    );
+
+    let mut harness = Conformance::new(&SLOPPY, c"/", features.features(), ports);
+    harness.in_place_broken();
+    let report = harness.run();
+    assert!(!report.checks.contains(&Check::InPlace));
+}
+
+#[test]
+fn atom_sequences() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper);
+    let ports = vec![
+        PortInfo::new(PortType::Atom, PortDirection::Input),
+        PortInfo::new(PortType::Atom, PortDirection::Output),
+    ];
+    let mut harness = Conformance::new(&THRU, c"/", features.features(), ports.clone());
+    harness.atom_capacity(1024);
+    let report = harness.run();
+    assert!(report.is_ok(), "{report}");
+
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper);
+    let report = Conformance::new(&OVERFLOW, c"/", features.features(), ports).run();
+    assert!(
+        report.violations_of(Check::EmptyAtoms).any(
+            |v| v.message == "port 1: atom output of 8200 bytes overflows its 8192 byte buffer"
+        )
+    );
+}
@@ -0,0 +1,187 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::{Bundle, PluginDescription, PortDescription, ScalePoint, write_bundle};
+use lv2_raw::control::ControlPort;
+use lv2_raw::*;
+
+mod common;
+use common::temp_dir;
+
+fn control(properties: &[Uri], minimum: f32, maximum: f32) -> PortDescription {
+    PortDescription {
+        symbol: "control".to_owned(),
+        port_type: Some(PortType::Control),
+        direction: Some(PortDirection::Input),
+        minimum: Some(minimum),
+        maximum: Some(maximum),
+        properties: properties.iter().map(|p| p.as_str().to_owned()).collect(),
+        ..Default::default()
+    }
+}
+
+fn mode() -> PortDescription {
+    PortDescription {
+        scale_points: vec![
+            ScalePoint {
+                value: 2.0,
+                label: "Band-pass".to_owned(),
+            },
+            ScalePoint {
+                value: 0.0,
+                label: "Low-pass".to_owned(),
+            },
+            ScalePoint {
+                value: 1.0,
+                label: "High-pass".to_owned(),
+            },
+        ],
+        ..control(&[LV2_CORE__ENUMERATION, LV2_CORE__INTEGER], 0.0, 2.0)
+    }
+}
+
+#[test]
+fn defaults() {
+    let port = ControlPort::from_description(&PortDescription {
+        minimum: None,
+        maximum: None,
+        ..control(&[], 0.0, 0.0)
+    })
+    .unwrap();
+    assert_eq!((port.minimum, port.maximum, port.default), (0.0, 1.0, 0.0));
+    assert_eq!(port.name, "control");
+    assert!(port.is_input());
+
+    let port = ControlPort::from_description(&PortDescription {
+        default: Some(-5.0),
+        ..control(&[], 10.0, -10.0)
+    })
+    .unwrap();
+    assert_eq!(
+        (port.minimum, port.maximum, port.default),
+        (-10.0, 10.0, -5.0)
+    );
+
+    let audio = PortDescription {
+        port_type: Some(PortType::Audio),
+        ..control(&[], 0.0, 1.0)
+    };
+    assert_eq!(ControlPort::from_description(&audio), None);
+}
+
+#[test]
+fn clamp_and_quantize() {
+    let linear = ControlPort::from_description(&control(&[], -1.0, 1.0)).unwrap();
+    assert_eq!(linear.clamp(2.0), 1.0);
+    assert_eq!(linear.clamp(f32::NAN), -1.0);
+    assert_eq!(linear.quantize(0.3), 0.3);
+
+    // Bounds the wrong way round, e.g. at a negative sample rate, are not
+    // worth a panic.
+    let reversed = ControlPort {
+        minimum: 1.0,
+        maximum: -1.0,
+        ..linear.clone()
+    };
+    assert_eq!(reversed.clamp(0.0), -1.0);
+    assert_eq!(reversed.quantize(5.0), -1.0);
+
+    let integer = ControlPort::from_description(&control(&[LV2_CORE__INTEGER], 0.0, 8.0)).unwrap();
+    assert_eq!(integer.quantize(2.6), 3.0);
+    assert_eq!(integer.quantize(9.0), 8.0);
+
+    let toggle = ControlPort::from_description(&control(&[LV2_CORE__TOGGLED], 0.0, 1.0)).unwrap();
+    assert_eq!(toggle.quantize(0.4), 0.0);
+    assert_eq!(toggle.quantize(0.6), 1.0);
+
+    let mode = ControlPort::from_description(&mode()).unwrap();
+    assert_eq!(mode.quantize(1.4), 1.0);
+    assert_eq!(mode.quantize(1.6), 2.0);
+    assert_eq!(mode.label(0.0), Some("Low-pass"));
+    assert_eq!(mode.label(0.5), None);
+    let labels: Vec<_> = mode.scale_points.iter().map(|p| p.label.as_str()).collect();
+    assert_eq!(labels, ["Low-pass", "High-pass", "Band-pass"]);
+}
+
+#[test]
+fn normalize() {
+    let linear = ControlPort::from_description(&control(&[], -10.0, 10.0)).unwrap();
+    assert_eq!(linear.normalize(0.0), 0.5);
+    assert_eq!(linear.normalize(20.0), 1.0);
+    assert_eq!(linear.denormalize(0.25), -5.0);
+
+    let log = ControlPort::from_description(&control(&[LV2_PORT_PROPS__LOGARITHMIC], 10.0, 1000.0))
+        .unwrap();
+    assert!((log.normalize(100.0) - 0.5).abs() < 1e-6);
+    assert!((log.denormalize(0.5) - 100.0).abs() < 1e-3);
+    for position in [0.0, 0.1, 0.7, 1.0] {
+        let value = log.denormalize(position);
+        assert!((log.normalize(value) - position).abs() < 1e-5);
+    }
+
+    // There is no logarithmic scale through zero, so this one is linear.
+    let through_zero =
+        ControlPort::from_description(&control(&[LV2_PORT_PROPS__LOGARITHMIC], 0.0, 4.0)).unwrap();
+    assert_eq!(through_zero.normalize(1.0), 0.25);
+
+    let integer = ControlPort::from_description(&control(&[LV2_CORE__INTEGER], 0.0, 3.0)).unwrap();
+    assert_eq!(integer.denormalize(0.4), 1.0);
+}
+
+#[test]
+fn sample_rate() {
+    let port = ControlPort::from_description(&PortDescription {
+        default: Some(0.25),
+        ..control(&[LV2_CORE__SAMPLERATE], 0.0, 0.5)
+    })
+    .unwrap();
+    let absolute = port.at_sample_rate(48000.0);
+    assert_eq!(
+        (absolute.minimum, absolute.maximum, absolute.default),
+        (0.0, 24000.0, 12000.0)
+    );
+    assert_eq!(absolute.at_sample_rate(48000.0), absolute);
+    let negative = port.at_sample_rate(-2.0);
+    assert_eq!((negative.minimum, negative.maximum), (-1.0, 0.0));
+}
+
+#[test]
+fn bundle_round_trip() {
+    let dir = temp_dir("control-bundle");
+    let plugin = PluginDescription {
+        uri: "urn:test:filter".to_owned(),
+        ports: vec![
+            PortDescription {
+                index: 0,
+                symbol: "mode".to_owned(),
+                name: Some("Mode".to_owned()),
+                ..mode()
+            },
+            PortDescription {
+                index: 1,
+                symbol: "cutoff".to_owned(),
+                default: Some(1000.0),
+                unit: Some(LV2_UNITS__HZ.as_str().to_owned()),
+                ..control(&[LV2_PORT_PROPS__LOGARITHMIC], 20.0, 20000.0)
+            },
+        ],
+        ..Default::default()
+    };
+    write_bundle(&dir, std::slice::from_ref(&plugin)).unwrap();
+
+    let bundle = Bundle::load(&dir).unwrap();
+    let loaded = bundle.plugin("urn:test:filter").unwrap();
+    let mut scale_points = loaded.ports[0].scale_points.clone();
+    scale_points.sort_by(|a, b| a.value.total_cmp(&b.value));
+    let mut expected = plugin.ports[0].scale_points.clone();
+    expected.sort_by(|a, b| a.value.total_cmp(&b.value));
+    assert_eq!(scale_points, expected);
+
+    let controls = loaded.controls();
+    assert_eq!(controls.len(), 2);
+    assert_eq!(controls[0].name, "Mode");
+    assert!(controls[0].enumeration && controls[0].integer);
+    assert_eq!(controls[1].unit, Some(Unit::Hz));
+    assert_eq!(Unit::Hz.symbol(), "Hz");
+    assert!(controls[1].logarithmic);
+    assert_eq!(controls[1].default, 1000.0);
+}
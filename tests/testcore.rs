@@ -0,0 +1,41 @@
+use lv2_raw::*;
+
+#[test]
+fn port_enums_round_trip() {
+    for t in PortType::ALL {
+        assert_eq!(PortType::from_uri(t.uri().as_str()), Some(t));
+    }
+    for d in PortDirection::ALL {
+        assert_eq!(PortDirection::from_uri(d.uri().as_str()), Some(d));
+    }
+    for p in PortProperty::ALL {
+        assert_eq!(PortProperty::from_uri(p.uri().as_str()), Some(p));
+    }
+
+    assert_eq!(
+        PortType::from_uri("http://lv2plug.in/ns/lv2core#CVPort"),
+        Some(PortType::Cv)
+    );
+    assert_eq!(PortType::Atom.uri(), LV2_ATOM__ATOMPORT);
+    assert_eq!(
+        PortProperty::from_uri("http://lv2plug.in/ns/lv2core#connectionOptional"),
+        Some(PortProperty::ConnectionOptional)
+    );
+    assert_eq!(PortType::from_uri(LV2_CORE__INPUTPORT.as_str()), None);
+    assert_eq!(
+        PortProperty::from_uri(LV2_CORE__HARDRTCAPABLE.as_str()),
+        None
+    );
+}
+
+#[test]
+fn core_uris() {
+    assert_eq!(LV2_CORE__PORT, "http://lv2plug.in/ns/lv2core#Port");
+    assert_eq!(LV2_CORE___PORT, "http://lv2plug.in/ns/lv2core#port");
+    assert_eq!(LV2_CORE__ENABLED, "http://lv2plug.in/ns/lv2core#enabled");
+    assert!(
+        LV2_CORE__LATENCY
+            .as_str()
+            .starts_with(LV2_CORE_PREFIX.as_str())
+    );
+}
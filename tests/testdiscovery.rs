@@ -0,0 +1,67 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{PluginIndex, default_lv2_path, lv2_path};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::temp_dir;
+
+fn write_bundle(dir: &Path, name: &str, manifest: &str) -> PathBuf {
+    let bundle = dir.join(name);
+    fs::create_dir_all(&bundle).unwrap();
+    fs::write(bundle.join("manifest.ttl"), manifest).unwrap();
+    bundle
+}
+
+fn manifest(uri: &str, binary: &str) -> String {
+    format!(
+        "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+         @prefix doap: <http://usefulinc.com/ns/doap#> .
+         <{uri}> a lv2:Plugin ; lv2:binary <{binary}> ; doap:name \"{binary}\" ."
+    )
+}
+
+#[test]
+fn scan_directories() {
+    let root = temp_dir("discovery");
+    let first = root.join("first");
+    let second = root.join("second");
+
+    let a = write_bundle(&first, "a.lv2", &manifest("urn:test:a", "a.so"));
+    write_bundle(&first, "broken.lv2", "<urn:test:broken> a");
+    write_bundle(
+        &first,
+        "not-a-bundle",
+        &manifest("urn:test:ignored", "x.so"),
+    );
+    fs::create_dir_all(first.join("empty.lv2")).unwrap();
+    let b = write_bundle(&second, "b.lv2", &manifest("urn:test:b", "b.so"));
+    write_bundle(&second, "shadow.lv2", &manifest("urn:test:a", "shadow.so"));
+
+    let index = PluginIndex::scan([first, root.join("missing"), second]);
+    let uris: Vec<_> = index.plugins().map(|p| p.uri()).collect();
+    assert_eq!(uris, ["urn:test:a", "urn:test:b"]);
+    assert_eq!(index.len(), 2);
+
+    let plugin_a = index.get("urn:test:a").unwrap();
+    assert_eq!(plugin_a.bundle_path, a);
+    assert_eq!(plugin_a.binary(), Some(a.join("a.so").as_path()));
+    assert_eq!(plugin_a.description.name.as_deref(), Some("a.so"));
+    assert_eq!(index.get("urn:test:b").unwrap().bundle_path, b);
+    assert!(index.get("urn:test:ignored").is_none());
+
+    assert_eq!(index.errors().len(), 1);
+    let (path, error) = &index.errors()[0];
+    assert!(path.ends_with("broken.lv2"));
+    assert!(error.to_string().contains("manifest.ttl:1:"));
+}
+
+#[test]
+fn search_path() {
+    if std::env::var_os("LV2_PATH").is_none() {
+        assert_eq!(lv2_path(), default_lv2_path());
+    }
+    #[cfg(target_os = "linux")]
+    assert!(default_lv2_path().ends_with(&[PathBuf::from("/usr/lib/lv2")]));
+}
@@ -0,0 +1,163 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{FeatureList, OptionValue, UridMapper};
+use lv2_raw::*;
+use std::ffi::{CStr, c_char, c_void};
+
+extern "C" fn schedule_work(
+    _handle: *mut c_void,
+    _size: u32,
+    _data: *const c_void,
+) -> LV2WorkerStatus {
+    LV2_WORKER_SUCCESS
+}
+
+extern "C" fn make_path(_handle: *mut c_void, _path: *const c_char) -> *mut c_char {
+    std::ptr::null_mut()
+}
+
+#[test]
+fn empty_list_is_a_single_null() {
+    let features = FeatureList::new();
+    assert!(features.is_empty());
+    let ptr = features.as_ptr();
+    assert!(!ptr.is_null());
+    assert!(unsafe { *ptr }.is_null());
+    assert_eq!(features.features().iter().count(), 0);
+}
+
+#[test]
+fn typed_payloads() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features
+        .urid_map(&mapper)
+        .urid_unmap(&mapper)
+        .worker_schedule(LV2WorkerSchedule {
+            handle: std::ptr::null_mut(),
+            schedule_work,
+        })
+        .state_make_path(LV2StateMakePath {
+            handle: std::ptr::null_mut(),
+            path: make_path,
+        })
+        .flag(LV2_CORE__ISLIVE)
+        .option(&mapper, c"urn:test:blockLength", OptionValue::Int(-512))
+        .option(&mapper, c"urn:test:sampleRate", OptionValue::Float(48000.0))
+        .option(&mapper, c"urn:test:frames", OptionValue::Long(1 << 40));
+
+    assert_eq!(features.len(), 6);
+    let view = features.features();
+    let raw = features.as_ptr();
+    unsafe {
+        assert!(!(*raw.add(5)).is_null());
+        assert!((*raw.add(6)).is_null());
+    }
+
+    let map = view.data(LV2_URID__MAP).unwrap().as_ptr() as *const LV2UridMap;
+    let id = unsafe { &*map }.map(LV2_ATOM__SEQUENCE);
+    assert_eq!(id, mapper.map(LV2_ATOM__SEQUENCE));
+    let unmap = view.data(LV2_URID__UNMAP).unwrap().as_ptr() as *const LV2UridUnmap;
+    assert_eq!(
+        unsafe { &*unmap }.unmap(id),
+        Some(LV2_ATOM__SEQUENCE.as_cstr())
+    );
+
+    assert!(view.contains(LV2_CORE__ISLIVE));
+    assert!(view.data(LV2_CORE__ISLIVE).is_none());
+    assert!(view.data(LV2_STATE__MAKEPATH).is_some());
+    assert!(!view.contains(LV2_LOG__LOG));
+
+    let options = view.data(LV2_OPTIONS__OPTIONS).unwrap().as_ptr() as *const LV2OptionsOption;
+    unsafe {
+        let block = &*options;
+        assert_eq!(block.key, mapper.map(c"urn:test:blockLength"));
+        assert_eq!(block.type_, mapper.map(LV2_ATOM__INT));
+        assert_eq!(block.size, 4);
+        assert_eq!(*(block.value as *const i32), -512);
+
+        let rate = &*options.add(1);
+        assert_eq!(rate.type_, mapper.map(LV2_ATOM__FLOAT));
+        assert_eq!(*(rate.value as *const f32), 48000.0);
+
+        let frames = &*options.add(2);
+        assert_eq!(frames.size, 8);
+        assert_eq!(*(frames.value as *const i64), 1 << 40);
+
+        assert_eq!((*options.add(3)).key, 0);
+        assert!((*options.add(3)).value.is_null());
+    }
+}
+
+#[test]
+fn replace_and_remove() {
+    let mut features = FeatureList::new();
+    let mut a = 1u32;
+    let mut b = 2u32;
+    unsafe {
+        features.raw(c"urn:test:feature", &mut a as *mut u32 as *mut c_void);
+        features.raw(c"urn:test:feature", &mut b as *mut u32 as *mut c_void);
+    }
+    assert_eq!(features.len(), 1);
+    let data = features.features().data(c"urn:test:feature").unwrap();
+    assert_eq!(unsafe { *(data.as_ptr() as *const u32) }, 2);
+
+    let uri = unsafe { CStr::from_ptr((**features.as_ptr()).uri) };
+    assert_eq!(uri, c"urn:test:feature");
+
+    assert!(features.remove(c"urn:test:feature"));
+    assert!(!features.remove(c"urn:test:feature"));
+    assert!(unsafe { *features.as_ptr() }.is_null());
+}
+
+#[test]
+fn typed_resolution() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features
+        .urid_map(&mapper)
+        .worker_schedule(LV2WorkerSchedule {
+            handle: std::ptr::null_mut(),
+            schedule_work,
+        })
+        .option(&mapper, c"urn:test:blockLength", OptionValue::Int(64))
+        .option(&mapper, c"urn:test:frames", OptionValue::Long(7));
+    let view = features.features();
+
+    let (map, log, schedule) = view
+        .resolve::<(LV2UridMap, Option<LV2LogLog>, LV2WorkerSchedule)>()
+        .unwrap();
+    assert_eq!(map.map(LV2_ATOM__INT), mapper.map(LV2_ATOM__INT));
+    assert!(log.is_none());
+    assert_eq!(
+        (schedule.schedule_work)(schedule.handle, 0, std::ptr::null()),
+        LV2_WORKER_SUCCESS
+    );
+
+    let options = view.require::<LV2OptionsOption>().unwrap();
+    let keys: Vec<_> = unsafe { options.iter_array() }.map(|o| o.key).collect();
+    assert_eq!(
+        keys,
+        [
+            mapper.map(c"urn:test:blockLength"),
+            mapper.map(c"urn:test:frames")
+        ]
+    );
+
+    let err = view
+        .resolve::<(LV2UridMap, LV2UridUnmap, Option<LV2StateMapPath>, LV2LogLog)>()
+        .err()
+        .unwrap();
+    assert_eq!(err.missing, [LV2_URID__UNMAP, LV2_LOG__LOG]);
+    assert_eq!(
+        err.to_string(),
+        "missing required features: <http://lv2plug.in/ns/ext/urid#unmap>, \
+         <http://lv2plug.in/ns/ext/log#log>"
+    );
+
+    let err = view.require::<LV2StateMakePath>().err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "missing required feature: <http://lv2plug.in/ns/ext/state#makePath>"
+    );
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "std")]
+
+#[cfg(unix)]
+use lv2_raw::host::Library;
+use lv2_raw::host::{FeatureList, Instance, InstanceError, InstanceState, PortInfo};
+use lv2_raw::*;
+use std::cell::RefCell;
+use std::ffi::c_char;
+use std::os::raw::c_void;
+use std::ptr;
+
+mod common;
+#[cfg(unix)]
+use common::example_library;
+
+thread_local! {
+    static CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(call: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(call));
+}
+
+fn take_calls() -> Vec<&'static str> {
+    CALLS.with(|calls| calls.take())
+}
+
+extern "C" fn instantiate(
+    _descriptor: *const LV2Descriptor,
+    _rate: f64,
+    _bundle_path: *const c_char,
+    _features: *const *const LV2Feature,
+) -> LV2Handle {
+    log("instantiate");
+    ptr::NonNull::<c_void>::dangling().as_ptr()
+}
+
+extern "C" fn connect_port(_handle: LV2Handle, _port: u32, _data: *mut c_void) {
+    log("connect_port");
+}
+
+extern "C" fn activate(_handle: LV2Handle) {
+    log("activate");
+}
+
+extern "C" fn run(_handle: LV2Handle, _n_samples: u32) {
+    log("run");
+}
+
+extern "C" fn deactivate(_handle: LV2Handle) {
+    log("deactivate");
+}
+
+extern "C" fn cleanup(_handle: LV2Handle) {
+    log("cleanup");
+}
+
+const LOGGER: LV2Descriptor = LV2Descriptor {
+    uri: c"urn:lv2_raw:test:logger".as_ptr(),
+    instantiate,
+    connect_port,
+    activate: Some(activate),
+    run,
+    deactivate: Some(deactivate),
+    cleanup,
+    extension_data: None,
+};
+
+fn ports() -> Vec<PortInfo> {
+    vec![
+        PortInfo::new(PortType::Control, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output)
+            .with_property(PortProperty::ConnectionOptional),
+    ]
+}
+
+#[test]
+fn lifecycle_rules() {
+    let features = FeatureList::new();
+    let mut control = 0.0f32;
+    let mut instance =
+        Instance::from_descriptor(&LOGGER, 44100.0, c"/tmp/", features.features(), ports())
+            .unwrap();
+    assert_eq!(instance.state(), InstanceState::Inactive);
+
+    assert_eq!(instance.run(1), Err(InstanceError::NotActive));
+    assert_eq!(instance.deactivate(), Err(InstanceError::NotActive));
+    instance.activate().unwrap();
+    assert_eq!(instance.activate(), Err(InstanceError::AlreadyActive));
+
+    let err = instance.run(1).unwrap_err();
+    assert_eq!(err, InstanceError::UnconnectedPorts(vec![0]));
+    assert_eq!(err.to_string(), "required ports are not connected: 0");
+
+    unsafe {
+        assert_eq!(
+            instance.connect_port(2, ptr::null_mut()),
+            Err(InstanceError::NoSuchPort { index: 2, count: 2 })
+        );
+        instance
+            .connect_port(0, &mut control as *mut f32 as *mut c_void)
+            .unwrap();
+    }
+    assert!(instance.is_connected(0));
+    assert!(!instance.is_connected(1));
+    instance.run(1).unwrap();
+    instance.deactivate().unwrap();
+    instance.activate().unwrap();
+    drop(instance);
+
+    assert_eq!(
+        take_calls(),
+        [
+            "instantiate",
+            "activate",
+            "connect_port",
+            "run",
+            "deactivate",
+            "activate",
+            "deactivate",
+            "cleanup"
+        ]
+    );
+}
+
+#[test]
+fn without_activate() {
+    let descriptor = LV2Descriptor {
+        activate: None,
+        deactivate: None,
+        ..LOGGER
+    };
+    let features = FeatureList::new();
+    let mut instance =
+        Instance::from_descriptor(&descriptor, 44100.0, c"/tmp/", features.features(), vec![])
+            .unwrap();
+    instance.run(8).unwrap();
+    instance.activate().unwrap();
+    instance.run(8).unwrap();
+    drop(instance);
+
+    assert_eq!(take_calls(), ["instantiate", "run", "run", "cleanup"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn loaded_plugin() {
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:invert")
+        .unwrap();
+    let features = FeatureList::new();
+    let ports = vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ];
+    let mut instance = Instance::new(&plugin, 48000.0, features.features(), ports).unwrap();
+    drop(plugin);
+
+    let mut buffer = [1.0f32, -0.5];
+    let data = buffer.as_mut_ptr() as *mut c_void;
+    unsafe {
+        instance.connect_port(0, data).unwrap();
+        instance.connect_port(1, data).unwrap();
+    }
+    instance.activate().unwrap();
+    instance.run(2).unwrap();
+    assert_eq!(buffer, [-1.0, 0.5]);
+}
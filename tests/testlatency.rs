@@ -0,0 +1,105 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::{Bundle, PluginDescription, PortDescription, write_bundle};
+#[cfg(unix)]
+use lv2_raw::host::{
+    FeatureList, Instance, InstanceError, Library, PortBuffers, UridMapper, query_latency,
+};
+use lv2_raw::host::{PortInfo, latency_port};
+use lv2_raw::*;
+
+mod common;
+#[cfg(unix)]
+use common::example_library;
+use common::temp_dir;
+
+fn delay_ports() -> Vec<PortInfo> {
+    vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+        PortInfo::new(PortType::Control, PortDirection::Output).with_designation(LV2_CORE__LATENCY),
+    ]
+}
+
+#[test]
+fn find_latency_port() {
+    assert_eq!(latency_port(&delay_ports()), Some(2));
+
+    let legacy = [
+        PortInfo::new(PortType::Control, PortDirection::Input)
+            .with_property(PortProperty::ReportsLatency),
+        PortInfo::new(PortType::Control, PortDirection::Output)
+            .with_property(PortProperty::ReportsLatency),
+    ];
+    assert_eq!(latency_port(&legacy), Some(1));
+
+    let other = [PortInfo::new(PortType::Control, PortDirection::Output)
+        .with_designation(LV2_CORE__FREEWHEELING)];
+    assert_eq!(latency_port(&other), None);
+}
+
+#[test]
+fn designation_round_trip() {
+    let dir = temp_dir("latency-bundle");
+    let plugin = PluginDescription {
+        uri: "urn:test:delay".to_owned(),
+        ports: vec![PortDescription {
+            index: 0,
+            symbol: "latency".to_owned(),
+            port_type: Some(PortType::Control),
+            direction: Some(PortDirection::Output),
+            designation: Some(LV2_CORE__LATENCY.as_str().to_owned()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    write_bundle(&dir, std::slice::from_ref(&plugin)).unwrap();
+
+    let bundle = Bundle::load(&dir).unwrap();
+    let loaded = bundle.plugin("urn:test:delay").unwrap();
+    assert_eq!(loaded.ports, plugin.ports);
+    assert_eq!(loaded.latency_port(), Some(0));
+    assert!(loaded.port_infos().unwrap()[0].reports_latency());
+}
+
+#[cfg(unix)]
+#[test]
+fn query() {
+    let mapper = UridMapper::new();
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:delay")
+        .unwrap();
+    let features = FeatureList::new();
+    let mut instance = Instance::new(&plugin, 48000.0, features.features(), delay_ports()).unwrap();
+    let mut buffers = PortBuffers::new(instance.ports(), 16, 1024, &mapper.map_feature());
+    unsafe { buffers.connect(&mut instance).unwrap() };
+
+    assert_eq!(
+        query_latency(&mut instance, &mut buffers),
+        Err(InstanceError::NotActive)
+    );
+    instance.activate().unwrap();
+    assert_eq!(query_latency(&mut instance, &mut buffers), Ok(Some(64)));
+    assert_eq!(buffers.control(2), Some(64.0));
+}
+
+#[cfg(unix)]
+#[test]
+fn query_without_latency_port() {
+    let mapper = UridMapper::new();
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:invert")
+        .unwrap();
+    let features = FeatureList::new();
+    let ports = vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ];
+    let mut instance = Instance::new(&plugin, 48000.0, features.features(), ports).unwrap();
+    let mut buffers = PortBuffers::new(instance.ports(), 16, 1024, &mapper.map_feature());
+    unsafe { buffers.connect(&mut instance).unwrap() };
+    instance.activate().unwrap();
+    assert_eq!(query_latency(&mut instance, &mut buffers), Ok(None));
+}
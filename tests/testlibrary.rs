@@ -0,0 +1,69 @@
+#![cfg(feature = "std")]
+#![cfg(unix)]
+
+use lv2_raw::host::{Library, LibraryError};
+use std::path::PathBuf;
+use std::ptr;
+
+mod common;
+use common::example_library;
+
+#[test]
+fn enumerate_and_find() {
+    let library = Library::open(example_library()).unwrap();
+    assert!(!library.descriptors().is_lib_descriptor());
+    assert!(library.bundle_path().to_bytes().ends_with(b"/examples/"));
+
+    let uris: Vec<_> = library.plugins().map(|p| p.uri().to_owned()).collect();
+    assert_eq!(
+        uris,
+        [
+            c"urn:lv2_raw:examples:amp".to_owned(),
+            c"urn:lv2_raw:examples:invert".to_owned(),
+            c"urn:lv2_raw:examples:delay".to_owned()
+        ]
+    );
+    assert_eq!(
+        library.plugin(1).unwrap().uri(),
+        c"urn:lv2_raw:examples:invert"
+    );
+    assert!(library.plugin(3).is_none());
+    assert!(library.find(c"urn:lv2_raw:examples:missing").is_none());
+}
+
+#[test]
+fn plugin_keeps_library_loaded() {
+    let plugin = Library::open(example_library())
+        .unwrap()
+        .find(c"urn:lv2_raw:examples:amp")
+        .unwrap();
+
+    let descriptor = plugin.descriptor();
+    let features = [ptr::null()];
+    let bundle_path = plugin.library().bundle_path();
+    let handle =
+        (descriptor.instantiate)(descriptor, 48000.0, bundle_path.as_ptr(), features.as_ptr());
+    assert!(!handle.is_null());
+
+    let mut gain = 0.5f32;
+    let input = [1.0f32, -2.0, 4.0];
+    let mut output = [0.0f32; 3];
+    (descriptor.connect_port)(handle, 0, &mut gain as *mut f32 as *mut _);
+    (descriptor.connect_port)(handle, 1, input.as_ptr() as *mut _);
+    (descriptor.connect_port)(handle, 2, output.as_mut_ptr() as *mut _);
+    (descriptor.run)(handle, 3);
+    (descriptor.cleanup)(handle);
+    assert_eq!(output, [0.5, -1.0, 2.0]);
+}
+
+#[test]
+fn open_errors() {
+    let missing = PathBuf::from("/nonexistent/lib.so");
+    match Library::open(&missing) {
+        Err(LibraryError::Open { path, message }) => {
+            assert_eq!(path, missing);
+            assert!(!message.is_empty());
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+}
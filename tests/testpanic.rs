@@ -0,0 +1,239 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{FeatureList, UridMapper};
+use lv2_raw::plugin::plugin_descriptor;
+use lv2_raw::*;
+use std::ffi::{CStr, c_char, c_void};
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+static EVENTS: AtomicUsize = AtomicUsize::new(0);
+static WRITES: Mutex<Vec<(u32, f32)>> = Mutex::new(Vec::new());
+
+/// Copies its input, and panics on blocks of 3 frames.
+struct Fragile {
+    input: *const f32,
+    output: *mut f32,
+}
+
+impl Plugin for Fragile {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:fragile");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+        (PortType::Atom, PortDirection::Output),
+    ];
+
+    fn instantiate(_: f64, bundle_path: &CStr, _: Features<'_>) -> Option<Self> {
+        assert_ne!(bundle_path, c"/panic/");
+        Some(Fragile {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        assert_ne!(sample_count, 3, "three frames");
+        unsafe { ptr::copy_nonoverlapping(self.input, self.output, sample_count as usize) };
+    }
+
+    fn extension_data(uri: &CStr) -> *const c_void {
+        assert_ne!(uri, c"urn:lv2_raw:test:panic");
+        ptr::null()
+    }
+}
+
+impl Drop for Fragile {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+static FRAGILE: LV2Descriptor = plugin_descriptor::<Fragile>();
+
+/// Panics on events of port 1.
+struct FragileUi {
+    controller: UiController,
+}
+
+impl PluginUi for FragileUi {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:fragile#ui");
+
+    fn instantiate(
+        plugin_uri: &CStr,
+        _: &CStr,
+        controller: UiController,
+        _: Features<'_>,
+    ) -> Option<(Self, LV2UIWidget)> {
+        assert_eq!(plugin_uri, Fragile::URI.as_cstr());
+        Some((FragileUi { controller }, 42 as LV2UIWidget))
+    }
+
+    fn port_event(&mut self, port_index: u32, _: u32, _: u32, buffer: *const c_void) {
+        EVENTS.fetch_add(1, Ordering::SeqCst);
+        assert_ne!(port_index, 1);
+        self.controller
+            .write_control(port_index, unsafe { *(buffer as *const f32) } * 2.0);
+    }
+}
+
+lv2_raw::lv2ui_descriptors!(FragileUi);
+
+extern "C" fn write(
+    controller: LV2UIControllerRaw,
+    port_index: u32,
+    buffer_size: u32,
+    protocol: u32,
+    buffer: *const c_void,
+) {
+    assert_eq!(controller as usize, 7);
+    assert_eq!((buffer_size, protocol), (4, 0));
+    let value = unsafe { *(buffer as *const f32) };
+    WRITES.lock().unwrap().push((port_index, value));
+}
+
+#[test]
+fn plugin() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper);
+    let instantiate = |bundle_path: &CStr| {
+        (FRAGILE.instantiate)(&FRAGILE, 48000.0, bundle_path.as_ptr(), features.as_ptr())
+    };
+    assert!(instantiate(c"/panic/").is_null());
+    let handle = instantiate(c"/");
+    assert!(!handle.is_null());
+
+    let input = [1.0f32; 8];
+    let mut output = [0.5f32; 8];
+    let mut atom = [0u64; 8];
+    atom[0] = 48;
+    (FRAGILE.connect_port)(handle, 0, input.as_ptr() as *mut c_void);
+    (FRAGILE.connect_port)(handle, 1, output.as_mut_ptr() as *mut c_void);
+    (FRAGILE.connect_port)(handle, 2, atom.as_mut_ptr() as *mut c_void);
+    (FRAGILE.activate.unwrap())(handle);
+
+    let runs = RUNS.load(Ordering::SeqCst);
+    (FRAGILE.run)(handle, 2);
+    assert_eq!(output, [1.0, 1.0, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5]);
+    output.fill(0.5);
+    (FRAGILE.run)(handle, 3);
+    assert_eq!(output, [0.0, 0.0, 0.0, 0.5, 0.5, 0.5, 0.5, 0.5]);
+    let sequence = unsafe { &*(atom.as_ptr() as *const LV2AtomSequence) };
+    assert_eq!(sequence.atom.size, 8);
+    assert_eq!(sequence.atom.type_, mapper.map(LV2_ATOM__SEQUENCE));
+
+    // Faulted: the plugin is not called again.
+    (FRAGILE.run)(handle, 8);
+    assert_eq!(output, [0.0; 8]);
+    assert_eq!(RUNS.load(Ordering::SeqCst), runs + 2);
+    (FRAGILE.deactivate.unwrap())(handle);
+    let drops = DROPS.load(Ordering::SeqCst);
+    (FRAGILE.cleanup)(handle);
+    assert_eq!(DROPS.load(Ordering::SeqCst), drops + 1);
+
+    let extension_data = FRAGILE.extension_data.unwrap();
+    assert!(extension_data(c"urn:lv2_raw:test:panic".as_ptr()).is_null());
+}
+
+#[test]
+fn ui() {
+    let descriptor = unsafe { &*lv2ui_descriptor(0) };
+    assert!(lv2ui_descriptor(1).is_null());
+    assert_eq!(
+        unsafe { CStr::from_ptr(descriptor.uri) },
+        FragileUi::URI.as_cstr()
+    );
+
+    let features = FeatureList::new();
+    let mut widget = ptr::null_mut();
+    let instantiate = |plugin_uri: &CStr, widget: *mut LV2UIWidget| {
+        (descriptor.instantiate_raw)(
+            descriptor,
+            plugin_uri.as_ptr(),
+            c"/".as_ptr(),
+            Some(write),
+            7 as LV2UIControllerRaw,
+            widget,
+            features.as_ptr(),
+        )
+    };
+    assert!(instantiate(c"urn:lv2_raw:test:other", &mut widget).is_null());
+    let handle = instantiate(Fragile::URI.as_cstr(), &mut widget);
+    assert!(!handle.is_null());
+    assert_eq!(widget as usize, 42);
+
+    let port_event = descriptor.port_event.unwrap();
+    let value = 0.25f32;
+    let buffer = &value as *const f32 as *const c_void;
+    let events = EVENTS.load(Ordering::SeqCst);
+    port_event(handle, 0, 4, 0, buffer);
+    assert_eq!(*WRITES.lock().unwrap(), [(0, 0.5)]);
+    port_event(handle, 1, 4, 0, buffer);
+    port_event(handle, 0, 4, 0, buffer);
+    assert_eq!(EVENTS.load(Ordering::SeqCst), events + 2);
+    assert_eq!(WRITES.lock().unwrap().len(), 1);
+    (descriptor.cleanup)(handle);
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[test]
+fn log() {
+    static MESSAGES: Mutex<Vec<(LV2Urid, String)>> = Mutex::new(Vec::new());
+
+    // Rust can not define variadic functions, but on x86-64 a variadic call
+    // passes its arguments like a plain one.
+    extern "C" fn printf(
+        _: LV2LogHandle,
+        type_: LV2Urid,
+        fmt: *const c_char,
+        arg: *const c_char,
+    ) -> i32 {
+        assert_eq!(unsafe { CStr::from_ptr(fmt) }, c"%s");
+        let message = unsafe { CStr::from_ptr(arg) }
+            .to_string_lossy()
+            .into_owned();
+        MESSAGES.lock().unwrap().push((type_, message));
+        0
+    }
+    extern "C" fn vprintf(_: LV2LogHandle, _: LV2Urid, _: *const c_char, _: *mut c_void) -> i32 {
+        unreachable!()
+    }
+
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper).log(LV2LogLog {
+        handle: ptr::null_mut(),
+        printf: unsafe {
+            std::mem::transmute::<
+                extern "C" fn(LV2LogHandle, LV2Urid, *const c_char, *const c_char) -> i32,
+                unsafe extern "C" fn(LV2LogHandle, LV2Urid, *const c_char, ...) -> i32,
+            >(printf)
+        },
+        vprintf,
+    });
+    let handle = (FRAGILE.instantiate)(&FRAGILE, 48000.0, c"/panic/".as_ptr(), features.as_ptr());
+    assert!(handle.is_null());
+    let messages = MESSAGES.lock().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].0, mapper.map(LV2_LOG__ERROR));
+    assert!(
+        messages[0]
+            .1
+            .starts_with("urn:lv2_raw:test:fragile: panic in instantiate(): assertion"),
+        "{}",
+        messages[0].1
+    );
+}
@@ -0,0 +1,269 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::*;
+use std::ffi::{CStr, c_void};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Gain {
+    gain: *const f32,
+    input: *const f32,
+    output: *mut f32,
+    activations: u32,
+}
+
+impl Plugin for Gain {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:gain");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Control, PortDirection::Input),
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+    ];
+
+    fn instantiate(rate: f64, bundle_path: &CStr, features: Features<'_>) -> Option<Self> {
+        assert_eq!(rate, 48000.0);
+        assert_eq!(bundle_path, c"/tmp/gain.lv2/");
+        assert_eq!(features.iter().count(), 0);
+        Some(Gain {
+            gain: ptr::null(),
+            input: ptr::null(),
+            output: ptr::null_mut(),
+            activations: 0,
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.gain = data as *const f32,
+            1 => self.input = data as *const f32,
+            2 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn activate(&mut self) {
+        self.activations += 1;
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        assert_eq!(self.activations, 1);
+        let n = sample_count as usize;
+        if n == 0 {
+            return;
+        }
+        unsafe {
+            let gain = *self.gain;
+            let input = std::slice::from_raw_parts(self.input, n);
+            let output = std::slice::from_raw_parts_mut(self.output, n);
+            for (o, i) in output.iter_mut().zip(input) {
+                *o = i * gain;
+            }
+        }
+    }
+
+    fn extension_data(uri: &CStr) -> *const c_void {
+        if uri == c"urn:lv2_raw:test:ext" {
+            &DROPS as *const AtomicUsize as *const c_void
+        } else {
+            ptr::null()
+        }
+    }
+}
+
+impl Drop for Gain {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct Refuses;
+
+impl Plugin for Refuses {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:refuses");
+    const PORTS: &'static [(PortType, PortDirection)] = &[];
+
+    fn instantiate(_: f64, _: &CStr, features: Features<'_>) -> Option<Self> {
+        features.data(LV2_URID__MAP).map(|_| Refuses)
+    }
+
+    fn connect_port(&mut self, _: u32, _: *mut c_void) {}
+
+    fn run(&mut self, _: u32) {}
+}
+
+lv2_raw::lv2_descriptors!(Gain, Refuses);
+
+#[test]
+fn exported_descriptors() {
+    let gain = unsafe { &*lv2_descriptor(0) };
+    let refuses = unsafe { &*lv2_descriptor(1) };
+    assert!(lv2_descriptor(2).is_null());
+    assert_eq!(unsafe { CStr::from_ptr(gain.uri) }, Gain::URI.as_cstr());
+    assert_eq!(
+        unsafe { CStr::from_ptr(refuses.uri) },
+        Refuses::URI.as_cstr()
+    );
+
+    let no_features = [ptr::null::<LV2Feature>()];
+    let handle = (refuses.instantiate)(refuses, 48000.0, c"/".as_ptr(), no_features.as_ptr());
+    assert!(handle.is_null());
+
+    let handle = (gain.instantiate)(
+        gain,
+        48000.0,
+        c"/tmp/gain.lv2/".as_ptr(),
+        no_features.as_ptr(),
+    );
+    assert!(!handle.is_null());
+
+    let mut level = 0.5f32;
+    let input = [1.0f32, 2.0, 3.0, 4.0];
+    let mut output = [0.0f32; 4];
+    (gain.connect_port)(handle, 0, &mut level as *mut f32 as *mut c_void);
+    (gain.connect_port)(handle, 1, input.as_ptr() as *mut c_void);
+    (gain.connect_port)(handle, 2, output.as_mut_ptr() as *mut c_void);
+
+    (gain.activate.unwrap())(handle);
+    (gain.run)(handle, 0);
+    assert_eq!(output, [0.0; 4]);
+    (gain.run)(handle, 4);
+    assert_eq!(output, [0.5, 1.0, 1.5, 2.0]);
+    (gain.deactivate.unwrap())(handle);
+
+    let extension_data = gain.extension_data.unwrap();
+    assert!(!extension_data(c"urn:lv2_raw:test:ext".as_ptr()).is_null());
+    assert!(extension_data(c"urn:lv2_raw:test:unknown".as_ptr()).is_null());
+
+    let drops = DROPS.load(Ordering::SeqCst);
+    (gain.cleanup)(handle);
+    assert_eq!(DROPS.load(Ordering::SeqCst), drops + 1);
+}
+
+static LIB_CLEANUPS: AtomicUsize = AtomicUsize::new(0);
+
+struct SharedState {
+    descriptor: LV2LibDescriptor,
+    bundle_path: std::ffi::CString,
+}
+
+extern "C" fn lib_cleanup(handle: LV2LibHandle) {
+    drop(unsafe { Box::from_raw(handle as *mut SharedState) });
+    LIB_CLEANUPS.fetch_add(1, Ordering::SeqCst);
+}
+
+extern "C" fn lib_get_plugin(handle: LV2LibHandle, index: u32) -> *const LV2Descriptor {
+    let shared = unsafe { &*(handle as *const SharedState) };
+    assert_eq!(shared.bundle_path.as_c_str(), c"/tmp/lib.lv2/");
+    // Only expose the first plugin through the library descriptor.
+    if index == 0 {
+        lv2_descriptor(0)
+    } else {
+        ptr::null()
+    }
+}
+
+extern "C" fn test_lib_descriptor(
+    bundle_path: *const std::ffi::c_char,
+    _features: *const *const LV2Feature,
+) -> *const LV2LibDescriptor {
+    let shared = Box::into_raw(Box::new(SharedState {
+        descriptor: LV2LibDescriptor {
+            handle: ptr::null_mut(),
+            size: std::mem::size_of::<LV2LibDescriptor>() as u32,
+            cleanup: lib_cleanup,
+            get_plugin: lib_get_plugin,
+        },
+        bundle_path: unsafe { CStr::from_ptr(bundle_path) }.into(),
+    }));
+    unsafe {
+        (*shared).descriptor.handle = shared as LV2LibHandle;
+        &(*shared).descriptor
+    }
+}
+
+extern "C" fn null_lib_descriptor(
+    _bundle_path: *const std::ffi::c_char,
+    _features: *const *const LV2Feature,
+) -> *const LV2LibDescriptor {
+    ptr::null()
+}
+
+#[test]
+fn lib_descriptor_is_preferred() {
+    use lv2_raw::host::PluginDescriptors;
+
+    let no_features = [ptr::null::<LV2Feature>()];
+    let before = LIB_CLEANUPS.load(Ordering::SeqCst);
+    let descriptors = unsafe {
+        PluginDescriptors::new(
+            Some(test_lib_descriptor),
+            Some(lv2_descriptor),
+            c"/tmp/lib.lv2/",
+            no_features.as_ptr(),
+        )
+    }
+    .unwrap();
+    assert!(descriptors.is_lib_descriptor());
+    assert_eq!(descriptors.iter().count(), 1);
+    assert!(descriptors.find(Gain::URI).is_some());
+    assert!(descriptors.find(Refuses::URI).is_none());
+    drop(descriptors);
+    assert_eq!(LIB_CLEANUPS.load(Ordering::SeqCst), before + 1);
+
+    // A NULL library descriptor refuses to load, even with lv2_descriptor().
+    assert!(
+        unsafe {
+            PluginDescriptors::new(
+                Some(null_lib_descriptor),
+                Some(lv2_descriptor),
+                c"/tmp/lib.lv2/",
+                no_features.as_ptr(),
+            )
+        }
+        .is_none()
+    );
+
+    let descriptors = unsafe {
+        PluginDescriptors::new(
+            None,
+            Some(lv2_descriptor),
+            c"/tmp/lib.lv2/",
+            no_features.as_ptr(),
+        )
+    }
+    .unwrap();
+    assert!(!descriptors.is_lib_descriptor());
+    assert_eq!(descriptors.iter().count(), 2);
+    assert!(descriptors.find(Refuses::URI).is_some());
+
+    assert!(unsafe { PluginDescriptors::new(None, None, c"/", no_features.as_ptr()) }.is_none());
+}
+
+extern "C" fn null_uri_descriptor(index: u32) -> *const LV2Descriptor {
+    if index > 0 {
+        return lv2_descriptor(index - 1);
+    }
+    // A copy of the first descriptor, without its URI.
+    let mut descriptor = unsafe { ptr::read(lv2_descriptor(0)) };
+    descriptor.uri = ptr::null();
+    Box::leak(Box::new(descriptor))
+}
+
+#[test]
+fn null_uri() {
+    use lv2_raw::host::PluginDescriptors;
+
+    let no_features = [ptr::null::<LV2Feature>()];
+    let descriptors = unsafe {
+        PluginDescriptors::new(None, Some(null_uri_descriptor), c"/", no_features.as_ptr())
+    }
+    .unwrap();
+    assert_eq!(descriptors.iter().count(), 3);
+    assert!(descriptors.find(Gain::URI).is_some());
+    assert_eq!(
+        format!("{descriptors:?}"),
+        format!("[{:?}, {:?}]", Gain::URI, Refuses::URI)
+    );
+}
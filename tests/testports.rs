@@ -0,0 +1,98 @@
+use lv2_raw::*;
+use std::cell::UnsafeCell;
+use std::ffi::c_void;
+
+#[test]
+fn control_input() {
+    let mut port = ControlInput::new(0.5);
+    assert!(!port.is_connected());
+    assert_eq!(port.value(), 0.5);
+
+    let mut value = 2.0f32;
+    unsafe { port.connect(&mut value as *mut f32 as *mut c_void) };
+    assert!(port.is_connected());
+    assert_eq!(port.value(), 2.0);
+    assert_eq!(port.signal(), CvSignal::Constant(2.0));
+}
+
+#[test]
+fn cv_input() {
+    let mut port = CvInput::new(1.0);
+    let signal = unsafe { port.signal(4) };
+    assert_eq!(signal, CvSignal::Constant(1.0));
+    assert!(signal.is_constant());
+    assert_eq!(signal.get(100), 1.0);
+
+    let mut samples = [0.0f32, 0.25, 0.5, 0.75];
+    unsafe { port.connect(samples.as_mut_ptr() as *mut c_void) };
+    let signal = unsafe { port.signal(4) };
+    assert!(!signal.is_constant());
+    assert_eq!(signal.value(), 0.0);
+    assert_eq!(signal.get(2), 0.5);
+    // Past the end, the last value holds.
+    assert_eq!(signal.get(4), 0.75);
+    assert_eq!(
+        unsafe { port.signal(3) },
+        CvSignal::Samples(&[0.0, 0.25, 0.5])
+    );
+}
+
+#[test]
+fn cv_output() {
+    let mut port = CvOutput::new();
+    assert!(unsafe { port.samples(4) }.is_none());
+    // Unconnected outputs are not written.
+    unsafe { port.write(4, CvSignal::Constant(1.0)) };
+
+    let mut samples = [0.0f32; 4];
+    unsafe { port.connect(samples.as_mut_ptr() as *mut c_void) };
+    unsafe { port.write(4, ControlInput::new(0.5).signal()) };
+    assert_eq!(samples, [0.5; 4]);
+
+    let input = [1.0, 2.0, 3.0];
+    unsafe { port.write(4, CvSignal::Samples(&input)) };
+    assert_eq!(samples, [1.0, 2.0, 3.0, 3.0]);
+
+    unsafe { port.samples(4) }.unwrap()[0] = -1.0;
+    assert_eq!(samples[0], -1.0);
+}
+
+#[test]
+fn fade() {
+    let mut fade = Fade::new(4, true);
+    assert!(fade.is_settled());
+    assert_eq!(fade.block(2).collect::<Vec<_>>(), [1.0, 1.0]);
+
+    fade.set(false);
+    assert_eq!(fade.block(3).collect::<Vec<_>>(), [0.75, 0.5, 0.25]);
+    assert_eq!(fade.gain(), 0.25);
+    // The fade goes on in the next block, and stops at the end.
+    assert_eq!(fade.block(3).collect::<Vec<_>>(), [0.0, 0.0, 0.0]);
+    assert!(fade.is_settled());
+
+    fade.set(true);
+    fade.settle();
+    assert_eq!(fade.gain(), 1.0);
+    assert_eq!(Fade::new(0, false).block(1).len(), 1);
+}
+
+#[test]
+fn enabled_port() {
+    let mut port = EnabledPort::new(4);
+    assert!(port.is_enabled());
+    assert!(!port.is_bypassed());
+
+    // The host writes the port while the plugin holds a pointer to it.
+    let enabled = UnsafeCell::new(0.0f32);
+    unsafe { port.connect(enabled.get() as *mut c_void) };
+    assert!(!port.is_enabled());
+    assert_eq!(port.block(2).collect::<Vec<_>>(), [0.75, 0.5]);
+    assert!(!port.is_bypassed());
+    assert_eq!(port.block(2).collect::<Vec<_>>(), [0.25, 0.0]);
+    assert!(port.is_bypassed());
+
+    unsafe { *enabled.get() = 1.0 };
+    assert_eq!(port.block(1).collect::<Vec<_>>(), [0.25]);
+    port.settle();
+    assert_eq!(port.block(1).collect::<Vec<_>>(), [1.0]);
+}
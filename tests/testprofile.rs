@@ -0,0 +1,139 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{FeatureList, PortInfo, Profile, Profiler, Stimulus, UridMapper};
+use lv2_raw::plugin::plugin_descriptor;
+use lv2_raw::*;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+mod common;
+
+static NONZERO: AtomicUsize = AtomicUsize::new(0);
+static EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts the non-zero samples and the events it sees.
+struct Counter {
+    input: *const f32,
+    events: *const LV2AtomSequence,
+}
+
+impl Plugin for Counter {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:counter");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Atom, PortDirection::Input),
+    ];
+
+    fn instantiate(_: f64, _: &CStr, _: Features<'_>) -> Option<Self> {
+        Some(Counter {
+            input: ptr::null(),
+            events: ptr::null(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.events = data as *const LV2AtomSequence,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        unsafe {
+            let input = std::slice::from_raw_parts(self.input, sample_count as usize);
+            let nonzero = input.iter().filter(|&&x| x != 0.0).count();
+            NONZERO.fetch_add(nonzero, Ordering::Relaxed);
+            EVENTS.fetch_add((&*self.events).into_iter().count(), Ordering::Relaxed);
+        }
+    }
+}
+
+static COUNTER: LV2Descriptor = plugin_descriptor::<Counter>();
+
+fn profile(runs: &[u64]) -> Profile {
+    Profile {
+        uri: CString::new("urn:test").unwrap(),
+        sample_rate: 48000.0,
+        block_size: 480,
+        stimulus: Stimulus::Noise,
+        instantiate: Duration::ZERO,
+        activate: Duration::ZERO,
+        deactivate: Duration::ZERO,
+        runs: runs.iter().map(|&us| Duration::from_micros(us)).collect(),
+    }
+}
+
+#[test]
+fn statistics() {
+    let mut runs: Vec<u64> = (1..=100).collect();
+    runs.reverse();
+    let profile = profile(&runs);
+    assert_eq!(profile.budget(), Duration::from_millis(10));
+    assert_eq!(profile.mean(), Duration::from_nanos(50_500));
+    assert_eq!(profile.percentile(0.99), Duration::from_micros(99));
+    assert_eq!(profile.percentile(0.5), Duration::from_micros(50));
+    assert_eq!(profile.worst(), Duration::from_micros(100));
+    assert_eq!(profile.load(Duration::from_millis(5)), 0.5);
+    assert!(profile.to_string().contains("run() worst"));
+
+    let empty = self::profile(&[]);
+    assert_eq!(empty.mean(), Duration::ZERO);
+    assert_eq!(empty.percentile(0.99), Duration::ZERO);
+}
+
+#[test]
+fn stimuli() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper);
+    let ports = vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Atom, PortDirection::Input),
+    ];
+    for stimulus in [Stimulus::Silence, Stimulus::Noise, Stimulus::DenseMidi] {
+        let mut profiler = Profiler::new(&COUNTER, c"/", features.features(), ports.clone());
+        profiler.block_size(64).blocks(10).stimulus(stimulus);
+        NONZERO.store(0, Ordering::Relaxed);
+        EVENTS.store(0, Ordering::Relaxed);
+        let profile = profiler.run().unwrap();
+        assert_eq!(profile.runs.len(), 10);
+        assert_eq!(profile.uri.as_c_str(), c"urn:lv2_raw:test:counter");
+        assert!(profile.mean() <= profile.worst());
+
+        let nonzero = NONZERO.load(Ordering::Relaxed);
+        let events = EVENTS.load(Ordering::Relaxed);
+        match stimulus {
+            Stimulus::Silence => assert_eq!((nonzero, events), (0, 0)),
+            Stimulus::Noise => assert!(nonzero > 600 && events == 0),
+            Stimulus::DenseMidi => assert!(nonzero > 600 && events == 640),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn binary() {
+    let dir = common::temp_dir("profile");
+    let bundle = common::amp_bundle(&dir);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_lv2-profile"))
+        .args(["-n", "50", "-b", "32", "-c", "gain=2", "-s", "silence"])
+        .arg(&bundle)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout
+            .starts_with("<urn:lv2_raw:examples:amp>: 50 blocks of 32 frames at 48000 Hz, silence")
+    );
+    assert!(stdout.contains("run() p99"));
+    assert!(stdout.contains("instantiate"));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "std")]
+#![cfg(unix)]
+
+use lv2_raw::host::{Wav, WavFormat};
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+use common::{amp_bundle, delay_bundle, temp_dir};
+
+fn render(args: &[&Path]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_lv2-render"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn amp() {
+    let dir = temp_dir("render");
+    let bundle = amp_bundle(&dir);
+    let input = dir.join("in.wav");
+    let output = dir.join("out.wav");
+    let samples: Vec<f32> = (0..1000).map(|i| (i % 100) as f32 / 100.0).collect();
+    Wav {
+        sample_rate: 44100,
+        format: WavFormat::Pcm16,
+        channels: vec![samples],
+    }
+    .save(&input)
+    .unwrap();
+
+    let result = render(&[
+        Path::new("-i"),
+        &input,
+        Path::new("-c"),
+        Path::new("gain=0.5"),
+        Path::new("-b"),
+        Path::new("64"),
+        &bundle,
+        &output,
+    ]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let input = Wav::open(&input).unwrap();
+    let output = Wav::open(&output).unwrap();
+    assert_eq!(output.sample_rate, 44100);
+    assert_eq!(output.format, WavFormat::Pcm16);
+    assert_eq!(output.channels.len(), 1);
+    assert_eq!(output.frames(), 1000);
+    for (x, y) in input.channels[0].iter().zip(&output.channels[0]) {
+        assert!((x * 0.5 - y).abs() <= 1.0 / 32768.0, "{x} * 0.5 != {y}");
+    }
+}
+
+#[test]
+fn latency_is_compensated() {
+    let dir = temp_dir("render-latency");
+    let bundle = delay_bundle(&dir);
+    let input = dir.join("in.wav");
+    let output = dir.join("out.wav");
+    let samples: Vec<f32> = (0..1000).map(|i| (i % 10) as f32 / 10.0).collect();
+    Wav {
+        sample_rate: 48000,
+        format: WavFormat::Float32,
+        channels: vec![samples.clone()],
+    }
+    .save(&input)
+    .unwrap();
+
+    let result = render(&[
+        Path::new("-i"),
+        &input,
+        Path::new("-b"),
+        Path::new("100"),
+        &bundle,
+        &output,
+    ]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let output = Wav::open(&output).unwrap();
+    assert_eq!(output.channels, [samples]);
+}
+
+#[test]
+fn errors() {
+    let dir = temp_dir("render-errors");
+    let bundle = amp_bundle(&dir);
+    let output = dir.join("out.wav");
+
+    let result = render(&[Path::new("--block-size"), Path::new("0")]);
+    assert_eq!(result.status.code(), Some(2));
+
+    let result = render(&[
+        Path::new("-c"),
+        Path::new("level=1"),
+        Path::new("-l"),
+        Path::new("0.1"),
+        &bundle,
+        &output,
+    ]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("level"));
+
+    // Without inputs or a length there is nothing to render.
+    let result = render(&[&bundle, &output]);
+    assert!(!result.status.success());
+    assert!(!output.exists());
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{
+    Check, Conformance, FeatureList, Instance, PortInfo, RtCheckAllocator, RtViolationKind,
+    UridMapper, is_installed, rt_blocking, rt_check,
+};
+use lv2_raw::plugin::plugin_descriptor;
+use lv2_raw::*;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+
+#[global_allocator]
+static ALLOCATOR: RtCheckAllocator = RtCheckAllocator::new();
+
+/// Copies its input through a scratch buffer it reallocates on every run.
+struct Leaky {
+    input: *const f32,
+    output: *mut f32,
+    scratch: Vec<f32>,
+}
+
+impl Plugin for Leaky {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:leaky");
+    const PORTS: &'static [(PortType, PortDirection)] = &[
+        (PortType::Audio, PortDirection::Input),
+        (PortType::Audio, PortDirection::Output),
+    ];
+
+    fn instantiate(_: f64, _: &CStr, _: Features<'_>) -> Option<Self> {
+        Some(Leaky {
+            input: ptr::null(),
+            output: ptr::null_mut(),
+            scratch: Vec::new(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut c_void) {
+        match port {
+            0 => self.input = data as *const f32,
+            1 => self.output = data as *mut f32,
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, sample_count: u32) {
+        let n = sample_count as usize;
+        unsafe {
+            self.scratch = std::slice::from_raw_parts(self.input, n).to_vec();
+            ptr::copy_nonoverlapping(self.scratch.as_ptr(), self.output, n);
+        }
+    }
+}
+
+/// Maps a URI in `run()`.
+struct Mapping {
+    map: LV2UridMap,
+}
+
+impl Plugin for Mapping {
+    const URI: Uri = Uri::new(c"urn:lv2_raw:test:mapping");
+    const PORTS: &'static [(PortType, PortDirection)] = &[];
+
+    fn instantiate(_: f64, _: &CStr, features: Features<'_>) -> Option<Self> {
+        Some(Mapping {
+            map: *features.require::<LV2UridMap>().ok()?,
+        })
+    }
+
+    fn connect_port(&mut self, _: u32, _: *mut c_void) {}
+
+    fn run(&mut self, _: u32) {
+        self.map.map(LV2_ATOM__FLOAT);
+    }
+}
+
+static LEAKY: LV2Descriptor = plugin_descriptor::<Leaky>();
+static MAPPING: LV2Descriptor = plugin_descriptor::<Mapping>();
+
+fn audio_ports() -> Vec<PortInfo> {
+    vec![
+        PortInfo::new(PortType::Audio, PortDirection::Input),
+        PortInfo::new(PortType::Audio, PortDirection::Output),
+    ]
+}
+
+#[test]
+fn sections() {
+    assert!(is_installed());
+    let (value, report) = rt_check(|| 6 * 7);
+    assert_eq!(value, 42);
+    assert!(report.is_ok(), "{report}");
+
+    let (inner, outer) = rt_check(|| {
+        rt_blocking("sleep");
+        let (boxed, inner) = rt_check(|| Box::new(1u64));
+        drop(boxed);
+        inner
+    });
+    assert_eq!(inner.count(), 1);
+    assert_eq!(
+        inner.violations[0].kind,
+        RtViolationKind::Allocation { size: 8 }
+    );
+    let kinds: Vec<_> = outer.violations.iter().map(|v| v.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            RtViolationKind::Blocking { call: "sleep" },
+            RtViolationKind::Deallocation { size: 8 },
+        ]
+    );
+
+    let ((), report) = rt_check(|| {
+        for i in 0..100 {
+            drop(std::hint::black_box(vec![0u8; i + 1]));
+        }
+    });
+    assert_eq!(report.count(), 200);
+    assert_eq!(report.omitted, 200 - report.violations.len());
+
+    // Outside of a section nothing is recorded.
+    drop(vec![0u8; 16]);
+    let ((), report) = rt_check(|| ());
+    assert!(report.is_ok());
+}
+
+#[test]
+fn run_checked() {
+    let features = FeatureList::new();
+    let mut instance =
+        Instance::from_descriptor(&LEAKY, 48000.0, c"/", features.features(), audio_ports())
+            .unwrap();
+    let mut input = [0.5f32; 64];
+    let mut output = [0.0f32; 64];
+    unsafe {
+        instance
+            .connect_port(0, input.as_mut_ptr() as *mut c_void)
+            .unwrap();
+        instance
+            .connect_port(1, output.as_mut_ptr() as *mut c_void)
+            .unwrap();
+    }
+    instance.activate().unwrap();
+    let report = instance.run_checked(64).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(
+        report.violations[0].kind,
+        RtViolationKind::Allocation { size: 256 }
+    );
+    assert!(report.to_string().contains("Leaky"), "{report}");
+
+    // The second run also frees the first scratch buffer.
+    let report = instance.run_checked(64).unwrap();
+    assert!(
+        report
+            .violations
+            .iter()
+            .any(|v| v.kind == RtViolationKind::Deallocation { size: 256 })
+    );
+}
+
+#[test]
+fn blocking_features() {
+    let mapper = UridMapper::new();
+    let mut features = FeatureList::new();
+    features.urid_map(&mapper);
+    let mut instance =
+        Instance::from_descriptor(&MAPPING, 48000.0, c"/", features.features(), Vec::new())
+            .unwrap();
+    instance.activate().unwrap();
+    let report = instance.run_checked(16).unwrap();
+    assert_eq!(
+        report.violations[0].kind,
+        RtViolationKind::Blocking { call: "urid:map" }
+    );
+}
+
+#[test]
+fn conformance() {
+    let features = FeatureList::new();
+    let report = Conformance::new(&LEAKY, c"/", features.features(), audio_ports()).run();
+    assert!(report.is_ok(), "{report}");
+    assert!(!report.checks.contains(&Check::RealTime));
+
+    let mut harness = Conformance::new(&LEAKY, c"/", features.features(), audio_ports());
+    harness.hard_rt_capable();
+    let report = harness.run();
+    assert!(report.checks.contains(&Check::RealTime));
+    assert!(
+        report
+            .violations_of(Check::RealTime)
+            .any(|v| v.message.starts_with("run() allocated"))
+    );
+    assert_eq!(
+        report.violations.len(),
+        report.violations_of(Check::RealTime).count()
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::MidiFile;
+
+#[test]
+fn tracks_and_tempo() {
+    #[rustfmt::skip]
+    let track_one = [
+        // Tempo of 1 s per quarter note at tick 0.
+        0x00, 0xff, 0x51, 0x03, 0x0f, 0x42, 0x40,
+        // Note on at 0, then a running status note off at 96 ticks.
+        0x00, 0x90, 0x3c, 0x64,
+        0x60, 0x3c, 0x00,
+        // A system exclusive message at 192 ticks.
+        0x60, 0xf0, 0x03, 0x7e, 0x09, 0xf7,
+        0x00, 0xff, 0x2f, 0x00,
+    ];
+    #[rustfmt::skip]
+    let track_two = [
+        // A program change at 288 ticks (a two byte delta time).
+        0x82, 0x20, 0xc0, 0x05,
+        0x00, 0xff, 0x2f, 0x00,
+    ];
+    let mut bytes = b"MThd\0\0\0\x06\0\x01\0\x02\0\xc0".to_vec();
+    for track in [&track_one[..], &track_two[..]] {
+        bytes.extend_from_slice(b"MTrk");
+        bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(track);
+    }
+
+    let midi = MidiFile::from_bytes(&bytes).unwrap();
+    let events: Vec<_> = midi
+        .events
+        .iter()
+        .map(|e| (e.time, e.data.as_slice()))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            (0.0, &[0x90, 0x3c, 0x64][..]),
+            (0.5, &[0x90, 0x3c, 0x00][..]),
+            (1.0, &[0xf0, 0x7e, 0x09, 0xf7][..]),
+            (1.5, &[0xc0, 0x05][..]),
+        ]
+    );
+    assert_eq!(midi.duration(), 1.5);
+    assert!(MidiFile::from_bytes(&bytes[..bytes.len() - 3]).is_err());
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::turtle::*;
+use lv2_raw::*;
+
+const BASE: &str = "file:///lv2/amp.lv2/amp.ttl";
+
+#[test]
+fn syntax() {
+    let graph = Graph::parse(
+        r#"
+        @prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        PREFIX ex: <http://example.org/ns#>
+        # A comment, with <an IRI> and "a string".
+
+        <#amp> a lv2:Plugin, lv2:AmplifierPlugin ;
+            rdfs:label "Amp"@en , 'Ampli\u00E9' ;
+            ex:long """two
+lines with "quotes"""" ;
+            ex:numbers ( 1 -2.5 3e2 +4 ) ;
+            ex:flag true ;
+            ex:empty () ;
+            ex:typed "7"^^<http://www.w3.org/2001/XMLSchema#int> ;
+            ex:local ex:a.b\-c ;
+            lv2:port [
+                a lv2:InputPort ;
+                lv2:index 0
+            ] , _:out ; .
+
+        _:out lv2:index 1 .
+        [] ex:anonymous ex:subject .
+        @base <http://example.org/base/> .
+        <../other> ex:relative <sub/x> .
+        "#,
+        BASE,
+    )
+    .unwrap();
+
+    let amp = Term::iri("file:///lv2/amp.lv2/amp.ttl#amp");
+    assert!(graph.contains(&amp, RDF__TYPE, &LV2_CORE__PLUGIN.into()));
+    assert!(graph.contains(&amp, RDF__TYPE, &LV2_CORE__AMPLIFIERPLUGIN.into()));
+
+    let labels: Vec<_> = graph.objects(&amp, RDFS__LABEL).cloned().collect();
+    assert_eq!(labels.len(), 2);
+    assert_eq!(
+        labels[0].as_literal().unwrap().language.as_deref(),
+        Some("en")
+    );
+    assert_eq!(labels[1].as_str(), Some("Amplié"));
+
+    let long = graph.object(&amp, "http://example.org/ns#long").unwrap();
+    assert_eq!(long.as_str(), Some("two\nlines with \"quotes\""));
+
+    let list = graph
+        .list(graph.object(&amp, "http://example.org/ns#numbers").unwrap())
+        .unwrap();
+    let numbers: Vec<_> = list.iter().map(|t| t.as_f64().unwrap()).collect();
+    assert_eq!(numbers, [1.0, -2.5, 300.0, 4.0]);
+    assert_eq!(
+        list[0].as_literal().unwrap().datatype.as_deref(),
+        Some(XSD__INTEGER.as_str())
+    );
+    assert_eq!(list[3].as_i64(), Some(4));
+
+    let flag = graph.object(&amp, "http://example.org/ns#flag").unwrap();
+    assert_eq!(flag.as_bool(), Some(true));
+    let empty = graph.object(&amp, "http://example.org/ns#empty").unwrap();
+    assert_eq!(graph.list(empty), Some(vec![]));
+    let typed = graph.object(&amp, "http://example.org/ns#typed").unwrap();
+    assert_eq!(typed.as_i64(), Some(7));
+    assert_eq!(
+        graph.object(&amp, "http://example.org/ns#local"),
+        Some(&Term::iri("http://example.org/ns#a.b-c"))
+    );
+
+    let ports: Vec<_> = graph.objects(&amp, LV2_CORE___PORT).collect();
+    assert_eq!(ports.len(), 2);
+    assert!(ports.iter().all(|p| p.is_blank()));
+    assert_eq!(
+        graph.object(ports[0], LV2_CORE__INDEX).unwrap().as_i64(),
+        Some(0)
+    );
+    assert_eq!(
+        graph.object(ports[1], LV2_CORE__INDEX).unwrap().as_i64(),
+        Some(1)
+    );
+
+    assert!(graph.contains(
+        &Term::iri("http://example.org/other"),
+        "http://example.org/ns#relative",
+        &Term::iri("http://example.org/base/sub/x"),
+    ));
+}
+
+#[test]
+fn blank_nodes_are_local_to_documents() {
+    let mut graph = Graph::new();
+    graph.load("_:a <urn:p> 1 .", BASE).unwrap();
+    graph.load("_:a <urn:p> 2 .", BASE).unwrap();
+    let subjects: Vec<_> = graph.triples().iter().map(|t| &t.subject).collect();
+    assert_ne!(subjects[0], subjects[1]);
+}
+
+#[test]
+fn resolve() {
+    let base = "http://a/b/c/d;p?q";
+    for (reference, expected) in [
+        ("g", "http://a/b/c/g"),
+        ("./g", "http://a/b/c/g"),
+        ("g/", "http://a/b/c/g/"),
+        ("/g", "http://a/g"),
+        ("//g", "http://g"),
+        ("?y", "http://a/b/c/d;p?y"),
+        ("#s", "http://a/b/c/d;p?q#s"),
+        ("", "http://a/b/c/d;p?q"),
+        ("..", "http://a/b/"),
+        ("../g", "http://a/b/g"),
+        ("../../g", "http://a/g"),
+        ("urn:x", "urn:x"),
+    ] {
+        assert_eq!(resolve_iri(base, reference), expected, "{reference}");
+    }
+}
+
+#[test]
+fn numbers() {
+    let graph = Graph::parse("<a> <b> 1.e5, 2.5, 3.", BASE).unwrap();
+    let subject = Term::iri("file:///lv2/amp.lv2/a");
+    let values: Vec<_> = graph
+        .objects(&subject, "file:///lv2/amp.lv2/b")
+        .map(|t| {
+            let literal = t.as_literal().unwrap();
+            (literal.value.as_str(), literal.datatype.clone().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            ("1.e5", XSD__DOUBLE.as_str().to_owned()),
+            ("2.5", XSD__DECIMAL.as_str().to_owned()),
+            ("3", XSD__INTEGER.as_str().to_owned()),
+        ]
+    );
+    // Without exponent digits, the '.' ends the statement.
+    assert!(Graph::parse("<a> <b> 1.e .", BASE).is_err());
+}
+
+#[test]
+fn trailing_dots_in_names() {
+    let graph = Graph::parse(
+        "@prefix ex: <http://example.org/> .\n<a> <b> ex:c\\. , ex:d.e. <f> <g> ex:h .",
+        BASE,
+    )
+    .unwrap();
+    let objects: Vec<_> = graph
+        .objects(&Term::iri("file:///lv2/amp.lv2/a"), "file:///lv2/amp.lv2/b")
+        .cloned()
+        .collect();
+    assert_eq!(
+        objects,
+        [
+            Term::iri("http://example.org/c."),
+            Term::iri("http://example.org/d.e")
+        ]
+    );
+    assert_eq!(graph.len(), 3);
+}
+
+#[test]
+fn nesting_limit() {
+    let nested = |depth| {
+        format!(
+            "<a> <b> {}1{} .",
+            "[ <c> ".repeat(depth),
+            " ]".repeat(depth)
+        )
+    };
+    assert!(Graph::parse(&nested(32), BASE).is_ok());
+    let err = Graph::parse(&nested(100_000), BASE).unwrap_err();
+    assert_eq!(err.message, "too deeply nested");
+    let err = Graph::parse(&format!("<a> <b> {} .", "(".repeat(100_000)), BASE).unwrap_err();
+    assert_eq!(err.message, "too deeply nested");
+}
+
+#[test]
+fn errors() {
+    let err = Graph::parse("<a> <b> <c>", BASE).unwrap_err();
+    assert_eq!((err.line, err.column), (1, 12));
+    assert_eq!(err.message, "expected '.', found end of input");
+
+    let err = Graph::parse("\n\n  <a> nope:b <c> .", BASE).unwrap_err();
+    assert_eq!((err.line, err.column), (3, 7));
+    assert_eq!(err.to_string(), "3:7: undefined prefix 'nope:'");
+
+    assert!(Graph::parse("<a> <b> \"open .", BASE).is_err());
+    assert!(Graph::parse("<a> <b> ( 1 2 .", BASE).is_err());
+    assert!(Graph::parse("<a> <b> \"\\q\" .", BASE).is_err());
+}
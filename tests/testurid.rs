@@ -0,0 +1,205 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::UridMapper;
+use lv2_raw::*;
+use std::ffi::CStr;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn map_and_unmap_through_features() {
+    let mapper = UridMapper::new();
+    let map = mapper.map_feature();
+    let unmap = mapper.unmap_feature();
+
+    let uri = c"http://lv2plug.in/ns/ext/atom#Sequence";
+    let id = (map.map)(map.handle, uri.as_ptr());
+    assert_ne!(id, 0);
+    assert_eq!((map.map)(map.handle, uri.as_ptr()), id);
+    assert_eq!(mapper.map(uri), id);
+
+    let back = (unmap.unmap)(unmap.handle, id);
+    assert!(!back.is_null());
+    assert_eq!(unsafe { CStr::from_ptr(back) }, uri);
+
+    assert!((unmap.unmap)(unmap.handle, 0).is_null());
+    assert!((unmap.unmap)(unmap.handle, id + 1).is_null());
+    assert_eq!((map.map)(map.handle, std::ptr::null()), 0);
+}
+
+#[test]
+fn unmapped_strings_are_stable() {
+    let mapper = UridMapper::new();
+    let first = mapper.map(c"urn:first");
+    let ptr = mapper.unmap(first).unwrap().as_ptr();
+
+    // Force the table to grow well past its initial capacity.
+    for i in 0..1000 {
+        let uri = std::ffi::CString::new(format!("urn:test:{i}")).unwrap();
+        mapper.map(&uri);
+    }
+
+    // The mapper may move, but the strings it handed out must not.
+    let moved = mapper;
+    assert_eq!(moved.unmap(first).unwrap().as_ptr(), ptr);
+    assert_eq!(moved.len(), 1001);
+}
+
+#[test]
+fn shared_between_threads() {
+    let mapper = Arc::new(UridMapper::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mapper = Arc::clone(&mapper);
+            thread::spawn(move || {
+                (0..100)
+                    .map(|i| {
+                        let uri = std::ffi::CString::new(format!("urn:shared:{i}")).unwrap();
+                        mapper.map(&uri)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    for ids in &results[1..] {
+        assert_eq!(ids, &results[0]);
+    }
+    assert!(results[0].iter().all(|&id| id != 0));
+    assert_eq!(mapper.len(), 100);
+}
+
+#[test]
+fn uri_constants() {
+    assert_eq!(
+        LV2_ATOM__SEQUENCE.as_str(),
+        "http://lv2plug.in/ns/ext/atom#Sequence"
+    );
+    assert_eq!(LV2_URID__MAP, "http://lv2plug.in/ns/ext/urid#map");
+    assert_eq!(LV2_MIDI__MIDIEVENT.to_bytes_with_nul().last(), Some(&0));
+    assert_eq!(
+        format!("{LV2_TIME__POSITION}"),
+        "http://lv2plug.in/ns/ext/time#Position"
+    );
+
+    let mapper = UridMapper::new();
+    let map = mapper.map_feature();
+    let id = map.map(LV2_ATOM__SEQUENCE);
+    assert_eq!(id, mapper.map(LV2_ATOM__SEQUENCE));
+    assert_eq!(
+        mapper.unmap_feature().unmap(id),
+        Some(LV2_ATOM__SEQUENCE.as_cstr())
+    );
+
+    let data = &map as *const LV2UridMap as *mut std::ffi::c_void;
+    let feature = LV2Feature {
+        uri: LV2_URID__MAP.as_ptr(),
+        data,
+    };
+    unsafe {
+        assert!(feature.has_uri(LV2_URID__MAP));
+        assert!(!feature.has_uri(LV2_URID__UNMAP));
+        assert!(LV2_URID__MAP.matches(feature.uri));
+        assert!(!LV2_URID__MAP.matches(std::ptr::null()));
+    }
+}
+
+const TEST_CUSTOM: Uri = Uri::new(c"urn:lv2_raw:test#custom");
+
+lv2_raw::urid_table! {
+    struct TestUrids {
+        atom_sequence: LV2_ATOM__SEQUENCE,
+        midi_event: LV2_MIDI__MIDIEVENT,
+        time_position: LV2_TIME__POSITION,
+        custom: TEST_CUSTOM,
+    }
+}
+
+extern "C" fn refuse_custom(_handle: LV2UridMapHandle, uri: *const std::ffi::c_char) -> LV2Urid {
+    if unsafe { TEST_CUSTOM.matches(uri) } {
+        0
+    } else {
+        1
+    }
+}
+
+#[test]
+fn urid_table() {
+    let mapper = UridMapper::new();
+    let unmap = mapper.unmap_feature();
+    let urids = TestUrids::new(&mapper.map_feature(), Some(&unmap)).unwrap();
+    assert_eq!(urids.atom_sequence, mapper.map(LV2_ATOM__SEQUENCE));
+    assert_eq!(urids.midi_event, mapper.map(LV2_MIDI__MIDIEVENT));
+    assert_eq!(urids.time_position, mapper.map(LV2_TIME__POSITION));
+    assert_eq!(urids.custom, mapper.map(TEST_CUSTOM));
+
+    let debug = format!("{urids:?}");
+    assert!(
+        debug.starts_with("TestUrids { atom_sequence: 1 <http://lv2plug.in/ns/ext/atom#Sequence>")
+    );
+    assert!(debug.contains("custom: 4 <urn:lv2_raw:test#custom>"));
+
+    let plain = TestUrids::new(&mapper.map_feature(), None).unwrap();
+    assert!(format!("{plain:?}").contains("custom: 4 }"));
+
+    let refusing = LV2UridMap {
+        handle: std::ptr::null_mut(),
+        map: refuse_custom,
+    };
+    let err = TestUrids::new(&refusing, None).unwrap_err();
+    assert_eq!(err.field, "custom");
+    assert_eq!(err.uri, TEST_CUSTOM);
+}
+
+extern "C" fn legacy_uri_to_id(
+    callback_data: LV2UriMapCallbackData,
+    map: *const std::ffi::c_char,
+    uri: *const std::ffi::c_char,
+) -> u32 {
+    assert!(map.is_null());
+    let mapper = unsafe { &*(callback_data as *const UridMapper) };
+    mapper.map(unsafe { CStr::from_ptr(uri) })
+}
+
+#[test]
+fn urid_map_adapter() {
+    let mapper = UridMapper::new();
+    let legacy = LV2UriMapFeature {
+        callback_data: &mapper as *const UridMapper as *mut std::ffi::c_void,
+        uri_to_id: legacy_uri_to_id,
+    };
+    let map = mapper.map_feature();
+
+    let legacy_feature = LV2Feature {
+        uri: LV2_URI_MAP_URI.as_ptr(),
+        data: &legacy as *const LV2UriMapFeature as *mut std::ffi::c_void,
+    };
+    let map_feature = LV2Feature {
+        uri: LV2_URID__MAP.as_ptr(),
+        data: &map as *const LV2UridMap as *mut std::ffi::c_void,
+    };
+
+    // Only uri-map: the adapter falls back to it.
+    let features = [&legacy_feature as *const LV2Feature, std::ptr::null()];
+    let adapter = unsafe { UridMapAdapter::from_features(features.as_ptr()) }.unwrap();
+    assert!(adapter.is_legacy());
+    let id = adapter.map(LV2_ATOM__SEQUENCE);
+    assert_eq!(id, mapper.map(LV2_ATOM__SEQUENCE));
+    let urids = TestUrids::new(&adapter, None).unwrap();
+    assert_eq!(urids.atom_sequence, id);
+
+    // Both: urid#map wins.
+    let features = [
+        &legacy_feature as *const LV2Feature,
+        &map_feature as *const LV2Feature,
+        std::ptr::null(),
+    ];
+    let adapter = unsafe { UridMapAdapter::from_features(features.as_ptr()) }.unwrap();
+    assert!(!adapter.is_legacy());
+    assert_eq!(adapter.map(LV2_ATOM__SEQUENCE), id);
+
+    // Neither.
+    let features = [std::ptr::null::<LV2Feature>()];
+    assert!(unsafe { UridMapAdapter::from_features(features.as_ptr()) }.is_none());
+}
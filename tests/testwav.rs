@@ -0,0 +1,98 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::host::{Wav, WavError, WavFormat};
+
+fn sine() -> Wav {
+    let left: Vec<f32> = (0..100).map(|i| (i as f32 * 0.1).sin() * 0.9).collect();
+    let right = left.iter().map(|x| -x).collect();
+    Wav {
+        sample_rate: 44100,
+        format: WavFormat::Float32,
+        channels: vec![left, right],
+    }
+}
+
+#[test]
+fn round_trip() {
+    for (format, tolerance) in [
+        (WavFormat::Pcm8, 1.0 / 256.0),
+        (WavFormat::Pcm16, 1.0 / 65536.0),
+        (WavFormat::Pcm24, 1.0 / 16_777_216.0),
+        (WavFormat::Pcm32, 1e-7),
+        (WavFormat::Float32, 0.0),
+        (WavFormat::Float64, 0.0),
+    ] {
+        let mut wav = sine();
+        wav.format = format;
+        let bytes = wav.to_bytes().unwrap();
+        assert_eq!(
+            bytes.len(),
+            44 + 200 * usize::from(format.bits() / 8),
+            "{format:?}"
+        );
+        let read = Wav::from_bytes(&bytes).unwrap();
+        assert_eq!(read.sample_rate, 44100);
+        assert_eq!(read.format, format);
+        assert_eq!(read.frames(), 100);
+        for (a, b) in read
+            .channels
+            .iter()
+            .flatten()
+            .zip(wav.channels.iter().flatten())
+        {
+            assert!((a - b).abs() <= tolerance, "{format:?}: {a} != {b}");
+        }
+    }
+}
+
+#[test]
+fn extensible() {
+    // Mono 24-bit WAVE_FORMAT_EXTENSIBLE with a LIST chunk before the data.
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF\0\0\0\0WAVEfmt ");
+    bytes.extend_from_slice(&40u32.to_le_bytes());
+    bytes.extend_from_slice(&0xfffeu16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&48000u32.to_le_bytes());
+    bytes.extend_from_slice(&(48000u32 * 3).to_le_bytes());
+    bytes.extend_from_slice(&3u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(&22u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(&4u32.to_le_bytes());
+    bytes.extend_from_slice(&[
+        1, 0, 0, 0, 0, 0, 0x10, 0, 0x80, 0, 0, 0xaa, 0, 0x38, 0x9b, 0x71,
+    ]);
+    bytes.extend_from_slice(b"LIST\x03\0\0\0abc\0");
+    bytes.extend_from_slice(b"data\x09\0\0\0");
+    bytes.extend_from_slice(&[0, 0, 0x40, 0, 0, 0xc0, 0xff, 0xff, 0x7f]);
+
+    let wav = Wav::from_bytes(&bytes).unwrap();
+    assert_eq!(wav.format, WavFormat::Pcm24);
+    assert_eq!(wav.sample_rate, 48000);
+    assert_eq!(wav.channels, [vec![0.5, -0.5, 8_388_607.0 / 8_388_608.0]]);
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        Wav::from_bytes(b"RIFF\0\0\0\0AVI "),
+        Err(WavError::Invalid(_))
+    ));
+    let mut wav = sine();
+    wav.format = WavFormat::Pcm16;
+    let mut bytes = wav.to_bytes().unwrap();
+    // Claim 12-bit samples.
+    bytes[34] = 12;
+    assert!(matches!(
+        Wav::from_bytes(&bytes),
+        Err(WavError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn unequal_channels() {
+    let mut wav = sine();
+    wav.channels[1].pop();
+    assert!(matches!(wav.to_bytes(), Err(WavError::Invalid(_))));
+}
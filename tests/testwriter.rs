@@ -0,0 +1,195 @@
+#![cfg(feature = "std")]
+
+use lv2_raw::bundle::{
+    Bundle, PluginDescription, PortDescription, UiDescription, data_turtle, manifest_turtle,
+    write_bundle,
+};
+use lv2_raw::turtle::{Graph, Object, RDFS__LABEL, Term, Writer};
+use lv2_raw::*;
+use std::path::PathBuf;
+
+mod common;
+use common::temp_dir;
+
+fn synth() -> PluginDescription {
+    PluginDescription {
+        uri: "urn:test:synth".to_owned(),
+        name: Some("Test \"Synth\"".to_owned()),
+        binary: Some(PathBuf::from("synth.so")),
+        data_files: vec![PathBuf::from("synth.ttl")],
+        ports: vec![
+            PortDescription {
+                index: 0,
+                symbol: "events".to_owned(),
+                name: Some("Events".to_owned()),
+                port_type: Some(PortType::Atom),
+                direction: Some(PortDirection::Input),
+                buffer_type: Some(LV2_ATOM__SEQUENCE.as_str().to_owned()),
+                supports: vec![LV2_MIDI__MIDIEVENT.as_str().to_owned()],
+                ..Default::default()
+            },
+            PortDescription {
+                index: 1,
+                symbol: "level".to_owned(),
+                name: Some("Level".to_owned()),
+                port_type: Some(PortType::Control),
+                direction: Some(PortDirection::Input),
+                default: Some(0.25),
+                minimum: Some(-90.0),
+                maximum: Some(f32::INFINITY),
+                properties: vec![LV2_CORE__CONNECTIONOPTIONAL.as_str().to_owned()],
+                ..Default::default()
+            },
+            PortDescription {
+                index: 2,
+                symbol: "out".to_owned(),
+                port_type: Some(PortType::Audio),
+                direction: Some(PortDirection::Output),
+                ..Default::default()
+            },
+        ],
+        required_features: vec![LV2_URID__MAP.as_str().to_owned()],
+        optional_features: vec![LV2_CORE__HARDRTCAPABLE.as_str().to_owned()],
+        extension_data: vec![LV2_STATE__INTERFACE.as_str().to_owned()],
+        uis: vec![UiDescription {
+            uri: "urn:test:synth#ui".to_owned(),
+            class: Some(LV2_UI__X11UI.as_str().to_owned()),
+            binary: Some(PathBuf::from("synth ui.so")),
+        }],
+    }
+}
+
+#[test]
+fn bundle_round_trip() {
+    let dir = temp_dir("writer").join("synth.lv2");
+    let plugins = [
+        synth(),
+        PluginDescription {
+            uri: "urn:test:other".to_owned(),
+            binary: Some(PathBuf::from("synth.so")),
+            ..Default::default()
+        },
+    ];
+    write_bundle(&dir, &plugins).unwrap();
+    assert!(dir.join("synth.ttl").is_file());
+    assert!(dir.join("plugin.ttl").is_file());
+
+    let bundle = Bundle::load(&dir).unwrap();
+    assert_eq!(bundle.plugins.len(), 2);
+
+    // Paths come back absolute.
+    let mut expected = synth();
+    expected.binary = Some(dir.join("synth.so"));
+    expected.data_files = vec![dir.join("synth.ttl")];
+    expected.uis[0].binary = Some(dir.join("synth ui.so"));
+    assert_eq!(bundle.plugins[0], expected);
+
+    let other = bundle.plugin("urn:test:other").unwrap();
+    assert_eq!(other.data_files, [dir.join("plugin.ttl")]);
+    assert!(other.ports.is_empty());
+}
+
+#[test]
+fn generated_text() {
+    let synth = synth();
+    let manifest = manifest_turtle(std::slice::from_ref(&synth));
+    assert!(manifest.contains("@prefix lv2: <http://lv2plug.in/ns/lv2core#> .\n"));
+    assert!(manifest.contains(
+        "<urn:test:synth>\n    a lv2:Plugin ;\n    lv2:binary <synth.so> ;\n    rdfs:seeAlso <synth.ttl> .\n"
+    ));
+    assert!(manifest.contains("a ui:X11UI ;\n    lv2:binary <synth%20ui.so> .\n"));
+
+    let data = data_turtle([&synth]);
+    assert!(data.contains("doap:name \"Test \\\"Synth\\\"\""));
+    assert!(data.contains("lv2:requiredFeature urid:map"));
+    assert!(data.contains("atom:supports midi:MidiEvent"));
+    assert!(data.contains("lv2:default 0.25 ;"));
+    assert!(data.contains("lv2:minimum -90.0 ;"));
+    assert!(data.contains("lv2:maximum \"INF\"^^<http://www.w3.org/2001/XMLSchema#double>"));
+    assert!(data.contains("lv2:index 2 ;"));
+    Graph::parse(&data, "file:///synth.lv2/synth.ttl").unwrap();
+}
+
+#[test]
+fn writer() {
+    let mut writer = Writer::new();
+    writer.prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#");
+    let subject = Term::iri("http://example.org/a b");
+    writer.statement(
+        &subject,
+        &[
+            (
+                RDFS__LABEL.as_str().to_owned(),
+                vec![
+                    Term::string("two\nlines").into(),
+                    Object::Term(Term::Literal(turtle::Literal {
+                        value: "hallo".to_owned(),
+                        datatype: None,
+                        language: Some("de".to_owned()),
+                    })),
+                ],
+            ),
+            (
+                "http://example.org/nested".to_owned(),
+                vec![
+                    Object::Node(vec![(
+                        "http://example.org/value".to_owned(),
+                        vec![Term::typed("1.5e3", turtle::XSD__DOUBLE.as_str()).into()],
+                    )]),
+                    Object::Node(Vec::new()),
+                ],
+            ),
+        ],
+    );
+    writer.statement(&Term::iri("http://example.org/empty"), &[]);
+    let text = writer.finish();
+    assert!(text.contains("<http://example.org/a\\u0020b>"));
+    assert!(!text.contains("empty"));
+
+    let graph = Graph::parse(&text, "http://example.org/").unwrap();
+    assert_eq!(graph.len(), 5);
+    let labels: Vec<_> = graph.objects(&subject, RDFS__LABEL).collect();
+    assert_eq!(labels[0].as_str(), Some("two\nlines"));
+    assert_eq!(
+        labels[1].as_literal().unwrap().language.as_deref(),
+        Some("de")
+    );
+    let nested = graph.object(&subject, "http://example.org/nested").unwrap();
+    let value = graph.object(nested, "http://example.org/value").unwrap();
+    assert_eq!(value.as_f64(), Some(1500.0));
+}
+
+#[test]
+fn numbers_round_trip() {
+    let predicate = "http://example.org/value";
+    for (value, datatype) in [
+        ("1.e5", turtle::XSD__DOUBLE),
+        ("-2.5E-3", turtle::XSD__DOUBLE),
+        (".5e1", turtle::XSD__DOUBLE),
+        ("3.", turtle::XSD__DECIMAL),
+        ("-7", turtle::XSD__INTEGER),
+    ] {
+        let mut writer = Writer::new();
+        let subject = Term::iri("http://example.org/a");
+        writer.statement(
+            &subject,
+            &[(
+                predicate.to_owned(),
+                vec![Term::typed(value, datatype.as_str()).into()],
+            )],
+        );
+        let text = writer.finish();
+        let graph = Graph::parse(&text, "http://example.org/").unwrap();
+        let literal = graph
+            .object(&subject, predicate)
+            .unwrap()
+            .as_literal()
+            .unwrap();
+        assert_eq!(literal.value, value, "{text}");
+        assert_eq!(
+            literal.datatype.as_deref(),
+            Some(datatype.as_str()),
+            "{text}"
+        );
+    }
+}